@@ -2,17 +2,24 @@ use itertools::MultiUnzip;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote};
 use soroban_env_common::Symbol;
-use syn::{spanned::Spanned, DataEnum, Error, Ident, Path};
+use syn::{spanned::Spanned, DataEnum, Error, Fields, Generics, Ident, Path};
 
 use stellar_xdr::{
-    ScSpecEntry, ScSpecTypeDef, ScSpecUdtUnionCaseV0, ScSpecUdtUnionV0, StringM, WriteXdr,
+    ScSpecEntry, ScSpecTypeDef, ScSpecTypeTuple, ScSpecTypeUdt, ScSpecUdtStructFieldV0,
+    ScSpecUdtStructV0, ScSpecUdtUnionCaseV0, ScSpecUdtUnionV0, StringM, VecM, WriteXdr,
 };
 
-use crate::map_type::map_type;
+use crate::{
+    generics::{
+        spec_name, type_params, uses_type_params, val_predicates, where_clause, xdr_predicates,
+    },
+    map_type::map_type_generic,
+};
 
 pub fn derive_type_enum(
     path: &Path,
     enum_ident: &Ident,
+    generics: &Generics,
     data: &DataEnum,
     spec: bool,
     lib: &Option<String>,
@@ -20,6 +27,14 @@ pub fn derive_type_enum(
     // Collect errors as they are encountered and emit them at the end.
     let mut errors = Vec::<Error>::new();
 
+    let params = type_params(generics).unwrap_or_else(|e| {
+        errors.extend(e);
+        vec![]
+    });
+
+    // Structs describing the fields of variants with named fields.
+    let mut spec_structs = Vec::<ScSpecUdtStructV0>::new();
+
     let variants = &data.variants;
     let (spec_cases, discriminant_consts, try_froms, intos, try_from_xdrs, into_xdrs): (Vec<_>, Vec<_>, Vec<_>, Vec<_>, Vec<_>, Vec<_>) = variants
         .iter()
@@ -29,18 +44,17 @@ pub fn derive_type_enum(
             // Should have a separate derive for those types of enums that maps
             // to an integer type only.
             // TODO: Use attributes tagged on variant to control whether field is included.
-            // TODO: Support multi-field enum variants.
-            // TODO: Or, error on multi-field enum variants.
             // TODO: Handle field names longer than a symbol. Hash the name? Truncate the name?
             let ident = &v.ident;
             let name = &ident.to_string();
             if let Err(e) = Symbol::try_from_str(name) {
                 errors.push(Error::new(ident.span(), format!("enum variant name {}", e)));
             }
-            if v.fields.len() > 1 {
-                errors.push(Error::new(v.fields.span(), "enum variant name {} has too many tuple values, max 1 supported"));
-            }
-            let field = v.fields.iter().next();
+            let field_count_usize = v.fields.len();
+            let field_count_u32: u32 = field_count_usize.try_into().unwrap_or_else(|_| {
+                errors.push(Error::new(v.fields.span(), "enum variant has too many fields exceeding u32::MAX"));
+                0
+            });
             let discriminant_const_sym_ident = format_ident!("DISCRIMINANT_SYM_{}", name.to_uppercase());
             let discriminant_const_u64_ident = format_ident!("DISCRIMINANT_U64_{}", name.to_uppercase());
             let discriminant_const_sym = quote! {
@@ -53,10 +67,189 @@ pub fn derive_type_enum(
                 #discriminant_const_sym
                 #discriminant_const_u64
             };
-            if let Some(f) = field {
+            if let Fields::Named(_) = v.fields {
+                // Variants with named fields have as their value a map of the
+                // fields, the same as a struct. The fields are described in the
+                // spec by a struct named with the path of the variant, e.g.
+                // `State.Auction`, which no Rust type can be named. Variants of
+                // generic enums are named with the type parameters of the
+                // enum, e.g. `State<T>.Auction`.
+                let struct_name = format!("{}.{}", spec_name(enum_ident, &params), name);
+                let spec_fields = v.fields.iter().map(|f| {
+                    let f_ident = f.ident.as_ref().unwrap();
+                    let f_name = f_ident.to_string();
+                    if let Err(e) = Symbol::try_from_str(&f_name) {
+                        errors.push(Error::new(f_ident.span(), format!("enum variant field name {}", e)));
+                    }
+                    ScSpecUdtStructFieldV0 {
+                        name: f_name.try_into().unwrap_or_else(|_| StringM::default()),
+                        type_: match map_type_generic(&f.ty, &params) {
+                            Ok(t) => t,
+                            Err(e) => {
+                                errors.push(e);
+                                ScSpecTypeDef::I32
+                            }
+                        },
+                    }
+                }).collect::<Vec<_>>();
+                spec_structs.push(ScSpecUdtStructV0 {
+                    lib: lib.as_deref().unwrap_or_default().try_into().unwrap_or_default(),
+                    name: struct_name.clone().try_into().unwrap_or_else(|_| {
+                        errors.push(Error::new(ident.span(), "enum name and variant name are too long for the spec"));
+                        StringM::default()
+                    }),
+                    fields: spec_fields.try_into().unwrap_or_else(|_| {
+                        errors.push(Error::new(v.fields.span(), "enum variant has too many fields for the spec"));
+                        VecM::default()
+                    }),
+                });
                 let spec_case = ScSpecUdtUnionCaseV0 {
                     name: name.try_into().unwrap_or_else(|_| StringM::default()),
-                    type_: Some(match map_type(&f.ty) {
+                    type_: Some(ScSpecTypeDef::Udt(ScSpecTypeUdt {
+                        name: struct_name.try_into().unwrap_or_else(|_| StringM::default()),
+                    })),
+                };
+                let field_idents = v.fields.iter().map(|f| f.ident.as_ref().unwrap()).collect::<Vec<_>>();
+                let field_names = field_idents.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+                let field_try_froms = field_idents.iter().zip(&field_names).map(|(f_ident, f_name)| quote! {
+                    #f_ident: if let Some(Ok(val)) = map.get(#path::symbol!(#f_name)) {
+                        val.try_into_val(env)?
+                    } else {
+                        Err(#path::ConversionError)?
+                    }
+                });
+                let field_try_from_xdrs = field_idents.iter().zip(&field_names).map(|(f_ident, f_name)| quote! {
+                    #f_ident: {
+                        let key = &#f_name.try_into().map_err(|_| #path::xdr::Error::Invalid)?;
+                        let idx = map.binary_search_by_key(key, |entry| entry.key.clone()).map_err(|_| #path::xdr::Error::Invalid)?;
+                        let rv: #path::RawVal = (&map[idx].val.clone()).try_into_val(env).map_err(|_| #path::xdr::Error::Invalid)?;
+                        rv.try_into_val(env).map_err(|_| #path::xdr::Error::Invalid)?
+                    }
+                });
+                let try_from = quote! {
+                    #discriminant_const_u64_ident => {
+                        if iter.len() > 1 {
+                            return Err(#path::ConversionError);
+                        }
+                        let map: #path::Map<#path::Symbol, #path::RawVal> = iter.next().ok_or(#path::ConversionError)??.try_into_val(env)?;
+                        if map.len() != #field_count_u32 {
+                            return Err(#path::ConversionError);
+                        }
+                        Self::#ident { #(#field_try_froms,)* }
+                    }
+                };
+                let into = quote! {
+                    #enum_ident::#ident { #(ref #field_idents),* } => {
+                        let mut map = #path::Map::<#path::Symbol, #path::RawVal>::new(env);
+                        #(map.set(#path::symbol!(#field_names), #field_idents.into_val(env));)*
+                        let map: #path::RawVal = map.into();
+                        (#discriminant_const_sym_ident, map).into_val(env)
+                    }
+                };
+                let try_from_xdr = quote! {
+                    #name => {
+                        if iter.len() > 1 {
+                            return Err(#path::xdr::Error::Invalid);
+                        }
+                        let map = match iter.next().ok_or(#path::xdr::Error::Invalid)? {
+                            #path::xdr::ScVal::Object(Some(#path::xdr::ScObject::Map(map))) => map,
+                            _ => return Err(#path::xdr::Error::Invalid),
+                        };
+                        if map.len() != #field_count_usize {
+                            return Err(#path::xdr::Error::Invalid);
+                        }
+                        map.validate()?;
+                        Self::#ident { #(#field_try_from_xdrs,)* }
+                    }
+                };
+                let into_xdr = quote! {
+                    #enum_ident::#ident { #(#field_idents),* } => {
+                        let name: #path::xdr::ScVal = #name.try_into().map_err(|_| #path::xdr::Error::Invalid)?;
+                        let map = #path::xdr::ScMap::sorted_from(alloc::vec![
+                            #(#path::xdr::ScMapEntry {
+                                key: #field_names.try_into().map_err(|_| #path::xdr::Error::Invalid)?,
+                                val: #field_idents.try_into().map_err(|_| #path::xdr::Error::Invalid)?,
+                            },)*
+                        ])?;
+                        let map = #path::xdr::ScVal::Object(Some(#path::xdr::ScObject::Map(map)));
+                        #path::xdr::ScVec(alloc::vec![name, map].try_into()?)
+                    }
+                };
+                (spec_case, discriminant_const, try_from, into, try_from_xdr, into_xdr)
+            } else if field_count_usize > 1 {
+                // Variants with multiple fields have as their value a vec of
+                // the fields, the same as a tuple of the fields.
+                let value_types = v.fields.iter().map(|f| match map_type_generic(&f.ty, &params) {
+                    Ok(t) => t,
+                    Err(e) => {
+                        errors.push(e);
+                        ScSpecTypeDef::I32
+                    }
+                }).collect::<Vec<_>>();
+                let spec_case = ScSpecUdtUnionCaseV0 {
+                    name: name.try_into().unwrap_or_else(|_| StringM::default()),
+                    type_: Some(ScSpecTypeDef::Tuple(Box::new(ScSpecTypeTuple {
+                        value_types: value_types.try_into().unwrap_or_else(|_| {
+                            errors.push(Error::new(v.fields.span(), "enum variant has too many fields for the spec"));
+                            VecM::default()
+                        }),
+                    }))),
+                };
+                let value_idents = (0..field_count_usize).map(|i| format_ident!("value_{}", i)).collect::<Vec<_>>();
+                let value_try_froms = value_idents.iter().map(|_| quote! {
+                    values.next().ok_or(#path::ConversionError)??.try_into_val(env)?
+                });
+                let value_try_from_xdrs = value_idents.iter().map(|_| quote! {
+                    {
+                        let rv: #path::RawVal = values.next().ok_or(#path::xdr::Error::Invalid)?.try_into_val(env).map_err(|_| #path::xdr::Error::Invalid)?;
+                        rv.try_into_val(env).map_err(|_| #path::xdr::Error::Invalid)?
+                    }
+                });
+                let try_from = quote! {
+                    #discriminant_const_u64_ident => {
+                        if iter.len() > 1 {
+                            return Err(#path::ConversionError);
+                        }
+                        let values: #path::Vec<#path::RawVal> = iter.next().ok_or(#path::ConversionError)??.try_into_val(env)?;
+                        if values.len() != #field_count_u32 {
+                            return Err(#path::ConversionError);
+                        }
+                        let mut values = values.iter();
+                        Self::#ident(#(#value_try_froms,)*)
+                    }
+                };
+                let into = quote! { #enum_ident::#ident(#(ref #value_idents),*) => (#discriminant_const_sym_ident, (#(#value_idents,)*)).into_val(env) };
+                let try_from_xdr = quote! {
+                    #name => {
+                        if iter.len() > 1 {
+                            return Err(#path::xdr::Error::Invalid);
+                        }
+                        let values = match iter.next().ok_or(#path::xdr::Error::Invalid)? {
+                            #path::xdr::ScVal::Object(Some(#path::xdr::ScObject::Vec(values))) => values,
+                            _ => return Err(#path::xdr::Error::Invalid),
+                        };
+                        if values.len() != #field_count_usize {
+                            return Err(#path::xdr::Error::Invalid);
+                        }
+                        let mut values = values.iter();
+                        Self::#ident(#(#value_try_from_xdrs,)*)
+                    }
+                };
+                let into_xdr = quote! {
+                    #enum_ident::#ident(#(#value_idents),*) => {
+                        let name: #path::xdr::ScVal = #name.try_into().map_err(|_| #path::xdr::Error::Invalid)?;
+                        let values: alloc::vec::Vec<#path::xdr::ScVal> = alloc::vec![
+                            #(#value_idents.try_into().map_err(|_| #path::xdr::Error::Invalid)?,)*
+                        ];
+                        let values = #path::xdr::ScVal::Object(Some(#path::xdr::ScObject::Vec(#path::xdr::ScVec(values.try_into()?))));
+                        #path::xdr::ScVec(alloc::vec![name, values].try_into()?)
+                    }
+                };
+                (spec_case, discriminant_const, try_from, into, try_from_xdr, into_xdr)
+            } else if let Some(f) = v.fields.iter().next() {
+                let spec_case = ScSpecUdtUnionCaseV0 {
+                    name: name.try_into().unwrap_or_else(|_| StringM::default()),
+                    type_: Some(match map_type_generic(&f.ty, &params) {
                         Ok(t) => t,
                         Err(e) => {
                             errors.push(e);
@@ -118,22 +311,40 @@ pub fn derive_type_enum(
         return quote! { #(#compile_errors)* };
     }
 
+    // Conversions for generic types are implemented for the type arguments
+    // that the fields using the type parameters can be converted for.
+    let generic_types = data
+        .variants
+        .iter()
+        .flat_map(|v| v.fields.iter())
+        .map(|f| &f.ty)
+        .filter(|t| uses_type_params(t, &params))
+        .collect::<Vec<_>>();
+    let val_where = where_clause(generics, &val_predicates(path, &generic_types));
+    let xdr_where = where_clause(generics, &xdr_predicates(path, &generic_types));
+    let (impl_generics, ty_generics, type_where) = generics.split_for_impl();
+
     // Generated code spec.
     let spec_gen = if spec {
         let spec_entry = ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 {
             lib: lib.as_deref().unwrap_or_default().try_into().unwrap(),
-            name: enum_ident.to_string().try_into().unwrap(),
+            name: spec_name(enum_ident, &params).try_into().unwrap(),
             cases: spec_cases.try_into().unwrap(),
         });
-        let spec_xdr = spec_entry.to_xdr().unwrap();
+        // The structs describing the fields of variants are part of the spec
+        // of the enum, and follow the union in the same static.
+        let mut spec_xdr = spec_entry.to_xdr().unwrap();
+        for s in spec_structs {
+            spec_xdr.extend(ScSpecEntry::UdtStructV0(s).to_xdr().unwrap());
+        }
         let spec_xdr_lit = proc_macro2::Literal::byte_string(spec_xdr.as_slice());
         let spec_xdr_len = spec_xdr.len();
         let spec_ident = format_ident!("__SPEC_XDR_{}", enum_ident.to_string().to_uppercase());
         Some(quote! {
             #[cfg_attr(target_family = "wasm", link_section = "contractspecv0")]
-            pub static #spec_ident: [u8; #spec_xdr_len] = #enum_ident::spec_xdr();
+            pub static #spec_ident: [u8; #spec_xdr_len] = *#spec_xdr_lit;
 
-            impl #enum_ident {
+            impl #impl_generics #enum_ident #ty_generics #type_where {
                 pub const fn spec_xdr() -> [u8; #spec_xdr_len] {
                     *#spec_xdr_lit
                 }
@@ -147,7 +358,7 @@ pub fn derive_type_enum(
     quote! {
         #spec_gen
 
-        impl #path::TryFromVal<#path::Env, #path::RawVal> for #enum_ident {
+        impl #impl_generics #path::TryFromVal<#path::Env, #path::RawVal> for #enum_ident #ty_generics #val_where {
             type Error = #path::ConversionError;
             #[inline(always)]
             fn try_from_val(env: &#path::Env, val: #path::RawVal) -> Result<Self, Self::Error> {
@@ -163,15 +374,15 @@ pub fn derive_type_enum(
             }
         }
 
-        impl #path::TryIntoVal<#path::Env, #enum_ident> for #path::RawVal {
+        impl #impl_generics #path::TryIntoVal<#path::Env, #enum_ident #ty_generics> for #path::RawVal #val_where {
             type Error = #path::ConversionError;
             #[inline(always)]
-            fn try_into_val(self, env: &#path::Env) -> Result<#enum_ident, Self::Error> {
+            fn try_into_val(self, env: &#path::Env) -> Result<#enum_ident #ty_generics, Self::Error> {
                 <_ as #path::TryFromVal<_, _>>::try_from_val(env, self)
             }
         }
 
-        impl #path::IntoVal<#path::Env, #path::RawVal> for #enum_ident {
+        impl #impl_generics #path::IntoVal<#path::Env, #path::RawVal> for #enum_ident #ty_generics #val_where {
             #[inline(always)]
             fn into_val(self, env: &#path::Env) -> #path::RawVal {
                 #(#discriminant_consts)*
@@ -181,7 +392,7 @@ pub fn derive_type_enum(
             }
         }
 
-        impl #path::IntoVal<#path::Env, #path::RawVal> for &#enum_ident {
+        impl #impl_generics #path::IntoVal<#path::Env, #path::RawVal> for &#enum_ident #ty_generics #val_where {
             #[inline(always)]
             fn into_val(self, env: &#path::Env) -> #path::RawVal {
                 #(#discriminant_consts)*
@@ -192,7 +403,7 @@ pub fn derive_type_enum(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl #path::TryFromVal<#path::Env, #path::xdr::ScVec> for #enum_ident {
+        impl #impl_generics #path::TryFromVal<#path::Env, #path::xdr::ScVec> for #enum_ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_from_val(env: &#path::Env, val: #path::xdr::ScVec) -> Result<Self, Self::Error> {
@@ -212,16 +423,16 @@ pub fn derive_type_enum(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl #path::TryIntoVal<#path::Env, #enum_ident> for #path::xdr::ScVec {
+        impl #impl_generics #path::TryIntoVal<#path::Env, #enum_ident #ty_generics> for #path::xdr::ScVec #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
-            fn try_into_val(self, env: &#path::Env) -> Result<#enum_ident, Self::Error> {
+            fn try_into_val(self, env: &#path::Env) -> Result<#enum_ident #ty_generics, Self::Error> {
                 <_ as #path::TryFromVal<_, _>>::try_from_val(env, self)
             }
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl #path::TryFromVal<#path::Env, #path::xdr::ScObject> for #enum_ident {
+        impl #impl_generics #path::TryFromVal<#path::Env, #path::xdr::ScObject> for #enum_ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_from_val(env: &#path::Env, val: #path::xdr::ScObject) -> Result<Self, Self::Error> {
@@ -234,16 +445,16 @@ pub fn derive_type_enum(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl #path::TryIntoVal<#path::Env, #enum_ident> for #path::xdr::ScObject {
+        impl #impl_generics #path::TryIntoVal<#path::Env, #enum_ident #ty_generics> for #path::xdr::ScObject #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
-            fn try_into_val(self, env: &#path::Env) -> Result<#enum_ident, Self::Error> {
+            fn try_into_val(self, env: &#path::Env) -> Result<#enum_ident #ty_generics, Self::Error> {
                 <_ as #path::TryFromVal<_, _>>::try_from_val(env, self)
             }
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl #path::TryFromVal<#path::Env, #path::xdr::ScVal> for #enum_ident {
+        impl #impl_generics #path::TryFromVal<#path::Env, #path::xdr::ScVal> for #enum_ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_from_val(env: &#path::Env, val: #path::xdr::ScVal) -> Result<Self, Self::Error> {
@@ -256,16 +467,16 @@ pub fn derive_type_enum(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl #path::TryIntoVal<#path::Env, #enum_ident> for #path::xdr::ScVal {
+        impl #impl_generics #path::TryIntoVal<#path::Env, #enum_ident #ty_generics> for #path::xdr::ScVal #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
-            fn try_into_val(self, env: &#path::Env) -> Result<#enum_ident, Self::Error> {
+            fn try_into_val(self, env: &#path::Env) -> Result<#enum_ident #ty_generics, Self::Error> {
                 <_ as #path::TryFromVal<_, _>>::try_from_val(env, self)
             }
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl TryInto<#path::xdr::ScVec> for &#enum_ident {
+        impl #impl_generics TryInto<#path::xdr::ScVec> for &#enum_ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_into(self) -> Result<#path::xdr::ScVec, Self::Error> {
@@ -277,7 +488,7 @@ pub fn derive_type_enum(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl TryInto<#path::xdr::ScVec> for #enum_ident {
+        impl #impl_generics TryInto<#path::xdr::ScVec> for #enum_ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_into(self) -> Result<#path::xdr::ScVec, Self::Error> {
@@ -286,7 +497,7 @@ pub fn derive_type_enum(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl TryInto<#path::xdr::ScObject> for &#enum_ident {
+        impl #impl_generics TryInto<#path::xdr::ScObject> for &#enum_ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_into(self) -> Result<#path::xdr::ScObject, Self::Error> {
@@ -295,7 +506,7 @@ pub fn derive_type_enum(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl TryInto<#path::xdr::ScObject> for #enum_ident {
+        impl #impl_generics TryInto<#path::xdr::ScObject> for #enum_ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_into(self) -> Result<#path::xdr::ScObject, Self::Error> {
@@ -304,7 +515,7 @@ pub fn derive_type_enum(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl TryInto<#path::xdr::ScVal> for &#enum_ident {
+        impl #impl_generics TryInto<#path::xdr::ScVal> for &#enum_ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_into(self) -> Result<#path::xdr::ScVal, Self::Error> {
@@ -313,7 +524,7 @@ pub fn derive_type_enum(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl TryInto<#path::xdr::ScVal> for #enum_ident {
+        impl #impl_generics TryInto<#path::xdr::ScVal> for #enum_ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_into(self) -> Result<#path::xdr::ScVal, Self::Error> {
@@ -0,0 +1,229 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use soroban_env_common::Symbol;
+use soroban_spec::docs::Docs;
+use syn::{spanned::Spanned, Attribute, DataStruct, Error, Fields, Ident, Path};
+
+use stellar_xdr::{
+    ScSpecEntry, ScSpecTypeDef, ScSpecTypeUdt, ScSpecUdtStructFieldV0, ScSpecUdtStructV0, StringM,
+    WriteXdr,
+};
+
+use crate::{
+    derive_storage::to_snake_case,
+    doc::{docs_from_attrs, docs_gen},
+    map_type::map_type,
+};
+
+/// Name of the attribute on fields of an event struct that are published as
+/// topics.
+const TOPIC_ATTR: &str = "topic";
+
+/// Maximum number of topic fields. Events have a maximum of four topics, and
+/// the first is always the event name.
+const MAX_TOPICS: usize = 3;
+
+/// Name of the field of the struct describing an event that has the type of the
+/// data of the event.
+const DATA_FIELD: &str = "data";
+
+pub fn derive_event(
+    path: &Path,
+    ident: &Ident,
+    attrs: &[Attribute],
+    data: &mut DataStruct,
+    name: &Option<String>,
+    spec: bool,
+    lib: &Option<String>,
+) -> TokenStream2 {
+    // Collect errors as they are encountered and emit them at the end.
+    let mut errors = Vec::<Error>::new();
+
+    let name = name
+        .clone()
+        .unwrap_or_else(|| to_snake_case(&ident.to_string()));
+    if let Err(e) = Symbol::try_from_str(&name) {
+        errors.push(Error::new(
+            ident.span(),
+            format!(
+                "event name {} {}, set a shorter name with the name argument",
+                name, e
+            ),
+        ));
+    }
+
+    if !matches!(data.fields, Fields::Named(_)) {
+        errors.push(Error::new(
+            data.fields.span(),
+            "events are supported only for structs with named fields",
+        ));
+    }
+
+    // The struct describing the event is named after the event, and the struct
+    // describing the data is named after the struct describing the event.
+    let event_name = format!("event.{}", name);
+    let data_name = format!("{}.{}", event_name, DATA_FIELD);
+
+    // Take the topic attributes off the fields, so that the struct can be
+    // emitted without them, collecting the doc strings of the event and its
+    // topics and data fields along the way.
+    let mut docs = Docs::new();
+    docs.insert(&event_name, docs_from_attrs(attrs));
+    let mut topics = Vec::new();
+    let mut values = Vec::new();
+    for f in data.fields.iter_mut() {
+        let count = f.attrs.len();
+        f.attrs.retain(|a| !a.path.is_ident(TOPIC_ATTR));
+        let is_topic = f.attrs.len() < count;
+        if let Some(field_ident) = &f.ident {
+            let field_path = if is_topic { &event_name } else { &data_name };
+            docs.insert(
+                format!("{}.{}", field_path, field_ident),
+                docs_from_attrs(&f.attrs),
+            );
+        }
+        if is_topic {
+            topics.push(&*f);
+        } else {
+            values.push(&*f);
+        }
+    }
+    if topics.len() > MAX_TOPICS {
+        errors.push(Error::new(
+            topics[MAX_TOPICS].span(),
+            format!("event has too many topics, max count {} topics", MAX_TOPICS),
+        ));
+    }
+
+    let spec_topics: Vec<_> = topics
+        .iter()
+        .filter_map(|f| f.ident.as_ref().map(|ident| (ident, &f.ty)))
+        .map(|(ident, ty)| {
+            // The last field of the struct describing the event is the data.
+            if ident == DATA_FIELD {
+                errors.push(Error::new(
+                    ident.span(),
+                    format!("event topic cannot be named {}", DATA_FIELD),
+                ));
+            }
+            ScSpecUdtStructFieldV0 {
+                name: ident
+                    .to_string()
+                    .try_into()
+                    .unwrap_or_else(|_| StringM::default()),
+                type_: match map_type(ty) {
+                    Ok(t) => t,
+                    Err(e) => {
+                        errors.push(e);
+                        ScSpecTypeDef::I32
+                    }
+                },
+            }
+        })
+        .collect();
+    let topic_exprs: Vec<_> = topics
+        .iter()
+        .filter_map(|f| f.ident.as_ref())
+        .map(|ident| quote! { <_ as #path::IntoVal<#path::Env, #path::RawVal>>::into_val(&self.#ident, env) })
+        .collect();
+
+    let (spec_fields, data_sets): (Vec<_>, Vec<_>) = values
+        .iter()
+        .filter_map(|f| f.ident.as_ref().map(|ident| (ident, &f.ty)))
+        .map(|(ident, ty)| {
+            let field_name = ident.to_string();
+            if let Err(e) = Symbol::try_from_str(&field_name) {
+                errors.push(Error::new(ident.span(), format!("event field name {}", e)));
+            }
+            let spec_field = ScSpecUdtStructFieldV0 {
+                name: field_name
+                    .clone()
+                    .try_into()
+                    .unwrap_or_else(|_| StringM::default()),
+                type_: match map_type(ty) {
+                    Ok(t) => t,
+                    Err(e) => {
+                        errors.push(e);
+                        ScSpecTypeDef::I32
+                    }
+                },
+            };
+            let data_set = quote! {
+                data.set(#path::symbol!(#field_name), <_ as #path::IntoVal<#path::Env, #path::RawVal>>::into_val(&self.#ident, env))
+            };
+            (spec_field, data_set)
+        })
+        .unzip();
+
+    // If errors have occurred, render them instead.
+    if !errors.is_empty() {
+        let compile_errors = errors.iter().map(Error::to_compile_error);
+        return quote! { #(#compile_errors)* };
+    }
+
+    // Generated code spec. Each event is described in the contract spec by a
+    // struct named after the event, where the fields are the topics that follow
+    // the name of the event and a last field that is the data, followed by a
+    // struct that describes the fields of the data. The names of the structs
+    // start with `event.`, which no type can, so that they are not mistaken for
+    // types of the contract.
+    let spec_gen = if spec {
+        let mut event_fields = spec_topics;
+        event_fields.push(ScSpecUdtStructFieldV0 {
+            name: DATA_FIELD.try_into().unwrap(),
+            type_: ScSpecTypeDef::Udt(ScSpecTypeUdt {
+                name: data_name.as_str().try_into().unwrap(),
+            }),
+        });
+        let lib = lib.as_deref().unwrap_or_default();
+        let event_spec_entry = ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+            lib: lib.try_into().unwrap(),
+            name: event_name.as_str().try_into().unwrap(),
+            fields: event_fields.try_into().unwrap(),
+        });
+        let data_spec_entry = ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+            lib: lib.try_into().unwrap(),
+            name: data_name.as_str().try_into().unwrap(),
+            fields: spec_fields.try_into().unwrap(),
+        });
+        let mut spec_xdr = event_spec_entry.to_xdr().unwrap();
+        spec_xdr.extend(data_spec_entry.to_xdr().unwrap());
+        let spec_xdr_lit = proc_macro2::Literal::byte_string(spec_xdr.as_slice());
+        let spec_xdr_len = spec_xdr.len();
+        let spec_ident = format_ident!("__SPEC_XDR_EVENT_{}", ident.to_string().to_uppercase());
+        let docs_gen = docs_gen("EVENT", &ident.to_string(), &[], &docs);
+
+        Some(quote! {
+            #[cfg_attr(target_family = "wasm", link_section = "contractspecv0")]
+            pub static #spec_ident: [u8; #spec_xdr_len] = #ident::spec_xdr();
+
+            impl #ident {
+                pub const fn spec_xdr() -> [u8; #spec_xdr_len] {
+                    *#spec_xdr_lit
+                }
+            }
+
+            #docs_gen
+        })
+    } else {
+        None
+    };
+
+    // Output.
+    quote! {
+        #spec_gen
+
+        impl #ident {
+            /// Publish the event from the currently executing contract.
+            #[inline(always)]
+            pub fn publish(&self, env: &#path::Env) {
+                let mut data = #path::Map::<#path::Symbol, #path::RawVal>::new(env);
+                #(#data_sets;)*
+                env.events().publish(
+                    (#path::symbol!(#name), #(#topic_exprs,)*),
+                    data,
+                );
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use itertools::MultiUnzip;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote};
+use soroban_spec::docs::Docs;
 use stellar_xdr::{
     ScSpecEntry, ScSpecFunctionInputV0, ScSpecFunctionV0, ScSpecTypeDef, StringM, VecM, WriteXdr,
 };
@@ -11,7 +12,10 @@ use syn::{
     Attribute, Error, FnArg, Ident, Pat, PatIdent, PatType, ReturnType, Type, TypePath,
 };
 
-use crate::map_type::map_type;
+use crate::{
+    doc::{docs_for_inputs, docs_from_attrs, docs_gen},
+    map_type::map_type,
+};
 
 #[allow(clippy::too_many_arguments)]
 pub fn derive_fn(
@@ -140,6 +144,7 @@ pub fn derive_fn(
     };
 
     // Generated code spec.
+    let input_names: Vec<String> = spec_args.iter().map(|a| a.name.to_string_lossy()).collect();
     let spec_entry = ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
         name: wrap_export_name.try_into().unwrap_or_else(|_| {
             const MAX: u32 = 10;
@@ -171,6 +176,17 @@ pub fn derive_fn(
     let spec_ident = format_ident!("__SPEC_XDR_{}", ident.to_string().to_uppercase());
     let spec_fn_ident = format_ident!("spec_xdr_{}", ident.to_string());
 
+    // Generated code docs.
+    let fn_doc = docs_from_attrs(attrs);
+    let mut docs = Docs::new();
+    docs.insert(wrap_export_name, &fn_doc);
+    docs.extend(docs_for_inputs(
+        wrap_export_name,
+        &fn_doc,
+        input_names.iter().map(String::as_str),
+    ));
+    let docs_static = docs_gen("FN", wrap_export_name, attrs, &docs);
+
     // If errors have occurred, render them instead.
     if !errors.is_empty() {
         let compile_errors = errors.iter().map(Error::to_compile_error);
@@ -184,6 +200,8 @@ pub fn derive_fn(
         #[cfg_attr(target_family = "wasm", link_section = "contractspecv0")]
         pub static #spec_ident: [u8; #spec_xdr_len] = #ty::#spec_fn_ident();
 
+        #docs_static
+
         impl #ty {
             #(#attrs)*
             pub const fn #spec_fn_ident() -> [u8; #spec_xdr_len] {
@@ -0,0 +1,237 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, DataEnum, Error, Generics, Ident, Path, Type, Visibility};
+
+use crate::derive_enum::derive_type_enum;
+
+/// Name of the attribute on each variant of a storage enum that declares the
+/// type of the value stored with the key.
+const VALUE_ATTR: &str = "value";
+
+/// Names of the functions of `Data`, that generated accessors must not have, as
+/// the functions of `Data` would be called instead of the accessors.
+const DATA_FNS: &[&str] = &[
+    "has",
+    "get",
+    "try_get",
+    "get_unchecked",
+    "try_get_unchecked",
+    "set",
+    "try_set",
+    "remove",
+    "try_remove",
+    "all",
+];
+
+pub fn derive_storage(
+    path: &Path,
+    vis: &Visibility,
+    enum_ident: &Ident,
+    generics: &Generics,
+    data: &mut DataEnum,
+    spec: bool,
+    lib: &Option<String>,
+) -> TokenStream2 {
+    // Collect errors as they are encountered and emit them at the end.
+    let mut errors = Vec::<Error>::new();
+
+    if !generics.params.is_empty() {
+        errors.push(Error::new(
+            generics.span(),
+            "generics are unsupported on contract storage keys",
+        ));
+    }
+
+    // Take the value attributes off the variants, so that the enum can be
+    // emitted without them.
+    let mut values = Vec::<Type>::new();
+    for v in data.variants.iter_mut() {
+        if v.discriminant.is_some() {
+            errors.push(Error::new(
+                v.span(),
+                "storage key variants cannot have an explicit integer literal",
+            ));
+        }
+        if v.fields.len() > 1 {
+            errors.push(Error::new(
+                v.fields.span(),
+                "storage key variants can have at most one field",
+            ));
+        }
+        let name = to_snake_case(&v.ident.to_string());
+        for prefix in ["get", "try_get", "set", "has", "remove"] {
+            let accessor = format!("{prefix}_{name}");
+            if DATA_FNS.contains(&accessor.as_str()) {
+                errors.push(Error::new(
+                    v.ident.span(),
+                    format!(
+                        "storage key {} has an accessor named {} that has the same name as a function of Data",
+                        v.ident, accessor,
+                    ),
+                ));
+            }
+        }
+        let (value_attrs, attrs) = v
+            .attrs
+            .drain(..)
+            .partition::<Vec<_>, _>(|a| a.path.is_ident(VALUE_ATTR));
+        v.attrs = attrs;
+        let mut value_attrs = value_attrs.into_iter();
+        match value_attrs.next() {
+            Some(a) => match a.parse_args::<Type>() {
+                Ok(t) => values.push(t),
+                Err(e) => errors.push(e),
+            },
+            None => errors.push(Error::new(
+                v.ident.span(),
+                format!(
+                    "storage key {} is missing a #[{}(Type)] attribute declaring the type of its value",
+                    v.ident, VALUE_ATTR,
+                ),
+            )),
+        }
+        for a in value_attrs {
+            errors.push(Error::new(
+                a.span(),
+                format!(
+                    "storage key {} has more than one #[{}] attribute",
+                    v.ident, VALUE_ATTR
+                ),
+            ));
+        }
+    }
+
+    // If errors have occurred, render them instead.
+    if !errors.is_empty() {
+        let compile_errors = errors.iter().map(Error::to_compile_error);
+        return quote! { #(#compile_errors)* };
+    }
+
+    // Conversions for the key type, and the key type in the spec.
+    let derived = derive_type_enum(path, enum_ident, generics, data, spec, lib);
+
+    // Accessors for each key.
+    let (fn_decls, fn_impls): (Vec<_>, Vec<_>) = data
+        .variants
+        .iter()
+        .zip(values.iter())
+        .map(|(v, value_ty)| {
+            let ident = &v.ident;
+            let name = to_snake_case(&ident.to_string());
+            let get_ident = format_ident!("get_{}", name);
+            let try_get_ident = format_ident!("try_get_{}", name);
+            let set_ident = format_ident!("set_{}", name);
+            let has_ident = format_ident!("has_{}", name);
+            let remove_ident = format_ident!("remove_{}", name);
+            let (key_input, key) = match v.fields.iter().next() {
+                Some(f) => {
+                    let key_ty = &f.ty;
+                    let key = match &f.ident {
+                        Some(field) => quote! { #enum_ident::#ident { #field: key } },
+                        None => quote! { #enum_ident::#ident(key) },
+                    };
+                    (Some(quote! { key: #key_ty, }), key)
+                }
+                None => (None, quote! { #enum_ident::#ident }),
+            };
+            let get_doc =
+                format!("Returns the value stored for the [`{enum_ident}::{ident}`] key.");
+            let set_doc = format!("Sets the value stored for the [`{enum_ident}::{ident}`] key.");
+            let has_doc = format!(
+                "Returns if there is a value stored for the [`{enum_ident}::{ident}`] key."
+            );
+            let remove_doc =
+                format!("Removes the value stored for the [`{enum_ident}::{ident}`] key.");
+            let fn_decl = quote! {
+                #[doc = #get_doc]
+                ///
+                /// ### Panics
+                ///
+                /// When the value stored cannot be converted into the type
+                /// expected.
+                fn #get_ident(&self, #key_input) -> Option<#value_ty>;
+                #[doc = #get_doc]
+                ///
+                /// ### Errors
+                ///
+                /// When the value stored cannot be converted into the type
+                /// expected.
+                fn #try_get_ident(&self, #key_input) -> Result<Option<#value_ty>, #path::data::DataError>;
+                #[doc = #set_doc]
+                fn #set_ident(&self, #key_input value: #value_ty);
+                #[doc = #has_doc]
+                fn #has_ident(&self, #key_input) -> bool;
+                #[doc = #remove_doc]
+                fn #remove_ident(&self, #key_input);
+            };
+            let fn_impl = quote! {
+                #[inline(always)]
+                fn #get_ident(&self, #key_input) -> Option<#value_ty> {
+                    self.get::<_, #value_ty>(#key).map(|v| v.unwrap())
+                }
+                #[inline(always)]
+                fn #try_get_ident(&self, #key_input) -> Result<Option<#value_ty>, #path::data::DataError> {
+                    self.try_get::<_, #value_ty>(#key)
+                }
+                #[inline(always)]
+                fn #set_ident(&self, #key_input value: #value_ty) {
+                    self.set(#key, value)
+                }
+                #[inline(always)]
+                fn #has_ident(&self, #key_input) -> bool {
+                    self.has(#key)
+                }
+                #[inline(always)]
+                fn #remove_ident(&self, #key_input) {
+                    self.remove(#key)
+                }
+            };
+            (fn_decl, fn_impl)
+        })
+        .unzip();
+
+    let trait_ident = format_ident!("{}Storage", enum_ident);
+    let trait_doc =
+        format!("Typed accessors for the contract data stored with the keys of [`{enum_ident}`].");
+
+    // Output.
+    quote! {
+        #derived
+
+        #[doc = #trait_doc]
+        #vis trait #trait_ident {
+            #(#fn_decls)*
+        }
+
+        impl #trait_ident for #path::data::Data {
+            #(#fn_impls)*
+        }
+    }
+}
+
+/// Converts an upper camel case identifier, such as a variant name, into snake
+/// case.
+pub fn to_snake_case(s: &str) -> String {
+    let mut snake = String::with_capacity(s.len() + 4);
+    let mut prev: Option<char> = None;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_uppercase() {
+            let next_is_lower = chars.peek().map_or(false, |n| n.is_lowercase());
+            let boundary = match prev {
+                Some(p) => {
+                    p.is_lowercase() || p.is_numeric() || (p.is_uppercase() && next_is_lower)
+                }
+                None => false,
+            };
+            if boundary {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+        prev = Some(c);
+    }
+    snake
+}
@@ -1,50 +1,107 @@
+use std::collections::HashSet;
+
 use itertools::MultiUnzip;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote};
 use soroban_env_common::Symbol;
-use syn::{spanned::Spanned, DataStruct, Error, Ident, Path};
+use syn::{spanned::Spanned, DataStruct, Error, Generics, Ident, Path};
 
 use stellar_xdr::{
-    ScSpecEntry, ScSpecTypeDef, ScSpecUdtStructFieldV0, ScSpecUdtStructV0, StringM, WriteXdr,
+    ScSpecEntry, ScSpecTypeDef, ScSpecTypeUdt, ScSpecUdtStructFieldV0, ScSpecUdtStructV0,
+    ScSpecUdtUnionCaseV0, ScSpecUdtUnionV0, StringM, WriteXdr,
 };
 
-use crate::map_type::map_type;
+use crate::{
+    field_attrs::FieldArgs,
+    generics::{
+        spec_name, type_params, uses_type_params, val_predicates, where_clause, xdr_predicates,
+    },
+    map_type::map_type_generic,
+};
 
-// TODO: Add field attribute for including/excluding fields in types.
 // TODO: Better handling of partial types and types without all their fields and
 // types with private fields.
 
 pub fn derive_type_struct(
     path: &Path,
     ident: &Ident,
+    generics: &Generics,
     data: &DataStruct,
+    field_args: &[FieldArgs],
+    version: Option<u32>,
+    version_of: &Option<String>,
     spec: bool,
     lib: &Option<String>,
 ) -> TokenStream2 {
     // Collect errors as they are encountered and emit them at the end.
     let mut errors = Vec::<Error>::new();
 
+    // Versioned structs are tagged with a symbol naming the version, e.g. `V2`.
+    let version_name = version.map(|v| format!("V{}", v));
+    if version == Some(0) {
+        errors.push(Error::new(
+            ident.span(),
+            "struct version must be 1 or greater",
+        ));
+    }
+    if version.is_none() && version_of.is_some() {
+        errors.push(Error::new(
+            ident.span(),
+            "version_of can only be used on versioned contract types",
+        ));
+    }
+    if version.is_some() && !generics.params.is_empty() {
+        errors.push(Error::new(
+            generics.span(),
+            "versioned contract types cannot have generic parameters",
+        ));
+    }
+
+    let params = type_params(generics).unwrap_or_else(|e| {
+        errors.extend(e);
+        vec![]
+    });
+
     let fields = &data.fields;
-    let field_count_usize: usize = fields.len();
-    let field_count_u32: u32 = fields.len().try_into().unwrap_or_else(|_| {
+    // Skipped fields are not included in the map, and fields with a default
+    // are not required to be in the map.
+    let field_count_usize: usize = field_args.iter().filter(|a| !a.skip).count();
+    let field_count_u32: u32 = field_count_usize.try_into().unwrap_or_else(|_| {
         errors.push(Error::new(
             data.struct_token.span(),
             "struct has too many fields exceeding u32::MAX",
         ));
         0
     });
+    // Structs with no fields in the spec would be described the same as unit
+    // structs, which are converted to void instead of to a map.
+    if field_count_usize == 0 && version.is_none() {
+        errors.push(Error::new(
+            ident.span(),
+            "structs with named fields must have at least one field that is not skipped, use a unit struct instead",
+        ));
+    }
+    let has_defaults = field_args.iter().any(|a| a.default);
+    let mut names = HashSet::<String>::new();
     let (spec_fields, try_froms, intos, try_from_xdrs, into_xdrs): (Vec<_>, Vec<_>, Vec<_>, Vec<_>, Vec<_>) = fields
         .iter()
-        .enumerate()
-        .map(|(_, f)| {
+        .zip(field_args)
+        .map(|(f, args)| {
             let ident = f.ident.as_ref().unwrap();
-            let name = ident.to_string();
+            if args.skip {
+                let try_from = quote! { #ident: Default::default() };
+                return (None, try_from.clone(), None, try_from, None);
+            }
+            let name = args.rename.clone().unwrap_or_else(|| ident.to_string());
             if let Err(e) = Symbol::try_from_str(&name) {
                 errors.push(Error::new(ident.span(), format!("struct field name {}", e)));
             }
+            if !names.insert(name.clone()) {
+                errors.push(Error::new(ident.span(), format!("struct field name {} is used by more than one field", name)));
+            }
             let spec_field = ScSpecUdtStructFieldV0 {
                 name: name.clone().try_into().unwrap_or_else(|_| StringM::default()),
-                type_: match map_type(&f.ty) {
+                type_: match map_type_generic(&f.ty, &params) {
                     Ok(t) => t,
                     Err(e) => {
                         errors.push(e);
@@ -53,20 +110,54 @@ pub fn derive_type_struct(
                 },
             };
             let map_key = quote! { #path::symbol!(#name) };
-            let try_from = quote! {
-                #ident: if let Some(Ok(val)) = map.get(#map_key) {
-                    val.try_into_val(env)?
-                } else {
-                    Err(#path::ConversionError)?
+            // Structs with fields that have a default count the fields found
+            // in the map, to check that the map has no other fields.
+            let found = if has_defaults { quote! { found += 1; } } else { quote! {} };
+            let try_from = if args.default {
+                quote! {
+                    #ident: match map.get(#map_key) {
+                        Some(Ok(val)) => {
+                            found += 1;
+                            val.try_into_val(env)?
+                        }
+                        Some(Err(_)) => Err(#path::ConversionError)?,
+                        None => Default::default(),
+                    }
+                }
+            } else {
+                quote! {
+                    #ident: if let Some(Ok(val)) = map.get(#map_key) {
+                        #found
+                        val.try_into_val(env)?
+                    } else {
+                        Err(#path::ConversionError)?
+                    }
                 }
             };
             let into = quote! { map.set(#map_key, (&self.#ident).into_val(env)) };
-            let try_from_xdr = quote! {
-                #ident: {
-                    let key = &#name.try_into().map_err(|_| #path::xdr::Error::Invalid)?;
-                    let idx = map.binary_search_by_key(key, |entry| entry.key.clone()).map_err(|_| #path::xdr::Error::Invalid)?;
-                    let rv: #path::RawVal = (&map[idx].val.clone()).try_into_val(env).map_err(|_| #path::xdr::Error::Invalid)?;
-                    rv.try_into_val(env).map_err(|_| #path::xdr::Error::Invalid)?
+            let try_from_xdr = if args.default {
+                quote! {
+                    #ident: {
+                        let key = &#name.try_into().map_err(|_| #path::xdr::Error::Invalid)?;
+                        match map.binary_search_by_key(key, |entry| entry.key.clone()) {
+                            Ok(idx) => {
+                                found += 1;
+                                let rv: #path::RawVal = (&map[idx].val.clone()).try_into_val(env).map_err(|_| #path::xdr::Error::Invalid)?;
+                                rv.try_into_val(env).map_err(|_| #path::xdr::Error::Invalid)?
+                            }
+                            Err(_) => Default::default(),
+                        }
+                    }
+                }
+            } else {
+                quote! {
+                    #ident: {
+                        let key = &#name.try_into().map_err(|_| #path::xdr::Error::Invalid)?;
+                        let idx = map.binary_search_by_key(key, |entry| entry.key.clone()).map_err(|_| #path::xdr::Error::Invalid)?;
+                        #found
+                        let rv: #path::RawVal = (&map[idx].val.clone()).try_into_val(env).map_err(|_| #path::xdr::Error::Invalid)?;
+                        rv.try_into_val(env).map_err(|_| #path::xdr::Error::Invalid)?
+                    }
                 }
             };
             let into_xdr = quote! {
@@ -75,9 +166,12 @@ pub fn derive_type_struct(
                     val: (&self.#ident).try_into().map_err(|_| #path::xdr::Error::Invalid)?,
                 }
             };
-            (spec_field, try_from, into, try_from_xdr, into_xdr)
+            (Some(spec_field), try_from, Some(into), try_from_xdr, Some(into_xdr))
         })
         .multiunzip();
+    let spec_fields = spec_fields.into_iter().flatten().collect::<Vec<_>>();
+    let intos = intos.into_iter().flatten().collect::<Vec<_>>();
+    let into_xdrs = into_xdrs.into_iter().flatten().collect::<Vec<_>>();
 
     // If errors have occurred, render them instead.
     if !errors.is_empty() {
@@ -85,22 +179,125 @@ pub fn derive_type_struct(
         return quote! { #(#compile_errors)* };
     }
 
-    // Generated code spec.
+    // Structs with fields that have a default accept maps that are missing
+    // those fields, and check after converting that every field in the map was
+    // converted.
+    let try_from_val_body = if has_defaults {
+        quote! {
+            if map.len() > #field_count_u32 {
+                return Err(#path::ConversionError);
+            }
+            let mut found = 0u32;
+            let val = Self{
+                #(#try_froms,)*
+            };
+            if found != map.len() {
+                return Err(#path::ConversionError);
+            }
+            Ok(val)
+        }
+    } else {
+        quote! {
+            if map.len() != #field_count_u32 {
+                return Err(#path::ConversionError);
+            }
+            Ok(Self{
+                #(#try_froms,)*
+            })
+        }
+    };
+    let try_from_xdr_body = if has_defaults {
+        quote! {
+            if map.len() > #field_count_usize {
+                return Err(#path::xdr::Error::Invalid);
+            }
+            map.validate()?;
+            let mut found = 0usize;
+            let val = Self{
+                #(#try_from_xdrs,)*
+            };
+            if found != map.len() {
+                return Err(#path::xdr::Error::Invalid);
+            }
+            Ok(val)
+        }
+    } else {
+        quote! {
+            if map.len() != #field_count_usize {
+                return Err(#path::xdr::Error::Invalid);
+            }
+            map.validate()?;
+            Ok(Self{
+                #(#try_from_xdrs,)*
+            })
+        }
+    };
+
+    // Conversions for generic types are implemented for the type arguments
+    // that the fields using the type parameters can be converted for.
+    let generic_types = fields
+        .iter()
+        .map(|f| &f.ty)
+        .filter(|t| uses_type_params(t, &params))
+        .collect::<Vec<_>>();
+    let val_where = where_clause(generics, &val_predicates(path, &generic_types));
+    let xdr_where = where_clause(generics, &xdr_predicates(path, &generic_types));
+    let (impl_generics, ty_generics, type_where) = generics.split_for_impl();
+
+    // Generated code spec. Versioned structs are described as a union with a
+    // case for each version, e.g. `V1`, `V2`, that has the fields of that
+    // version as a struct named with the path of the case, e.g. `Balance.V2`,
+    // the same as an enum variant with named fields. The union is generated by
+    // the current version, and the structs of earlier versions, which name the
+    // current version with `version_of`, are generated by those versions.
     let spec_gen = if spec {
-        let spec_entry = ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
-            lib: lib.as_deref().unwrap_or_default().try_into().unwrap(),
-            name: ident.to_string().try_into().unwrap(),
-            fields: spec_fields.try_into().unwrap(),
-        });
-        let spec_xdr = spec_entry.to_xdr().unwrap();
+        let lib = lib.as_deref().unwrap_or_default();
+        let spec_entries = if let (Some(version), Some(version_name)) = (version, &version_name) {
+            let union_name = version_of.clone().unwrap_or_else(|| ident.to_string());
+            let struct_entry = ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+                lib: lib.try_into().unwrap(),
+                name: format!("{}.{}", union_name, version_name)
+                    .try_into()
+                    .unwrap(),
+                fields: spec_fields.try_into().unwrap(),
+            });
+            if version_of.is_some() {
+                vec![struct_entry]
+            } else {
+                let cases = (1..=version)
+                    .map(|v| ScSpecUdtUnionCaseV0 {
+                        name: format!("V{}", v).try_into().unwrap(),
+                        type_: Some(ScSpecTypeDef::Udt(ScSpecTypeUdt {
+                            name: format!("{}.V{}", union_name, v).try_into().unwrap(),
+                        })),
+                    })
+                    .collect::<Vec<_>>();
+                let union_entry = ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 {
+                    lib: lib.try_into().unwrap(),
+                    name: union_name.try_into().unwrap(),
+                    cases: cases.try_into().unwrap(),
+                });
+                vec![union_entry, struct_entry]
+            }
+        } else {
+            vec![ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+                lib: lib.try_into().unwrap(),
+                name: spec_name(ident, &params).try_into().unwrap(),
+                fields: spec_fields.try_into().unwrap(),
+            })]
+        };
+        let spec_xdr = spec_entries
+            .iter()
+            .flat_map(|e| e.to_xdr().unwrap())
+            .collect::<Vec<u8>>();
         let spec_xdr_lit = proc_macro2::Literal::byte_string(spec_xdr.as_slice());
         let spec_xdr_len = spec_xdr.len();
         let spec_ident = format_ident!("__SPEC_XDR_{}", ident.to_string().to_uppercase());
         Some(quote! {
             #[cfg_attr(target_family = "wasm", link_section = "contractspecv0")]
-            pub static #spec_ident: [u8; #spec_xdr_len] = #ident::spec_xdr();
+            pub static #spec_ident: [u8; #spec_xdr_len] = *#spec_xdr_lit;
 
-            impl #ident {
+            impl #impl_generics #ident #ty_generics #type_where {
                 pub const fn spec_xdr() -> [u8; #spec_xdr_len] {
                     *#spec_xdr_lit
                 }
@@ -110,102 +307,155 @@ pub fn derive_type_struct(
         None
     };
 
+    // Versioned structs are converted to and from a vec of the version symbol
+    // followed by the map of the fields. Values of earlier versions are
+    // converted to the previous version of the struct and then migrated. Values
+    // that are not tagged with a version are read by version 1 as a map of the
+    // fields, as stored before the struct was versioned.
+    let (val_to_map, map_to_val, xdr_object_to_self, self_to_xdr_object) = if let Some(
+        version_name,
+    ) = &version_name
+    {
+        let migrate = if version == Some(1) {
+            quote! { val.try_into_val(env)? }
+        } else {
+            quote! {{
+                let previous = <<Self as #path::data::Migrate>::Previous as #path::TryFromVal<#path::Env, #path::RawVal>>::try_from_val(env, val)?;
+                return Ok(<Self as #path::data::Migrate>::migrate(env, previous));
+            }}
+        };
+        (
+            quote! {
+                const VERSION: #path::Symbol = #path::Symbol::from_str(#version_name);
+                let map: #path::Map<#path::Symbol, #path::RawVal> = match <#path::Vec<#path::RawVal> as #path::TryFromVal<#path::Env, #path::RawVal>>::try_from_val(env, val) {
+                    Ok(vec) if vec.get(0).and_then(Result::ok).map(|v| v.get_payload()) == Some(VERSION.to_raw().get_payload()) => {
+                        if vec.len() != 2 {
+                            return Err(#path::ConversionError);
+                        }
+                        vec.get(1).ok_or(#path::ConversionError)??.try_into_val(env)?
+                    }
+                    _ => #migrate,
+                };
+            },
+            quote! {
+                const VERSION: #path::Symbol = #path::Symbol::from_str(#version_name);
+                let map: #path::RawVal = map.into();
+                (VERSION, map).into_val(env)
+            },
+            quote! {
+                use #path::TryIntoVal;
+                let rv: #path::RawVal = (&#path::xdr::ScVal::Object(Some(val))).try_into_val(env).map_err(|_| #path::xdr::Error::Invalid)?;
+                <_ as #path::TryFromVal<_, #path::RawVal>>::try_from_val(env, rv).map_err(|_| #path::xdr::Error::Invalid)
+            },
+            quote! {
+                extern crate alloc;
+                let version: #path::xdr::ScVal = #version_name.try_into().map_err(|_| #path::xdr::Error::Invalid)?;
+                let map = #path::xdr::ScVal::Object(Some(#path::xdr::ScObject::Map(self.try_into()?)));
+                Ok(#path::xdr::ScObject::Vec(#path::xdr::ScVec(alloc::vec![version, map].try_into()?)))
+            },
+        )
+    } else {
+        (
+            quote! {
+                let map: #path::Map<#path::Symbol, #path::RawVal> = val.try_into_val(env)?;
+            },
+            quote! {
+                map.into()
+            },
+            quote! {
+                if let #path::xdr::ScObject::Map(map) = val {
+                    <_ as #path::TryFromVal<_, _>>::try_from_val(env, map)
+                } else {
+                    Err(#path::xdr::Error::Invalid)
+                }
+            },
+            quote! {
+                Ok(#path::xdr::ScObject::Map(self.try_into()?))
+            },
+        )
+    };
+
     // Output.
     quote! {
         #spec_gen
 
-        impl #path::TryFromVal<#path::Env, #path::RawVal> for #ident {
+        impl #impl_generics #path::TryFromVal<#path::Env, #path::RawVal> for #ident #ty_generics #val_where {
             type Error = #path::ConversionError;
             #[inline(always)]
             fn try_from_val(env: &#path::Env, val: #path::RawVal) -> Result<Self, Self::Error> {
                 use #path::TryIntoVal;
-                let map: #path::Map<#path::Symbol, #path::RawVal> = val.try_into_val(env)?;
-                if map.len() != #field_count_u32 {
-                    return Err(#path::ConversionError);
-                }
-                Ok(Self{
-                    #(#try_froms,)*
-                })
+                #val_to_map
+                #try_from_val_body
             }
         }
 
-        impl #path::TryIntoVal<#path::Env, #ident> for #path::RawVal {
+        impl #impl_generics #path::TryIntoVal<#path::Env, #ident #ty_generics> for #path::RawVal #val_where {
             type Error = #path::ConversionError;
             #[inline(always)]
-            fn try_into_val(self, env: &#path::Env) -> Result<#ident, Self::Error> {
+            fn try_into_val(self, env: &#path::Env) -> Result<#ident #ty_generics, Self::Error> {
                 <_ as #path::TryFromVal<_, _>>::try_from_val(env, self)
             }
         }
 
-        impl #path::IntoVal<#path::Env, #path::RawVal> for #ident {
+        impl #impl_generics #path::IntoVal<#path::Env, #path::RawVal> for #ident #ty_generics #val_where {
             #[inline(always)]
             fn into_val(self, env: &#path::Env) -> #path::RawVal {
                 let mut map = #path::Map::<#path::Symbol, #path::RawVal>::new(env);
                 #(#intos;)*
-                map.into()
+                #map_to_val
             }
         }
 
-        impl #path::IntoVal<#path::Env, #path::RawVal> for &#ident {
+        impl #impl_generics #path::IntoVal<#path::Env, #path::RawVal> for &#ident #ty_generics #val_where {
             #[inline(always)]
             fn into_val(self, env: &#path::Env) -> #path::RawVal {
                 let mut map = #path::Map::<#path::Symbol, #path::RawVal>::new(env);
                 #(#intos;)*
-                map.into()
+                #map_to_val
             }
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl #path::TryFromVal<#path::Env, #path::xdr::ScMap> for #ident {
+        impl #impl_generics #path::TryFromVal<#path::Env, #path::xdr::ScMap> for #ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_from_val(env: &#path::Env, val: #path::xdr::ScMap) -> Result<Self, Self::Error> {
                 use #path::xdr::Validate;
                 use #path::TryIntoVal;
                 let map = val;
-                if map.len() != #field_count_usize {
-                    return Err(#path::xdr::Error::Invalid);
-                }
-                map.validate()?;
-                Ok(Self{
-                    #(#try_from_xdrs,)*
-                })
+                #try_from_xdr_body
             }
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl #path::TryIntoVal<#path::Env, #ident> for #path::xdr::ScMap {
+        impl #impl_generics #path::TryIntoVal<#path::Env, #ident #ty_generics> for #path::xdr::ScMap #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
-            fn try_into_val(self, env: &#path::Env) -> Result<#ident, Self::Error> {
+            fn try_into_val(self, env: &#path::Env) -> Result<#ident #ty_generics, Self::Error> {
                 <_ as #path::TryFromVal<_, _>>::try_from_val(env, self)
             }
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl #path::TryFromVal<#path::Env, #path::xdr::ScObject> for #ident {
+        impl #impl_generics #path::TryFromVal<#path::Env, #path::xdr::ScObject> for #ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_from_val(env: &#path::Env, val: #path::xdr::ScObject) -> Result<Self, Self::Error> {
-                if let #path::xdr::ScObject::Map(map) = val {
-                    <_ as #path::TryFromVal<_, _>>::try_from_val(env, map)
-                } else {
-                    Err(#path::xdr::Error::Invalid)
-                }
+                #xdr_object_to_self
             }
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl #path::TryIntoVal<#path::Env, #ident> for #path::xdr::ScObject {
+        impl #impl_generics #path::TryIntoVal<#path::Env, #ident #ty_generics> for #path::xdr::ScObject #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
-            fn try_into_val(self, env: &#path::Env) -> Result<#ident, Self::Error> {
+            fn try_into_val(self, env: &#path::Env) -> Result<#ident #ty_generics, Self::Error> {
                 <_ as #path::TryFromVal<_, _>>::try_from_val(env, self)
             }
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl #path::TryFromVal<#path::Env, #path::xdr::ScVal> for #ident {
+        impl #impl_generics #path::TryFromVal<#path::Env, #path::xdr::ScVal> for #ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_from_val(env: &#path::Env, val: #path::xdr::ScVal) -> Result<Self, Self::Error> {
@@ -218,16 +468,16 @@ pub fn derive_type_struct(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl #path::TryIntoVal<#path::Env, #ident> for #path::xdr::ScVal {
+        impl #impl_generics #path::TryIntoVal<#path::Env, #ident #ty_generics> for #path::xdr::ScVal #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
-            fn try_into_val(self, env: &#path::Env) -> Result<#ident, Self::Error> {
+            fn try_into_val(self, env: &#path::Env) -> Result<#ident #ty_generics, Self::Error> {
                 <_ as #path::TryFromVal<_, _>>::try_from_val(env, self)
             }
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl TryInto<#path::xdr::ScMap> for &#ident {
+        impl #impl_generics TryInto<#path::xdr::ScMap> for &#ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_into(self) -> Result<#path::xdr::ScMap, Self::Error> {
@@ -240,7 +490,7 @@ pub fn derive_type_struct(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl TryInto<#path::xdr::ScMap> for #ident {
+        impl #impl_generics TryInto<#path::xdr::ScMap> for #ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_into(self) -> Result<#path::xdr::ScMap, Self::Error> {
@@ -249,16 +499,16 @@ pub fn derive_type_struct(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl TryInto<#path::xdr::ScObject> for &#ident {
+        impl #impl_generics TryInto<#path::xdr::ScObject> for &#ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_into(self) -> Result<#path::xdr::ScObject, Self::Error> {
-                Ok(#path::xdr::ScObject::Map(self.try_into()?))
+                #self_to_xdr_object
             }
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl TryInto<#path::xdr::ScObject> for #ident {
+        impl #impl_generics TryInto<#path::xdr::ScObject> for #ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_into(self) -> Result<#path::xdr::ScObject, Self::Error> {
@@ -267,7 +517,7 @@ pub fn derive_type_struct(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl TryInto<#path::xdr::ScVal> for &#ident {
+        impl #impl_generics TryInto<#path::xdr::ScVal> for &#ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_into(self) -> Result<#path::xdr::ScVal, Self::Error> {
@@ -276,7 +526,7 @@ pub fn derive_type_struct(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl TryInto<#path::xdr::ScVal> for #ident {
+        impl #impl_generics TryInto<#path::xdr::ScVal> for #ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_into(self) -> Result<#path::xdr::ScVal, Self::Error> {
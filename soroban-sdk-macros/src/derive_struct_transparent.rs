@@ -0,0 +1,172 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, DataStruct, Error, Fields, Generics, Ident, Path};
+
+use stellar_xdr::{
+    ScSpecEntry, ScSpecTypeDef, ScSpecUdtStructFieldV0, ScSpecUdtStructV0, StringM, WriteXdr,
+};
+
+use crate::{
+    generics::{
+        spec_name, type_params, uses_type_params, val_predicates, where_clause, xdr_predicates,
+    },
+    map_type::map_type_generic,
+};
+
+pub fn derive_type_struct_transparent(
+    path: &Path,
+    ident: &Ident,
+    generics: &Generics,
+    data: &DataStruct,
+    spec: bool,
+    lib: &Option<String>,
+) -> TokenStream2 {
+    // Collect errors as they are encountered and emit them at the end.
+    let mut errors = Vec::<Error>::new();
+
+    let params = type_params(generics).unwrap_or_else(|e| {
+        errors.extend(e);
+        vec![]
+    });
+
+    let field = match &data.fields {
+        Fields::Unnamed(f) if f.unnamed.len() == 1 => &f.unnamed[0],
+        _ => {
+            return Error::new(
+                data.fields.span(),
+                "transparent contract types must be tuple structs with exactly one field",
+            )
+            .to_compile_error()
+        }
+    };
+
+    // Transparent structs are described in the spec as a struct with a single
+    // field that has no name, that is an alias of the type of the field.
+    let spec_field = ScSpecUdtStructFieldV0 {
+        name: StringM::default(),
+        type_: match map_type_generic(&field.ty, &params) {
+            Ok(t) => t,
+            Err(e) => {
+                errors.push(e);
+                ScSpecTypeDef::I32
+            }
+        },
+    };
+
+    // If errors have occurred, render them instead.
+    if !errors.is_empty() {
+        let compile_errors = errors.iter().map(Error::to_compile_error);
+        return quote! { #(#compile_errors)* };
+    }
+
+    // Conversions for generic types are implemented for the type arguments
+    // that the field can be converted for.
+    let generic_types = Some(&field.ty)
+        .filter(|t| uses_type_params(t, &params))
+        .into_iter()
+        .collect::<Vec<_>>();
+    let val_where = where_clause(generics, &val_predicates(path, &generic_types));
+    let xdr_where = where_clause(generics, &xdr_predicates(path, &generic_types));
+    let (impl_generics, ty_generics, type_where) = generics.split_for_impl();
+
+    // Generated code spec.
+    let spec_gen = if spec {
+        let spec_entry = ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+            lib: lib.as_deref().unwrap_or_default().try_into().unwrap(),
+            name: spec_name(ident, &params).try_into().unwrap(),
+            fields: vec![spec_field].try_into().unwrap(),
+        });
+        let spec_xdr = spec_entry.to_xdr().unwrap();
+        let spec_xdr_lit = proc_macro2::Literal::byte_string(spec_xdr.as_slice());
+        let spec_xdr_len = spec_xdr.len();
+        let spec_ident = format_ident!("__SPEC_XDR_{}", ident.to_string().to_uppercase());
+        Some(quote! {
+            #[cfg_attr(target_family = "wasm", link_section = "contractspecv0")]
+            pub static #spec_ident: [u8; #spec_xdr_len] = *#spec_xdr_lit;
+
+            impl #impl_generics #ident #ty_generics #type_where {
+                pub const fn spec_xdr() -> [u8; #spec_xdr_len] {
+                    *#spec_xdr_lit
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    // Output.
+    quote! {
+        #spec_gen
+
+        impl #impl_generics #path::TryFromVal<#path::Env, #path::RawVal> for #ident #ty_generics #val_where {
+            type Error = #path::ConversionError;
+            #[inline(always)]
+            fn try_from_val(env: &#path::Env, val: #path::RawVal) -> Result<Self, Self::Error> {
+                use #path::TryIntoVal;
+                Ok(Self(val.try_into_val(env)?))
+            }
+        }
+
+        impl #impl_generics #path::TryIntoVal<#path::Env, #ident #ty_generics> for #path::RawVal #val_where {
+            type Error = #path::ConversionError;
+            #[inline(always)]
+            fn try_into_val(self, env: &#path::Env) -> Result<#ident #ty_generics, Self::Error> {
+                <_ as #path::TryFromVal<_, _>>::try_from_val(env, self)
+            }
+        }
+
+        impl #impl_generics #path::IntoVal<#path::Env, #path::RawVal> for #ident #ty_generics #val_where {
+            #[inline(always)]
+            fn into_val(self, env: &#path::Env) -> #path::RawVal {
+                use #path::IntoVal;
+                (&self.0).into_val(env)
+            }
+        }
+
+        impl #impl_generics #path::IntoVal<#path::Env, #path::RawVal> for &#ident #ty_generics #val_where {
+            #[inline(always)]
+            fn into_val(self, env: &#path::Env) -> #path::RawVal {
+                use #path::IntoVal;
+                (&self.0).into_val(env)
+            }
+        }
+
+        #[cfg(any(test, feature = "testutils"))]
+        impl #impl_generics #path::TryFromVal<#path::Env, #path::xdr::ScVal> for #ident #ty_generics #xdr_where {
+            type Error = #path::xdr::Error;
+            #[inline(always)]
+            fn try_from_val(env: &#path::Env, val: #path::xdr::ScVal) -> Result<Self, Self::Error> {
+                use #path::TryIntoVal;
+                let rv: #path::RawVal = (&val).try_into_val(env).map_err(|_| #path::xdr::Error::Invalid)?;
+                Ok(Self(rv.try_into_val(env).map_err(|_| #path::xdr::Error::Invalid)?))
+            }
+        }
+
+        #[cfg(any(test, feature = "testutils"))]
+        impl #impl_generics #path::TryIntoVal<#path::Env, #ident #ty_generics> for #path::xdr::ScVal #xdr_where {
+            type Error = #path::xdr::Error;
+            #[inline(always)]
+            fn try_into_val(self, env: &#path::Env) -> Result<#ident #ty_generics, Self::Error> {
+                <_ as #path::TryFromVal<_, _>>::try_from_val(env, self)
+            }
+        }
+
+        #[cfg(any(test, feature = "testutils"))]
+        impl #impl_generics TryInto<#path::xdr::ScVal> for &#ident #ty_generics #xdr_where {
+            type Error = #path::xdr::Error;
+            #[inline(always)]
+            fn try_into(self) -> Result<#path::xdr::ScVal, Self::Error> {
+                (&self.0).try_into().map_err(|_| #path::xdr::Error::Invalid)
+            }
+        }
+
+        #[cfg(any(test, feature = "testutils"))]
+        impl #impl_generics TryInto<#path::xdr::ScVal> for #ident #ty_generics #xdr_where {
+            type Error = #path::xdr::Error;
+            #[inline(always)]
+            fn try_into(self) -> Result<#path::xdr::ScVal, Self::Error> {
+                (&self).try_into()
+            }
+        }
+    }
+}
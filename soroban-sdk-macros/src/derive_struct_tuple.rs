@@ -1,17 +1,23 @@
 use itertools::MultiUnzip;
 use proc_macro2::{Literal, TokenStream as TokenStream2};
 use quote::{format_ident, quote};
-use syn::{spanned::Spanned, DataStruct, Error, Ident, Path};
+use syn::{spanned::Spanned, DataStruct, Error, Generics, Ident, Path};
 
 use stellar_xdr::{
     ScSpecEntry, ScSpecTypeDef, ScSpecUdtStructFieldV0, ScSpecUdtStructV0, StringM, WriteXdr,
 };
 
-use crate::map_type::map_type;
+use crate::{
+    generics::{
+        spec_name, type_params, uses_type_params, val_predicates, where_clause, xdr_predicates,
+    },
+    map_type::map_type_generic,
+};
 
 pub fn derive_type_struct_tuple(
     path: &Path,
     ident: &Ident,
+    generics: &Generics,
     data: &DataStruct,
     spec: bool,
     lib: &Option<String>,
@@ -19,6 +25,11 @@ pub fn derive_type_struct_tuple(
     // Collect errors as they are encountered and emit them at the end.
     let mut errors = Vec::<Error>::new();
 
+    let params = type_params(generics).unwrap_or_else(|e| {
+        errors.extend(e);
+        vec![]
+    });
+
     let fields = &data.fields;
     let field_count_usize: usize = fields.len();
     let field_count_u32: u32 = fields.len().try_into().unwrap_or_else(|_| {
@@ -38,7 +49,7 @@ pub fn derive_type_struct_tuple(
             let name = format!("{}", i);
             let spec_field = ScSpecUdtStructFieldV0 {
                 name: name.try_into().unwrap_or_else(|_| StringM::default()),
-                type_: match map_type(&f.ty) {
+                type_: match map_type_generic(&f.ty, &params) {
                     Ok(t) => t,
                     Err(e) => {
                         errors.push(e);
@@ -73,11 +84,22 @@ pub fn derive_type_struct_tuple(
         return quote! { #(#compile_errors)* };
     }
 
+    // Conversions for generic types are implemented for the type arguments
+    // that the fields using the type parameters can be converted for.
+    let generic_types = fields
+        .iter()
+        .map(|f| &f.ty)
+        .filter(|t| uses_type_params(t, &params))
+        .collect::<Vec<_>>();
+    let val_where = where_clause(generics, &val_predicates(path, &generic_types));
+    let xdr_where = where_clause(generics, &xdr_predicates(path, &generic_types));
+    let (impl_generics, ty_generics, type_where) = generics.split_for_impl();
+
     // Generated code spec.
     let spec_gen = if spec {
         let spec_entry = ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
             lib: lib.as_deref().unwrap_or_default().try_into().unwrap(),
-            name: ident.to_string().try_into().unwrap(),
+            name: spec_name(ident, &params).try_into().unwrap(),
             fields: spec_fields.try_into().unwrap(),
         });
         let spec_xdr = spec_entry.to_xdr().unwrap();
@@ -86,9 +108,9 @@ pub fn derive_type_struct_tuple(
         let spec_ident = format_ident!("__SPEC_XDR_{}", ident.to_string().to_uppercase());
         Some(quote! {
             #[cfg_attr(target_family = "wasm", link_section = "contractspecv0")]
-            pub static #spec_ident: [u8; #spec_xdr_len] = #ident::spec_xdr();
+            pub static #spec_ident: [u8; #spec_xdr_len] = *#spec_xdr_lit;
 
-            impl #ident {
+            impl #impl_generics #ident #ty_generics #type_where {
                 pub const fn spec_xdr() -> [u8; #spec_xdr_len] {
                     *#spec_xdr_lit
                 }
@@ -102,7 +124,7 @@ pub fn derive_type_struct_tuple(
     quote! {
         #spec_gen
 
-        impl #path::TryFromVal<#path::Env, #path::RawVal> for #ident {
+        impl #impl_generics #path::TryFromVal<#path::Env, #path::RawVal> for #ident #ty_generics #val_where {
             type Error = #path::ConversionError;
             #[inline(always)]
             fn try_from_val(env: &#path::Env, val: #path::RawVal) -> Result<Self, Self::Error> {
@@ -117,15 +139,15 @@ pub fn derive_type_struct_tuple(
             }
         }
 
-        impl #path::TryIntoVal<#path::Env, #ident> for #path::RawVal {
+        impl #impl_generics #path::TryIntoVal<#path::Env, #ident #ty_generics> for #path::RawVal #val_where {
             type Error = #path::ConversionError;
             #[inline(always)]
-            fn try_into_val(self, env: &#path::Env) -> Result<#ident, Self::Error> {
+            fn try_into_val(self, env: &#path::Env) -> Result<#ident #ty_generics, Self::Error> {
                 <_ as #path::TryFromVal<_, _>>::try_from_val(env, self)
             }
         }
 
-        impl #path::IntoVal<#path::Env, #path::RawVal> for #ident {
+        impl #impl_generics #path::IntoVal<#path::Env, #path::RawVal> for #ident #ty_generics #val_where {
             #[inline(always)]
             fn into_val(self, env: &#path::Env) -> #path::RawVal {
                 let mut vec = #path::Vec::<#path::RawVal>::new(env);
@@ -134,7 +156,7 @@ pub fn derive_type_struct_tuple(
             }
         }
 
-        impl #path::IntoVal<#path::Env, #path::RawVal> for &#ident {
+        impl #impl_generics #path::IntoVal<#path::Env, #path::RawVal> for &#ident #ty_generics #val_where {
             #[inline(always)]
             fn into_val(self, env: &#path::Env) -> #path::RawVal {
                 let mut vec = #path::Vec::<#path::RawVal>::new(env);
@@ -144,7 +166,7 @@ pub fn derive_type_struct_tuple(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl #path::TryFromVal<#path::Env, #path::xdr::ScVec> for #ident {
+        impl #impl_generics #path::TryFromVal<#path::Env, #path::xdr::ScVec> for #ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_from_val(env: &#path::Env, val: #path::xdr::ScVec) -> Result<Self, Self::Error> {
@@ -161,16 +183,16 @@ pub fn derive_type_struct_tuple(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl #path::TryIntoVal<#path::Env, #ident> for #path::xdr::ScVec {
+        impl #impl_generics #path::TryIntoVal<#path::Env, #ident #ty_generics> for #path::xdr::ScVec #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
-            fn try_into_val(self, env: &#path::Env) -> Result<#ident, Self::Error> {
+            fn try_into_val(self, env: &#path::Env) -> Result<#ident #ty_generics, Self::Error> {
                 <_ as #path::TryFromVal<_, _>>::try_from_val(env, self)
             }
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl #path::TryFromVal<#path::Env, #path::xdr::ScObject> for #ident {
+        impl #impl_generics #path::TryFromVal<#path::Env, #path::xdr::ScObject> for #ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_from_val(env: &#path::Env, val: #path::xdr::ScObject) -> Result<Self, Self::Error> {
@@ -183,16 +205,16 @@ pub fn derive_type_struct_tuple(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl #path::TryIntoVal<#path::Env, #ident> for #path::xdr::ScObject {
+        impl #impl_generics #path::TryIntoVal<#path::Env, #ident #ty_generics> for #path::xdr::ScObject #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
-            fn try_into_val(self, env: &#path::Env) -> Result<#ident, Self::Error> {
+            fn try_into_val(self, env: &#path::Env) -> Result<#ident #ty_generics, Self::Error> {
                 <_ as #path::TryFromVal<_, _>>::try_from_val(env, self)
             }
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl #path::TryFromVal<#path::Env, #path::xdr::ScVal> for #ident {
+        impl #impl_generics #path::TryFromVal<#path::Env, #path::xdr::ScVal> for #ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_from_val(env: &#path::Env, val: #path::xdr::ScVal) -> Result<Self, Self::Error> {
@@ -205,16 +227,16 @@ pub fn derive_type_struct_tuple(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl #path::TryIntoVal<#path::Env, #ident> for #path::xdr::ScVal {
+        impl #impl_generics #path::TryIntoVal<#path::Env, #ident #ty_generics> for #path::xdr::ScVal #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
-            fn try_into_val(self, env: &#path::Env) -> Result<#ident, Self::Error> {
+            fn try_into_val(self, env: &#path::Env) -> Result<#ident #ty_generics, Self::Error> {
                 <_ as #path::TryFromVal<_, _>>::try_from_val(env, self)
             }
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl TryInto<#path::xdr::ScVec> for &#ident {
+        impl #impl_generics TryInto<#path::xdr::ScVec> for &#ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_into(self) -> Result<#path::xdr::ScVec, Self::Error> {
@@ -227,7 +249,7 @@ pub fn derive_type_struct_tuple(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl TryInto<#path::xdr::ScVec> for #ident {
+        impl #impl_generics TryInto<#path::xdr::ScVec> for #ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_into(self) -> Result<#path::xdr::ScVec, Self::Error> {
@@ -236,7 +258,7 @@ pub fn derive_type_struct_tuple(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl TryInto<#path::xdr::ScObject> for &#ident {
+        impl #impl_generics TryInto<#path::xdr::ScObject> for &#ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_into(self) -> Result<#path::xdr::ScObject, Self::Error> {
@@ -245,7 +267,7 @@ pub fn derive_type_struct_tuple(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl TryInto<#path::xdr::ScObject> for #ident {
+        impl #impl_generics TryInto<#path::xdr::ScObject> for #ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_into(self) -> Result<#path::xdr::ScObject, Self::Error> {
@@ -254,7 +276,7 @@ pub fn derive_type_struct_tuple(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl TryInto<#path::xdr::ScVal> for &#ident {
+        impl #impl_generics TryInto<#path::xdr::ScVal> for &#ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_into(self) -> Result<#path::xdr::ScVal, Self::Error> {
@@ -263,7 +285,7 @@ pub fn derive_type_struct_tuple(
         }
 
         #[cfg(any(test, feature = "testutils"))]
-        impl TryInto<#path::xdr::ScVal> for #ident {
+        impl #impl_generics TryInto<#path::xdr::ScVal> for #ident #ty_generics #xdr_where {
             type Error = #path::xdr::Error;
             #[inline(always)]
             fn try_into(self) -> Result<#path::xdr::ScVal, Self::Error> {
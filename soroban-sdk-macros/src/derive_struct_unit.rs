@@ -0,0 +1,126 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Error, Generics, Ident, Path};
+
+use stellar_xdr::{ScSpecEntry, ScSpecUdtStructV0, VecM, WriteXdr};
+
+pub fn derive_type_struct_unit(
+    path: &Path,
+    ident: &Ident,
+    generics: &Generics,
+    spec: bool,
+    lib: &Option<String>,
+) -> TokenStream2 {
+    if !generics.params.is_empty() {
+        return Error::new(
+            ident.span(),
+            "unit structs with generic parameters are not supported as contract types",
+        )
+        .to_compile_error();
+    }
+
+    // Generated code spec. Unit structs are described in the spec as a struct
+    // with no fields.
+    let spec_gen = if spec {
+        let spec_entry = ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+            lib: lib.as_deref().unwrap_or_default().try_into().unwrap(),
+            name: ident.to_string().try_into().unwrap(),
+            fields: VecM::default(),
+        });
+        let spec_xdr = spec_entry.to_xdr().unwrap();
+        let spec_xdr_lit = proc_macro2::Literal::byte_string(spec_xdr.as_slice());
+        let spec_xdr_len = spec_xdr.len();
+        let spec_ident = format_ident!("__SPEC_XDR_{}", ident.to_string().to_uppercase());
+        Some(quote! {
+            #[cfg_attr(target_family = "wasm", link_section = "contractspecv0")]
+            pub static #spec_ident: [u8; #spec_xdr_len] = *#spec_xdr_lit;
+
+            impl #ident {
+                pub const fn spec_xdr() -> [u8; #spec_xdr_len] {
+                    *#spec_xdr_lit
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    // Output. Unit structs are void values.
+    quote! {
+        #spec_gen
+
+        impl #path::TryFromVal<#path::Env, #path::RawVal> for #ident {
+            type Error = #path::ConversionError;
+            #[inline(always)]
+            fn try_from_val(_env: &#path::Env, val: #path::RawVal) -> Result<Self, Self::Error> {
+                if val.is_void() {
+                    Ok(Self)
+                } else {
+                    Err(#path::ConversionError)
+                }
+            }
+        }
+
+        impl #path::TryIntoVal<#path::Env, #ident> for #path::RawVal {
+            type Error = #path::ConversionError;
+            #[inline(always)]
+            fn try_into_val(self, env: &#path::Env) -> Result<#ident, Self::Error> {
+                <_ as #path::TryFromVal<_, _>>::try_from_val(env, self)
+            }
+        }
+
+        impl #path::IntoVal<#path::Env, #path::RawVal> for #ident {
+            #[inline(always)]
+            fn into_val(self, _env: &#path::Env) -> #path::RawVal {
+                #path::RawVal::from_void()
+            }
+        }
+
+        impl #path::IntoVal<#path::Env, #path::RawVal> for &#ident {
+            #[inline(always)]
+            fn into_val(self, _env: &#path::Env) -> #path::RawVal {
+                #path::RawVal::from_void()
+            }
+        }
+
+        #[cfg(any(test, feature = "testutils"))]
+        impl #path::TryFromVal<#path::Env, #path::xdr::ScVal> for #ident {
+            type Error = #path::xdr::Error;
+            #[inline(always)]
+            fn try_from_val(_env: &#path::Env, val: #path::xdr::ScVal) -> Result<Self, Self::Error> {
+                if let #path::xdr::ScVal::Static(#path::xdr::ScStatic::Void) = val {
+                    Ok(Self)
+                } else {
+                    Err(#path::xdr::Error::Invalid)
+                }
+            }
+        }
+
+        #[cfg(any(test, feature = "testutils"))]
+        impl #path::TryIntoVal<#path::Env, #ident> for #path::xdr::ScVal {
+            type Error = #path::xdr::Error;
+            #[inline(always)]
+            fn try_into_val(self, env: &#path::Env) -> Result<#ident, Self::Error> {
+                <_ as #path::TryFromVal<_, _>>::try_from_val(env, self)
+            }
+        }
+
+        #[cfg(any(test, feature = "testutils"))]
+        impl TryInto<#path::xdr::ScVal> for &#ident {
+            type Error = #path::xdr::Error;
+            #[inline(always)]
+            fn try_into(self) -> Result<#path::xdr::ScVal, Self::Error> {
+                Ok(#path::xdr::ScVal::Static(#path::xdr::ScStatic::Void))
+            }
+        }
+
+        #[cfg(any(test, feature = "testutils"))]
+        impl TryInto<#path::xdr::ScVal> for #ident {
+            type Error = #path::xdr::Error;
+            #[inline(always)]
+            fn try_into(self) -> Result<#path::xdr::ScVal, Self::Error> {
+                (&self).try_into()
+            }
+        }
+    }
+}
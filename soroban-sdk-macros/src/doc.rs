@@ -0,0 +1,123 @@
+use itertools::Itertools;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use soroban_spec::docs::Docs;
+use syn::{Attribute, Data, DeriveInput, Fields, Lit, Meta, MetaNameValue};
+
+use crate::generics::spec_name;
+
+/// Returns the doc string of the doc attributes, with the leading space that
+/// doc comments have on each line removed.
+pub fn docs_from_attrs(attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .filter(|a| a.path.is_ident("doc"))
+        .filter_map(|a| match a.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(s), ..
+            })) => Some(s.value()),
+            _ => None,
+        })
+        .map(|s| s.strip_prefix(' ').map(str::to_string).unwrap_or(s))
+        .join("\n")
+}
+
+/// Returns the doc strings of the inputs of a function, documented in the doc
+/// string of the function as a list of the form:
+///
+/// ```text
+/// * `input` - Doc string of the input.
+/// ```
+pub fn docs_for_inputs<'a>(
+    fn_name: &str,
+    fn_doc: &str,
+    inputs: impl IntoIterator<Item = &'a str>,
+) -> Docs {
+    let mut docs = Docs::new();
+    for input in inputs {
+        let prefixes = [format!("* `{input}`"), format!("- `{input}`")];
+        let doc = fn_doc.lines().map(str::trim).find_map(|l| {
+            prefixes
+                .iter()
+                .find_map(|p| l.strip_prefix(p.as_str()))
+                .map(|rest| rest.trim_start_matches([' ', '-', ':']).to_string())
+        });
+        if let Some(doc) = doc {
+            docs.insert(format!("{fn_name}.{input}"), doc);
+        }
+    }
+    docs
+}
+
+/// Returns the doc strings of the type, and its fields, cases, or variants,
+/// with paths starting with the name of the type in the spec.
+pub fn docs_for_type(input: &DeriveInput) -> Docs {
+    let params = input
+        .generics
+        .type_params()
+        .map(|p| p.ident.clone())
+        .collect::<Vec<_>>();
+    let name = spec_name(&input.ident, &params);
+    let mut docs = Docs::new();
+    docs.insert(&name, docs_from_attrs(&input.attrs));
+    match &input.data {
+        Data::Struct(s) => {
+            if let Fields::Named(_) = s.fields {
+                for f in &s.fields {
+                    if let Some(ident) = &f.ident {
+                        docs.insert(format!("{name}.{ident}"), docs_from_attrs(&f.attrs));
+                    }
+                }
+            } else {
+                for (i, f) in s.fields.iter().enumerate() {
+                    docs.insert(format!("{name}.{i}"), docs_from_attrs(&f.attrs));
+                }
+            }
+        }
+        Data::Enum(e) => {
+            for v in &e.variants {
+                docs.insert(format!("{name}.{}", v.ident), docs_from_attrs(&v.attrs));
+            }
+        }
+        Data::Union(_) => {}
+    }
+    docs
+}
+
+/// Generates a static in the `contractdocsv0` custom section containing the
+/// doc strings, or nothing if there are no doc strings.
+///
+/// The kind of item, such as `FN` or `TYPE`, is part of the name of the
+/// static, so that the doc strings of functions and types with the same name
+/// do not collide.
+pub fn docs_gen(kind: &str, name: &str, attrs: &[Attribute], docs: &Docs) -> TokenStream2 {
+    if docs.is_empty() {
+        return quote! {};
+    }
+    let docs_xdr = match docs.to_xdr() {
+        Ok(xdr) => xdr,
+        Err(e) => {
+            let msg = format!("doc strings too long: {e}");
+            return quote! { compile_error!(#msg); };
+        }
+    };
+    let docs_xdr_lit = proc_macro2::Literal::byte_string(docs_xdr.as_slice());
+    let docs_xdr_len = docs_xdr.len();
+    let docs_ident = format_ident!("__DOCS_XDR_{}_{}", kind, name.to_uppercase());
+    quote! {
+        #[doc(hidden)]
+        #(#attrs)*
+        #[cfg_attr(target_family = "wasm", link_section = "contractdocsv0")]
+        pub static #docs_ident: [u8; #docs_xdr_len] = *#docs_xdr_lit;
+    }
+}
+
+/// Generates a static in the `contractdocsv0` custom section containing the
+/// doc strings of the type, if the type is included in the spec.
+pub fn derive_type_docs(input: &DeriveInput, spec: bool) -> TokenStream2 {
+    if spec {
+        docs_gen("TYPE", &input.ident.to_string(), &[], &docs_for_type(input))
+    } else {
+        quote! {}
+    }
+}
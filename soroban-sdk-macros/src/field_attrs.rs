@@ -0,0 +1,125 @@
+use darling::FromMeta;
+use syn::{spanned::Spanned, AttributeArgs, Data, Error, Field, Fields, Meta};
+
+/// Name of the attribute on fields of contract types that configures how the
+/// field is converted.
+const FIELD_ATTR: &str = "contracttype";
+
+/// Options of a field of a contract type, set with a `#[contracttype(...)]`
+/// attribute on the field.
+#[derive(Debug, Default, FromMeta)]
+pub struct FieldArgs {
+    /// Name of the field in the spec and in the map the struct is converted
+    /// to, if different to the name of the field.
+    pub rename: Option<String>,
+    /// Whether the field is its default value when it is missing from the map
+    /// the struct is converted from.
+    #[darling(default)]
+    pub default: bool,
+    /// Whether the field is left out of the spec and the map the struct is
+    /// converted to, and is always its default value when converted from.
+    #[darling(default)]
+    pub skip: bool,
+}
+
+/// Takes the field attributes off the fields of the type, so that the type can
+/// be emitted without them, and returns the options they set for each field of
+/// a struct with named fields.
+///
+/// Field attributes are only supported on fields of structs with named fields,
+/// and an error is returned for any on other fields.
+pub fn take_field_args(data: &mut Data) -> Result<Vec<FieldArgs>, Vec<Error>> {
+    let mut errors = Vec::<Error>::new();
+    let mut field_args = Vec::<FieldArgs>::new();
+    match data {
+        Data::Struct(s) => {
+            let named = matches!(s.fields, Fields::Named(_));
+            for f in s.fields.iter_mut() {
+                match take_attr(f) {
+                    Ok(Some(_)) if !named => errors.push(unsupported(f)),
+                    Ok(args) => field_args.push(args.unwrap_or_default()),
+                    Err(e) => errors.extend(e),
+                }
+            }
+        }
+        Data::Enum(e) => {
+            for f in e.variants.iter_mut().flat_map(|v| v.fields.iter_mut()) {
+                match take_attr(f) {
+                    Ok(Some(_)) => errors.push(unsupported(f)),
+                    Ok(None) => (),
+                    Err(e) => errors.extend(e),
+                }
+            }
+        }
+        Data::Union(_) => (),
+    }
+    if errors.is_empty() {
+        Ok(field_args)
+    } else {
+        Err(errors)
+    }
+}
+
+fn unsupported(f: &Field) -> Error {
+    Error::new(
+        f.span(),
+        "field attributes are only supported on fields of structs with named fields",
+    )
+}
+
+fn take_attr(f: &mut Field) -> Result<Option<FieldArgs>, Vec<Error>> {
+    let (field_attrs, attrs) = f
+        .attrs
+        .drain(..)
+        .partition::<Vec<_>, _>(|a| a.path.is_ident(FIELD_ATTR));
+    f.attrs = attrs;
+    let mut field_attrs = field_attrs.into_iter();
+    let attr = match field_attrs.next() {
+        Some(a) => a,
+        None => return Ok(None),
+    };
+    let mut errors = field_attrs
+        .map(|a| {
+            Error::new(
+                a.span(),
+                format!("field has more than one #[{}] attribute", FIELD_ATTR),
+            )
+        })
+        .collect::<Vec<_>>();
+    let args = match attr.parse_meta() {
+        Ok(Meta::List(l)) => {
+            let nested = l.nested.into_iter().collect::<AttributeArgs>();
+            match FieldArgs::from_list(&nested) {
+                Ok(args) => Some(args),
+                Err(e) => {
+                    errors.push(Error::new(attr.span(), e));
+                    None
+                }
+            }
+        }
+        Ok(m) => {
+            errors.push(Error::new(
+                m.span(),
+                format!("expected #[{}(...)]", FIELD_ATTR),
+            ));
+            None
+        }
+        Err(e) => {
+            errors.push(e);
+            None
+        }
+    };
+    if let Some(FieldArgs {
+        rename: Some(_),
+        skip: true,
+        ..
+    }) = args
+    {
+        errors.push(Error::new(attr.span(), "skipped fields cannot be renamed"));
+    }
+    if errors.is_empty() {
+        Ok(args)
+    } else {
+        Err(errors)
+    }
+}
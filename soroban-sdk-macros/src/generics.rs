@@ -0,0 +1,93 @@
+use itertools::Itertools;
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{spanned::Spanned, Error, GenericParam, Generics, Ident, Path, Type};
+
+/// Returns the identifiers of the type parameters of the generics, or an error
+/// for each lifetime or const parameter, which are unsupported on contract
+/// types.
+pub fn type_params(generics: &Generics) -> Result<Vec<Ident>, Vec<Error>> {
+    let mut errors = Vec::<Error>::new();
+    let mut params = Vec::<Ident>::new();
+    for p in &generics.params {
+        match p {
+            GenericParam::Type(t) => params.push(t.ident.clone()),
+            GenericParam::Lifetime(_) | GenericParam::Const(_) => errors.push(Error::new(
+                p.span(),
+                "only type parameters are supported on contract types",
+            )),
+        }
+    }
+    if errors.is_empty() {
+        Ok(params)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Returns the name of the type in the spec. Generic types are named with their
+/// type parameters, e.g. `Page<T>`, which the types of their fields refer to.
+pub fn spec_name(ident: &Ident, params: &[Ident]) -> String {
+    if params.is_empty() {
+        ident.to_string()
+    } else {
+        format!("{}<{}>", ident, params.iter().join(", "))
+    }
+}
+
+/// Returns true if the type refers to any of the type parameters.
+pub fn uses_type_params(t: &Type, params: &[Ident]) -> bool {
+    fn tokens_use(tokens: TokenStream2, params: &[Ident]) -> bool {
+        tokens.into_iter().any(|tt| match tt {
+            TokenTree::Ident(i) => params.contains(&i),
+            TokenTree::Group(g) => tokens_use(g.stream(), params),
+            _ => false,
+        })
+    }
+    !params.is_empty() && tokens_use(t.to_token_stream(), params)
+}
+
+/// Returns the predicates required of the types for values of the types to be
+/// converted to and from `RawVal`s.
+pub fn val_predicates(path: &Path, types: &[&Type]) -> Vec<TokenStream2> {
+    types
+        .iter()
+        .flat_map(|t| {
+            [
+                quote! { #path::RawVal: #path::TryIntoVal<#path::Env, #t> },
+                quote! { #path::ConversionError: From<<#path::RawVal as #path::TryIntoVal<#path::Env, #t>>::Error> },
+                quote! { for<'a> &'a #t: #path::IntoVal<#path::Env, #path::RawVal> },
+            ]
+        })
+        .collect()
+}
+
+/// Returns the predicates required of the types for values of the types to be
+/// converted to and from XDR.
+pub fn xdr_predicates(path: &Path, types: &[&Type]) -> Vec<TokenStream2> {
+    types
+        .iter()
+        .flat_map(|t| {
+            [
+                quote! { #path::RawVal: #path::TryIntoVal<#path::Env, #t> },
+                quote! { for<'a> &'a #t: TryInto<#path::xdr::ScVal> },
+            ]
+        })
+        .collect()
+}
+
+/// Returns a where clause containing the predicates of the generics where
+/// clause along with the additional predicates, or nothing if there are none.
+pub fn where_clause(generics: &Generics, predicates: &[TokenStream2]) -> TokenStream2 {
+    let predicates = generics
+        .where_clause
+        .iter()
+        .flat_map(|w| w.predicates.iter().map(ToTokens::to_token_stream))
+        .chain(predicates.iter().cloned())
+        .collect::<Vec<_>>();
+    if predicates.is_empty() {
+        quote! {}
+    } else {
+        quote! { where #(#predicates,)* }
+    }
+}
@@ -4,9 +4,16 @@ mod derive_client;
 mod derive_enum;
 mod derive_enum_int;
 mod derive_error_enum_int;
+mod derive_event;
 mod derive_fn;
+mod derive_storage;
 mod derive_struct;
+mod derive_struct_transparent;
 mod derive_struct_tuple;
+mod derive_struct_unit;
+mod doc;
+mod field_attrs;
+mod generics;
 mod map_type;
 mod path;
 mod syn_ext;
@@ -15,9 +22,15 @@ use derive_client::derive_client;
 use derive_enum::derive_type_enum;
 use derive_enum_int::derive_type_enum_int;
 use derive_error_enum_int::derive_type_error_enum_int;
+use derive_event::derive_event;
 use derive_fn::{derive_contract_function_set, derive_fn};
+use derive_storage::derive_storage;
 use derive_struct::derive_type_struct;
+use derive_struct_transparent::derive_type_struct_transparent;
 use derive_struct_tuple::derive_type_struct_tuple;
+use derive_struct_unit::derive_type_struct_unit;
+use doc::derive_type_docs;
+use field_attrs::take_field_args;
 
 use darling::FromMeta;
 use proc_macro::TokenStream;
@@ -26,15 +39,18 @@ use quote::quote;
 use sha2::{Digest, Sha256};
 use std::fs;
 use syn::{
-    parse_macro_input, parse_str, spanned::Spanned, AttributeArgs, Data, DeriveInput, Error,
-    Fields, ItemImpl, LitStr, Path, Type, Visibility,
+    parse_macro_input, parse_str, spanned::Spanned, AttributeArgs, Data, DataStruct, DeriveInput,
+    Error, Fields, ItemImpl, LitStr, Path, Type, Visibility,
 };
 
 use self::derive_client::ClientItem;
 
-use soroban_spec::gen::rust::{generate_from_wasm, GenerateFromFileError};
+use soroban_spec::{
+    gen::{rust::generate_from_wasm, GenerateFromFileError},
+    meta::check_interface_version,
+};
 
-use soroban_env_common::Symbol;
+use soroban_env_common::{meta, Symbol};
 
 fn default_crate_path() -> Path {
     parse_str("soroban_sdk").unwrap()
@@ -121,6 +137,10 @@ struct ContractTypeArgs {
     crate_path: Path,
     lib: Option<String>,
     export: Option<bool>,
+    #[darling(default)]
+    transparent: bool,
+    version: Option<u32>,
+    version_of: Option<String>,
 }
 
 /// Generates conversions from the struct/enum from/into a `RawVal`.
@@ -129,12 +149,53 @@ struct ContractTypeArgs {
 /// - Enums with integer values must have an explicit integer literal for every
 /// variant.
 /// - Enums with unit variants are supported.
-/// - Enums with tuple-like variants with a maximum of one tuple field are
-/// supported. The tuple field must be of a type that is also convertible to and
-/// from `RawVal`.
-/// - Enums with struct-like variants are not supported.
+/// - Enums with tuple-like variants are supported. The tuple fields must be of
+/// types that are also convertible to and from `RawVal`. Variants with multiple
+/// fields are described in the spec as having a tuple of the fields as their
+/// value.
+/// - Enums with struct-like variants are supported. The fields must be of
+/// types that are also convertible to and from `RawVal`. The fields of each
+/// such variant are described in the spec by a struct named with the path of
+/// the variant, e.g. `State.Auction` for the `Auction` variant of `State`.
 /// - Structs are supported. All fields must be of a type that is also
-/// convertible to and from `RawVal`.
+/// convertible to and from `RawVal`. Structs with named fields must have at
+/// least one field that is not skipped, so that they are not described the
+/// same as unit structs.
+/// - Fields of structs with named fields may have a `#[contracttype(...)]`
+/// attribute with the options:
+///   - `rename = "name"`: The name of the field in the spec and in the map the
+///   struct is converted to, instead of the name of the field.
+///   - `default`: The field is its `Default` value when it is missing from the
+///   map the struct is converted from, so that values stored before the field
+///   was added can still be converted. The spec does not mark fields with a
+///   default, so off-chain decoders, such as the bindings generated from the
+///   spec and the JSON conversion in `soroban-spec`, do not support them, and
+///   fail to decode values where the field is missing.
+///   - `skip`: The field is left out of the spec and the map the struct is
+///   converted to, and is always its `Default` value when converted from.
+/// - Structs with named fields can be versioned with the `version` option, e.g.
+/// `#[contracttype(version = 2)]`, so that values stored by earlier versions of
+/// a contract can be read after it is upgraded. Values are tagged with their
+/// version, and values of earlier versions are migrated when converted, see
+/// `data::Migrate`. Values stored before the struct was versioned are read as
+/// version 1. Earlier versions must name the current version with the
+/// `version_of` option, e.g. `#[contracttype(version = 1, version_of =
+/// "Balance")]`. The current version is described in the spec as a union with a
+/// case for each version, e.g. `V1` and `V2`, with the fields of each version
+/// described by a struct named with the path of the case, e.g. `Balance.V1`.
+/// - Unit structs are supported, and are converted to and from a void
+/// `RawVal`. They are described in the spec as a struct with no fields. An
+/// `Option` of a unit struct cannot distinguish `Some` from `None`.
+/// - Tuple structs with a single field, newtypes, are converted the same as
+/// their field when the `transparent` option is set, e.g.
+/// `#[contracttype(transparent)]`. They are described in the spec as a struct
+/// with a single unnamed field, an alias of the field's type.
+/// - Structs and enums with type parameters are supported, and are convertible
+/// for the type arguments their fields are convertible for. They are described
+/// in the spec named with their type parameters, e.g. `Page<T>`, and uses of
+/// them are named with their type arguments, e.g. `Page<u32>`. Bindings
+/// generated from the spec have a type for each instance used by the contract,
+/// e.g. `PageU32`.
 /// - All variant names, field names, and type names must be 10-characters or
 /// less in length.
 ///
@@ -147,7 +208,20 @@ pub fn contracttype(metadata: TokenStream, input: TokenStream) -> TokenStream {
         Ok(v) => v,
         Err(e) => return e.write_errors().into(),
     };
-    let input = parse_macro_input!(input as DeriveInput);
+    let mut input = parse_macro_input!(input as DeriveInput);
+    // Take the field attributes off the fields, so that the type can be
+    // emitted without them.
+    let field_args = match take_field_args(&mut input.data) {
+        Ok(field_args) => field_args,
+        Err(errors) => {
+            let compile_errors = errors.iter().map(Error::to_compile_error);
+            return quote! {
+                #input
+                #(#compile_errors)*
+            }
+            .into();
+        }
+    };
     let ident = &input.ident;
     // If the export argument has a value, do as it instructs regarding
     // exporting. If it does not have a value, export if the type is pub.
@@ -157,16 +231,60 @@ pub fn contracttype(metadata: TokenStream, input: TokenStream) -> TokenStream {
         matches!(input.vis, Visibility::Public(_))
     };
     let derived = match &input.data {
-        Data::Struct(s) => match s.fields {
-            Fields::Named(_) => derive_type_struct(&args.crate_path, ident, s, gen_spec, &args.lib),
-            Fields::Unnamed(_) => {
-                derive_type_struct_tuple(&args.crate_path, ident, s, gen_spec, &args.lib)
-            }
-            Fields::Unit => Error::new(
-                s.fields.span(),
-                "unit structs are not supported as contract types",
+        Data::Struct(s) if args.transparent => derive_type_struct_transparent(
+            &args.crate_path,
+            ident,
+            &input.generics,
+            s,
+            gen_spec,
+            &args.lib,
+        ),
+        Data::Enum(_) | Data::Union(_) if args.transparent => Error::new(
+            input.span(),
+            "transparent contract types must be tuple structs with exactly one field",
+        )
+        .to_compile_error(),
+        Data::Struct(DataStruct {
+            fields: Fields::Unnamed(_) | Fields::Unit,
+            ..
+        })
+        | Data::Enum(_)
+        | Data::Union(_)
+            if args.version.is_some() || args.version_of.is_some() =>
+        {
+            Error::new(
+                input.span(),
+                "versioned contract types must be structs with named fields",
             )
-            .to_compile_error(),
+            .to_compile_error()
+        }
+        Data::Struct(s) => match s.fields {
+            Fields::Named(_) => derive_type_struct(
+                &args.crate_path,
+                ident,
+                &input.generics,
+                s,
+                &field_args,
+                args.version,
+                &args.version_of,
+                gen_spec,
+                &args.lib,
+            ),
+            Fields::Unnamed(_) => derive_type_struct_tuple(
+                &args.crate_path,
+                ident,
+                &input.generics,
+                s,
+                gen_spec,
+                &args.lib,
+            ),
+            Fields::Unit => derive_type_struct_unit(
+                &args.crate_path,
+                ident,
+                &input.generics,
+                gen_spec,
+                &args.lib,
+            ),
         },
         Data::Enum(e) => {
             let count_of_variants = e.variants.len();
@@ -176,11 +294,18 @@ pub fn contracttype(metadata: TokenStream, input: TokenStream) -> TokenStream {
                 .filter(|v| v.discriminant.is_some())
                 .count();
             if count_of_int_variants == 0 {
-                derive_type_enum(&args.crate_path, ident, e, gen_spec, &args.lib)
+                derive_type_enum(
+                    &args.crate_path,
+                    ident,
+                    &input.generics,
+                    e,
+                    gen_spec,
+                    &args.lib,
+                )
             } else if count_of_int_variants == count_of_variants {
                 derive_type_enum_int(&args.crate_path, ident, e, gen_spec, &args.lib)
             } else {
-                Error::new(input.span(), "enums are supported as contract types only when all variants have an explicit integer literal, or when all variants are unit or single field")
+                Error::new(input.span(), "enums are supported as contract types only when all variants have an explicit integer literal, or when all variants are unit or tuple variants")
                     .to_compile_error()
             }
         }
@@ -190,6 +315,152 @@ pub fn contracttype(metadata: TokenStream, input: TokenStream) -> TokenStream {
         )
         .to_compile_error(),
     };
+    let docs = derive_type_docs(&input, gen_spec);
+    quote! {
+        #input
+        #derived
+        #docs
+    }
+    .into()
+}
+
+/// Generates typed accessors for contract data from an enum of storage keys.
+///
+/// Each variant of the enum is a key, and must have a `#[value(Type)]`
+/// attribute declaring the type of the value stored with the key. Variants may
+/// have a single field, either a tuple field or a named field, in which case the
+/// key is parameterized by the field.
+///
+/// The enum is a contract type, converting from/into a `RawVal` the same as
+/// enums with [`contracttype`]. A trait named `{Enum}Storage` is generated
+/// with `get_`, `try_get_`, `set_`, `has_`, and `remove_` functions for each
+/// key, named after the variant in snake case, and is implemented for `Data`.
+/// Variants with an accessor that has the same name as a function of `Data`,
+/// such as `get_unchecked` for a variant named `Unchecked`, are rejected, as
+/// are generic enums.
+///
+/// ### Examples
+///
+/// ```ignore
+/// use soroban_sdk::{contractimpl, contractstorage, AccountId, Env};
+///
+/// #[contractstorage]
+/// pub enum DataKey {
+///     #[value(AccountId)]
+///     Admin,
+///     #[value(i64)]
+///     Balance(AccountId),
+/// }
+///
+/// pub struct Contract;
+///
+/// #[contractimpl]
+/// impl Contract {
+///     pub fn balance(env: Env, id: AccountId) -> i64 {
+///         env.data().get_balance(id).unwrap_or(0)
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn contractstorage(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(metadata as AttributeArgs);
+    let args = match ContractTypeArgs::from_list(&args) {
+        Ok(v) => v,
+        Err(e) => return e.write_errors().into(),
+    };
+    let mut input = parse_macro_input!(input as DeriveInput);
+    // If the export argument has a value, do as it instructs regarding
+    // exporting. If it does not have a value, export if the type is pub.
+    let gen_spec = if let Some(export) = args.export {
+        export
+    } else {
+        matches!(input.vis, Visibility::Public(_))
+    };
+    let DeriveInput {
+        vis,
+        ident,
+        generics,
+        data,
+        ..
+    } = &mut input;
+    let derived = match data {
+        Data::Enum(e) => derive_storage(
+            &args.crate_path,
+            vis,
+            ident,
+            generics,
+            e,
+            gen_spec,
+            &args.lib,
+        ),
+        Data::Struct(s) => Error::new(
+            s.struct_token.span(),
+            "structs are unsupported as contract storage keys",
+        )
+        .to_compile_error(),
+        Data::Union(u) => Error::new(
+            u.union_token.span(),
+            "unions are unsupported as contract storage keys",
+        )
+        .to_compile_error(),
+    };
+    let docs = derive_type_docs(&input, gen_spec);
+    quote! {
+        #input
+        #derived
+        #docs
+    }
+    .into()
+}
+
+#[derive(Debug, FromMeta)]
+struct ContractEventArgs {
+    #[darling(default = "default_crate_path")]
+    crate_path: Path,
+    lib: Option<String>,
+    export: Option<bool>,
+    name: Option<String>,
+}
+
+#[proc_macro_attribute]
+pub fn contractevent(metadata: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(metadata as AttributeArgs);
+    let args = match ContractEventArgs::from_list(&args) {
+        Ok(v) => v,
+        Err(e) => return e.write_errors().into(),
+    };
+    let mut input = parse_macro_input!(input as DeriveInput);
+    // If the export argument has a value, do as it instructs regarding
+    // exporting. If it does not have a value, export if the type is pub.
+    let gen_spec = if let Some(export) = args.export {
+        export
+    } else {
+        matches!(input.vis, Visibility::Public(_))
+    };
+    let DeriveInput {
+        ident, attrs, data, ..
+    } = &mut input;
+    let derived = match data {
+        Data::Struct(s) => derive_event(
+            &args.crate_path,
+            ident,
+            attrs,
+            s,
+            &args.name,
+            gen_spec,
+            &args.lib,
+        ),
+        Data::Enum(e) => Error::new(
+            e.enum_token.span(),
+            "enums are unsupported as contract events",
+        )
+        .to_compile_error(),
+        Data::Union(u) => Error::new(
+            u.union_token.span(),
+            "unions are unsupported as contract events",
+        )
+        .to_compile_error(),
+    };
     quote! {
         #input
         #derived
@@ -242,9 +513,11 @@ pub fn contracterror(metadata: TokenStream, input: TokenStream) -> TokenStream {
         )
         .to_compile_error(),
     };
+    let docs = derive_type_docs(&input, gen_spec);
     quote! {
         #input
         #derived
+        #docs
     }
     .into()
 }
@@ -391,12 +664,26 @@ pub fn contractimport(metadata: TokenStream) -> TokenStream {
         }
     };
 
+    // Check the contract was built for the env interface this crate uses.
+    if let Err(e) = check_interface_version(&wasm, meta::INTERFACE_VERSION) {
+        return Error::new(Span::call_site(), e.to_string())
+            .into_compile_error()
+            .into();
+    }
+
     // Generate.
     match generate_from_wasm(&wasm, &args.file, args.sha256.as_deref()) {
         Ok(code) => quote! { #code },
         Err(e @ GenerateFromFileError::VerifySha256 { .. }) => {
             Error::new(args.sha256.span(), e.to_string()).into_compile_error()
         }
+        Err(GenerateFromFileError::Validate(diagnostics)) => {
+            let errors = diagnostics.iter().map(|d| {
+                let msg = format!("invalid contract spec in {}: {d}", args.file);
+                Error::new(Span::call_site(), msg).into_compile_error()
+            });
+            quote! { #(#errors)* }
+        }
         Err(e) => Error::new(Span::call_site(), e.to_string()).into_compile_error(),
     }
     .into()
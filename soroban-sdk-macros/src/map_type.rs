@@ -1,20 +1,37 @@
+use soroban_spec::gen::type_name;
 use stellar_xdr::{
     ScSpecTypeBytesN, ScSpecTypeDef, ScSpecTypeMap, ScSpecTypeOption, ScSpecTypeResult,
     ScSpecTypeSet, ScSpecTypeTuple, ScSpecTypeUdt, ScSpecTypeVec,
 };
 use syn::{
-    spanned::Spanned, Error, Expr, ExprLit, GenericArgument, Lit, Path, PathArguments, PathSegment,
-    Type, TypePath, TypeTuple,
+    spanned::Spanned, Error, Expr, ExprLit, GenericArgument, Ident, Lit, Path, PathArguments,
+    PathSegment, Type, TypePath, TypeTuple,
 };
 
-#[allow(clippy::too_many_lines)]
 pub fn map_type(t: &Type) -> Result<ScSpecTypeDef, Error> {
+    map_type_generic(t, &[])
+}
+
+/// Maps the type to its spec type, where the type may refer to the type
+/// parameters of a generic type. Type parameters are mapped to a user-defined
+/// type named the parameter, e.g. `T`, that is resolved by the generic type.
+///
+/// User-defined types with generic arguments are mapped to the user-defined
+/// type named with the names of the arguments, as they are written in Rust
+/// contracts, e.g. `Page<u32>`.
+#[allow(clippy::too_many_lines)]
+pub fn map_type_generic(t: &Type, params: &[Ident]) -> Result<ScSpecTypeDef, Error> {
+    let map_type = |t: &Type| map_type_generic(t, params);
     match t {
         Type::Path(TypePath {
             qself: None,
             path: Path { segments, .. },
         }) => {
             match segments.last() {
+                Some(PathSegment {
+                    ident,
+                    arguments: PathArguments::None,
+                }) if segments.len() == 1 && params.contains(ident) => udt(t, ident.to_string()),
                 Some(PathSegment {
                     ident,
                     arguments: PathArguments::None,
@@ -32,14 +49,7 @@ pub fn map_type(t: &Type) -> Result<ScSpecTypeDef, Error> {
                     "BigInt" => Ok(ScSpecTypeDef::BigInt),
                     "Address" => Ok(ScSpecTypeDef::Invoker),
                     "AccountId" => Ok(ScSpecTypeDef::AccountId),
-                    s => Ok(ScSpecTypeDef::Udt(ScSpecTypeUdt {
-                        name: s.try_into().map_err(|e| {
-                            Error::new(
-                                t.span(),
-                                format!("Udt name {:?} cannot be used in XDR spec: {}", s, e),
-                            )
-                        })?,
-                    })),
+                    s => udt(t, s.to_string()),
                 },
                 Some(PathSegment {
                     ident,
@@ -119,10 +129,20 @@ pub fn map_type(t: &Type) -> Result<ScSpecTypeDef, Error> {
                         };
                             Ok(ScSpecTypeDef::BytesN(ScSpecTypeBytesN { n }))
                         }
-                        _ => Err(Error::new(
-                            angle_bracketed.span(),
-                            "generics unsupported on user-defined types in contract functions",
-                        ))?,
+                        s => {
+                            let mut arg_names = Vec::<String>::new();
+                            for arg in &args {
+                                if let GenericArgument::Type(t) = arg {
+                                    arg_names.push(type_name(&map_type(t)?));
+                                } else {
+                                    Err(Error::new(
+                                        arg.span(),
+                                        "only type arguments are supported on user-defined types",
+                                    ))?;
+                                }
+                            }
+                            udt(t, format!("{}<{}>", s, arg_names.join(", ")))
+                        }
                     }
                 }
                 _ => Err(Error::new(t.span(), "unsupported type"))?,
@@ -132,7 +152,7 @@ pub fn map_type(t: &Type) -> Result<ScSpecTypeDef, Error> {
             Ok(ScSpecTypeDef::Tuple(Box::new(ScSpecTypeTuple {
                 value_types: elems
                     .iter()
-                    .map(map_type)
+                    .map(&map_type)
                     .collect::<Result<Vec<ScSpecTypeDef>, Error>>()? // TODO: Implement conversion to VecM from iters to omit this collect.
                     .try_into()
                     .map_err(|e| {
@@ -146,3 +166,14 @@ pub fn map_type(t: &Type) -> Result<ScSpecTypeDef, Error> {
         _ => Err(Error::new(t.span(), "unsupported type"))?,
     }
 }
+
+fn udt(t: &Type, name: String) -> Result<ScSpecTypeDef, Error> {
+    Ok(ScSpecTypeDef::Udt(ScSpecTypeUdt {
+        name: name.clone().try_into().map_err(|e| {
+            Error::new(
+                t.span(),
+                format!("Udt name {:?} cannot be used in XDR spec: {}", name, e),
+            )
+        })?,
+    }))
+}
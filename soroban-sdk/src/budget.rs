@@ -0,0 +1,101 @@
+//! Budget contains types for inspecting and limiting the resources consumed by
+//! contracts executing in a test [Env].
+#![cfg(any(test, feature = "testutils"))]
+#![cfg_attr(feature = "docs", doc(cfg(feature = "testutils")))]
+
+use crate::{
+    env::internal::{self, budget::CostType},
+    testutils, Env,
+};
+
+/// Budget tracks the CPU instructions and memory bytes consumed by contracts
+/// executing in the [Env], and limits how much can be consumed.
+///
+/// The methods for inspecting and limiting the budget are on the
+/// [`testutils::Budget`] trait.
+///
+/// ### Examples
+///
+/// ```
+/// use soroban_sdk::{testutils::Budget, Env};
+///
+/// # use soroban_sdk::{contractimpl, BytesN};
+/// #
+/// # pub struct Contract;
+/// #
+/// # #[contractimpl]
+/// # impl Contract {
+/// #     pub fn f(env: Env) {}
+/// # }
+/// #
+/// # fn main() {
+/// #     let env = Env::default();
+/// #     let contract_id = BytesN::from_array(&env, &[0; 32]);
+/// #     env.register_contract(&contract_id, Contract);
+/// #     let client = ContractClient::new(&env, &contract_id);
+/// env.budget().reset_default();
+/// client.f();
+/// let cpu = env.budget().cpu_instruction_cost();
+/// let mem = env.budget().memory_bytes_cost();
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Budget(Env);
+
+impl Budget {
+    #[inline(always)]
+    pub(crate) fn env(&self) -> &Env {
+        &self.0
+    }
+
+    #[inline(always)]
+    pub(crate) fn new(env: &Env) -> Budget {
+        Budget(env.clone())
+    }
+
+    fn with_budget<T>(&self, f: impl FnOnce(internal::budget::Budget) -> T) -> T {
+        self.env().host().with_budget(f)
+    }
+}
+
+impl testutils::Budget for Budget {
+    fn reset_default(&self) {
+        self.with_budget(|b| b.reset_default());
+    }
+
+    fn reset_unlimited(&self) {
+        self.with_budget(|b| b.reset_unlimited());
+    }
+
+    fn reset_limits(&self, cpu: u64, mem: u64) {
+        self.with_budget(|b| b.reset_limits(cpu, mem));
+    }
+
+    fn cpu_instruction_cost(&self) -> u64 {
+        self.with_budget(|b| b.get_cpu_insns_count())
+    }
+
+    fn memory_bytes_cost(&self) -> u64 {
+        self.with_budget(|b| b.get_mem_bytes_count())
+    }
+
+    fn input(&self, cost_type: CostType) -> u64 {
+        self.with_budget(|b| b.get_input(cost_type))
+    }
+
+    fn print(&self) {
+        self.with_budget(|b| println!("{}", b));
+    }
+}
+
+impl core::fmt::Display for Budget {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.with_budget(|b| write!(f, "{}", b))
+    }
+}
+
+impl core::fmt::Debug for Budget {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.with_budget(|b| write!(f, "{:?}", b))
+    }
+}
@@ -3,9 +3,68 @@ use core::fmt::Debug;
 
 use crate::{
     env::internal::{self, RawVal},
-    Env, IntoVal, TryFromVal,
+    ConversionError, Env, IntoVal, TryFromVal, TryIntoVal,
 };
 
+/// Error returned by the checked functions of [Data].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum DataError {
+    /// The key does not have a value stored.
+    NotFound,
+    /// The value stored cannot be converted into the type expected.
+    ConversionError,
+}
+
+/// Migrate upgrades values of the previous version of a versioned contract
+/// type, declared with `#[contracttype(version = N)]`.
+///
+/// Values of versioned types are stored tagged with their version. When a value
+/// tagged with an earlier version is read, such as with [Data::get], it is
+/// converted into the previous version of the type, and then migrated into
+/// the current version. Each version only migrates from the version before it,
+/// and values several versions old are migrated one version at a time, e.g.
+/// `V1 → V2 → V3`. The value stored is not changed until it is set again.
+///
+/// Types with version 1 do not have a previous version and do not implement
+/// Migrate. Types with any later version must implement it. Values that are not
+/// tagged with a version, stored before the type was versioned, are read as
+/// version 1, so version 1 must have the same fields as the type had before.
+///
+/// ### Examples
+///
+/// ```
+/// use soroban_sdk::{contracttype, data::Migrate, Env};
+///
+/// #[contracttype(version = 1, version_of = "Balance")]
+/// pub struct BalanceV1 {
+///     pub amount: i64,
+/// }
+///
+/// #[contracttype(version = 2)]
+/// pub struct Balance {
+///     pub amount: i64,
+///     pub frozen: bool,
+/// }
+///
+/// impl Migrate for Balance {
+///     type Previous = BalanceV1;
+///
+///     fn migrate(_env: &Env, previous: BalanceV1) -> Self {
+///         Balance {
+///             amount: previous.amount,
+///             frozen: false,
+///         }
+///     }
+/// }
+/// ```
+pub trait Migrate: Sized {
+    /// The previous version of the type.
+    type Previous: TryFromVal<Env, RawVal, Error = ConversionError>;
+
+    /// Migrates the value of the previous version of the type.
+    fn migrate(env: &Env, previous: Self::Previous) -> Self;
+}
+
 /// Data stores and retrieves data for the currently executing contract.
 ///
 /// All data stored can only be queried and modified by the contract that stores
@@ -86,9 +145,7 @@ impl Data {
     ///
     /// When the value stored cannot be converted into the type expected.
     ///
-    /// ### TODO
-    ///
-    /// Add safe checked versions of these functions.
+    /// See [Data::try_get] for a version that does not panic.
     #[inline(always)]
     pub fn get<K, V>(&self, key: K) -> Option<Result<V, V::Error>>
     where
@@ -107,12 +164,42 @@ impl Data {
         }
     }
 
+    /// Returns the value if there is a value stored for the given key in the
+    /// currently executing contracts data, or [None] if there is no value
+    /// stored.
+    ///
+    /// ### Errors
+    ///
+    /// [DataError::ConversionError] when the value stored cannot be converted
+    /// into the type expected.
+    #[inline(always)]
+    pub fn try_get<K, V>(&self, key: K) -> Result<Option<V>, DataError>
+    where
+        K: IntoVal<Env, RawVal>,
+        V: TryFromVal<Env, RawVal>,
+    {
+        let env = self.env();
+        let key = key.into_val(env);
+        let has = internal::Env::has_contract_data(env, key);
+        if has.is_true() {
+            let rv = internal::Env::get_contract_data(env, key);
+            match V::try_from_val(env, rv) {
+                Ok(v) => Ok(Some(v)),
+                Err(_) => Err(DataError::ConversionError),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+
     /// Returns the value there is a value stored for the given key in the
     /// currently executing contracts data.
     ///
     /// ### Panics
     ///
     /// When the key does not have a value stored.
+    ///
+    /// See [Data::try_get_unchecked] for a version that does not panic.
     #[inline(always)]
     pub fn get_unchecked<K, V>(&self, key: K) -> Result<V, V::Error>
     where
@@ -125,6 +212,24 @@ impl Data {
         V::try_from_val(env, rv)
     }
 
+    /// Returns the value if there is a value stored for the given key in the
+    /// currently executing contracts data.
+    ///
+    /// ### Errors
+    ///
+    /// [DataError::NotFound] when the key does not have a value stored.
+    ///
+    /// [DataError::ConversionError] when the value stored cannot be converted
+    /// into the type expected.
+    #[inline(always)]
+    pub fn try_get_unchecked<K, V>(&self, key: K) -> Result<V, DataError>
+    where
+        K: IntoVal<Env, RawVal>,
+        V: TryFromVal<Env, RawVal>,
+    {
+        self.try_get(key)?.ok_or(DataError::NotFound)
+    }
+
     /// Sets the value for the given key in the currently executing contracts
     /// data.
     ///
@@ -140,6 +245,34 @@ impl Data {
         internal::Env::put_contract_data(env, key.into_val(env), val.into_val(env));
     }
 
+    /// Sets the value for the given key in the currently executing contracts
+    /// data.
+    ///
+    /// If the key already has a value associated with it, the old value is
+    /// replaced by the new value.
+    ///
+    /// ### Errors
+    ///
+    /// [DataError::ConversionError] when the value cannot be converted into a
+    /// value that can be stored.
+    #[inline(always)]
+    pub fn try_set<K, V>(&self, key: K, val: V) -> Result<(), DataError>
+    where
+        K: IntoVal<Env, RawVal>,
+        V: TryIntoVal<Env, RawVal>,
+    {
+        let env = self.env();
+        let val = val
+            .try_into_val(env)
+            .map_err(|_| DataError::ConversionError)?;
+        internal::Env::put_contract_data(env, key.into_val(env), val);
+        Ok(())
+    }
+
+    /// Removes the value stored for the given key in the currently executing
+    /// contracts data.
+    ///
+    /// If the key does not have a value stored, nothing is removed.
     #[inline(always)]
     pub fn remove<K>(&self, key: K)
     where
@@ -148,4 +281,66 @@ impl Data {
         let env = self.env();
         internal::Env::del_contract_data(env, key.into_val(env));
     }
+
+    /// Removes the value stored for the given key in the currently executing
+    /// contracts data.
+    ///
+    /// ### Errors
+    ///
+    /// [DataError::NotFound] when the key does not have a value stored.
+    #[inline(always)]
+    pub fn try_remove<K>(&self, key: K) -> Result<(), DataError>
+    where
+        K: IntoVal<Env, RawVal>,
+    {
+        let env = self.env();
+        let key = key.into_val(env);
+        let has = internal::Env::has_contract_data(env, key);
+        if has.is_true() {
+            internal::Env::del_contract_data(env, key);
+            Ok(())
+        } else {
+            Err(DataError::NotFound)
+        }
+    }
+}
+
+#[cfg(any(test, feature = "testutils"))]
+use crate::{testutils, xdr, BytesN, Map};
+
+#[cfg(any(test, feature = "testutils"))]
+#[cfg_attr(feature = "docs", doc(cfg(feature = "testutils")))]
+impl testutils::Data for Data {
+    fn all(&self, contract_id: &BytesN<32>) -> Map<RawVal, RawVal> {
+        let env = self.env();
+        let contract_id = xdr::Hash(contract_id.to_array());
+        // The snapshot of the Env has the entries the Env was created from
+        // merged with the entries written since, so entries that have not been
+        // read are included.
+        let entries = env
+            .to_snapshot()
+            .entries()
+            .filter_map(|(_, v)| match v {
+                xdr::LedgerEntry {
+                    data:
+                        xdr::LedgerEntryData::ContractData(xdr::ContractDataEntry {
+                            contract_id: id,
+                            key,
+                            val,
+                        }),
+                    ..
+                } if id == &contract_id
+                    && key != &xdr::ScVal::Static(xdr::ScStatic::LedgerKeyContractCode) =>
+                {
+                    Some((key.clone(), val.clone()))
+                }
+                _ => None,
+            })
+            .collect::<std::vec::Vec<_>>();
+        let mut map = Map::new(env);
+        for (k, v) in entries {
+            map.set(k.try_into_val(env).unwrap(), v.try_into_val(env).unwrap());
+        }
+        map
+    }
 }
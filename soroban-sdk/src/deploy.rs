@@ -42,6 +42,35 @@
 //! ```
 use crate::{env::internal::Env as _, Bytes, BytesN, Env, IntoVal, TryFromVal};
 
+// Discriminants of the HashIdPreimage XDR union that contract IDs are derived
+// from.
+const ENVELOPE_TYPE_CONTRACT_ID_FROM_ED25519: u32 = 8;
+const ENVELOPE_TYPE_CONTRACT_ID_FROM_CONTRACT: u32 = 9;
+
+/// Derives a contract ID the same way the host does when deploying a contract.
+///
+/// The ID is the SHA-256 hash of the XDR encoded HashIdPreimage containing the
+/// network ID, the deployer's identity (a contract ID or an ed25519 public
+/// key), and the salt.
+///
+/// Panics if the salt is not 32 bytes, as the host will not deploy a contract
+/// with any other salt.
+fn derive_contract_id(
+    env: &Env,
+    envelope_type: u32,
+    deployer: &BytesN<32>,
+    salt: &Bytes,
+) -> BytesN<32> {
+    assert_eq!(salt.len(), 32, "salt must be 32 bytes");
+    let network_id = env.compute_hash_sha256(&env.ledger().network_passphrase());
+    let mut preimage = Bytes::new(env);
+    preimage.extend_from_array(&envelope_type.to_be_bytes());
+    preimage.append(&network_id.into());
+    preimage.append(&deployer.into());
+    preimage.append(salt);
+    env.compute_hash_sha256(&preimage)
+}
+
 /// Deployer provides access to deploying contracts.
 pub struct Deployer {
     env: Env,
@@ -111,9 +140,18 @@ pub struct DeployerWithCurrentContract {
 
 impl DeployerWithCurrentContract {
     /// Return the ID of the contract defined by the deployer.
-    #[doc(hidden)]
+    ///
+    /// The ID is the same ID that [deploy][Self::deploy] and
+    /// [deploy_token][Self::deploy_token] will return, and can be computed
+    /// before the contract is deployed.
     pub fn id(&self) -> BytesN<32> {
-        todo!()
+        let env = &self.env;
+        derive_contract_id(
+            env,
+            ENVELOPE_TYPE_CONTRACT_ID_FROM_CONTRACT,
+            &env.current_contract(),
+            &self.salt,
+        )
     }
 
     /// Deploy a contract.
@@ -156,10 +194,17 @@ pub struct DeployerWithOtherContract {
 }
 
 impl DeployerWithOtherContract {
-    #[doc(hidden)]
     /// Return the ID of the contract defined by the deployer.
+    ///
+    /// The ID is the same ID that the other contract would be given if it
+    /// deployed a contract with the same salt.
     pub fn id(&self) -> BytesN<32> {
-        todo!()
+        derive_contract_id(
+            &self.env,
+            ENVELOPE_TYPE_CONTRACT_ID_FROM_CONTRACT,
+            &self.contract_id,
+            &self.salt,
+        )
     }
 }
 
@@ -173,10 +218,18 @@ pub struct DeployerWithEd25519 {
 }
 
 impl DeployerWithEd25519 {
-    #[doc(hidden)]
     /// Return the ID of the contract defined by the deployer.
+    ///
+    /// The ID is the same ID that [deploy][Self::deploy] and
+    /// [deploy_token][Self::deploy_token] will return, and can be computed
+    /// before the contract is deployed.
     pub fn id(&self) -> BytesN<32> {
-        todo!()
+        derive_contract_id(
+            &self.env,
+            ENVELOPE_TYPE_CONTRACT_ID_FROM_ED25519,
+            &self.public_key,
+            &self.salt,
+        )
     }
 
     /// Deploy a contract.
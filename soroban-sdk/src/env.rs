@@ -27,6 +27,7 @@ pub mod internal {
 // the SDK in the crate::testutils mod.
 #[cfg(any(test, feature = "testutils"))]
 pub mod testutils {
+    pub use super::internal::budget::CostType;
     pub use super::internal::LedgerInfo;
 }
 
@@ -274,11 +275,14 @@ impl Env {
 }
 
 #[cfg(any(test, feature = "testutils"))]
-use crate::testutils::{Accounts as _, ContractFunctionSet, Ledger as _};
+use crate::{
+    budget::Budget,
+    testutils::{Accounts as _, ContractFunctionSet, Footprint, Ledger as _, LedgerSnapshot},
+};
 #[cfg(any(test, feature = "testutils"))]
 use rand::RngCore;
 #[cfg(any(test, feature = "testutils"))]
-use std::rc::Rc;
+use std::{path::Path, rc::Rc};
 #[cfg(any(test, feature = "testutils"))]
 #[cfg_attr(feature = "docs", doc(cfg(feature = "testutils")))]
 impl Env {
@@ -286,27 +290,35 @@ impl Env {
         &self.env_impl
     }
 
-    fn default_with_testutils() -> Env {
-        struct EmptySnapshotSource();
-
-        impl internal::storage::SnapshotSource for EmptySnapshotSource {
-            fn get(
-                &self,
-                _key: &xdr::LedgerKey,
-            ) -> Result<xdr::LedgerEntry, soroban_env_host::HostError> {
-                use xdr::{ScHostStorageErrorCode, ScStatus};
-                let status: internal::Status =
-                    ScStatus::HostStorageError(ScHostStorageErrorCode::UnknownError).into();
-                Err(status.into())
-            }
+    /// Get the [Budget] for inspecting and limiting the CPU instructions and
+    /// memory bytes consumed by contracts executing in the [Env].
+    #[inline(always)]
+    pub fn budget(&self) -> Budget {
+        Budget::new(self)
+    }
 
-            fn has(&self, _key: &xdr::LedgerKey) -> Result<bool, soroban_env_host::HostError> {
-                Ok(false)
-            }
-        }
+    fn default_with_testutils() -> Env {
+        Env::from_snapshot(LedgerSnapshot::new())
+    }
 
-        let rf = Rc::new(EmptySnapshotSource());
-        let storage = internal::storage::Storage::with_recording_footprint(rf);
+    /// Create an [Env] that starts from the ledger entries in the snapshot.
+    ///
+    /// Any ledger entry read by the [Env], such as accounts, contract code, or
+    /// contract data, that has not been written in the [Env] is read from the
+    /// snapshot. Entries that do not exist in the snapshot do not exist in the
+    /// [Env].
+    ///
+    /// ### Examples
+    /// ```
+    /// use soroban_sdk::{testutils::LedgerSnapshot, Env};
+    ///
+    /// # fn main() {
+    /// let snapshot = LedgerSnapshot::new();
+    /// let env = Env::from_snapshot(snapshot);
+    /// # }
+    /// ```
+    pub fn from_snapshot(snapshot: LedgerSnapshot) -> Env {
+        let storage = internal::storage::Storage::with_recording_footprint(snapshot.into_source());
         let env_impl = internal::EnvImpl::with_storage_and_budget(
             storage,
             internal::budget::Budget::default(),
@@ -327,6 +339,275 @@ impl Env {
         env
     }
 
+    /// Create an [Env] that starts from the ledger entries in the snapshot
+    /// file.
+    ///
+    /// See [LedgerSnapshot] for the format of the file.
+    ///
+    /// ### Panics
+    ///
+    /// If the file cannot be read or is not a valid snapshot.
+    pub fn from_snapshot_file(path: impl AsRef<Path>) -> Env {
+        Env::from_snapshot(LedgerSnapshot::read_file(path).unwrap())
+    }
+
+    /// Create a snapshot of the ledger entries in the [Env].
+    ///
+    /// The snapshot contains the entries the [Env] was created from and all
+    /// entries written since, such as accounts, contract code, and contract
+    /// data. Entries removed from the [Env] are not in the snapshot. The
+    /// snapshot can be used to create a new [Env] with
+    /// [Env::from_snapshot].
+    ///
+    /// Contracts registered with [Env::register_contract] are not stored as
+    /// ledger entries and must be registered again in the new [Env]. Their
+    /// contract data is included in the snapshot.
+    ///
+    /// ### Examples
+    /// ```
+    /// use soroban_sdk::{testutils::Accounts, Env};
+    ///
+    /// # fn main() {
+    /// let env = Env::default();
+    /// env.accounts().generate_and_create();
+    ///
+    /// let snapshot = env.to_snapshot();
+    /// let env = Env::from_snapshot(snapshot);
+    /// # }
+    /// ```
+    pub fn to_snapshot(&self) -> LedgerSnapshot {
+        let mut snapshot = self
+            .snapshot()
+            .as_deref()
+            .cloned()
+            .unwrap_or_else(LedgerSnapshot::new);
+        self.env_impl
+            .with_mut_storage(|storage| {
+                for (k, v) in storage.map.iter() {
+                    match v {
+                        Some(v) => snapshot.insert(k.clone(), v.clone()),
+                        None => {
+                            snapshot.remove(k);
+                        }
+                    }
+                }
+                Ok(())
+            })
+            .unwrap();
+        snapshot
+    }
+
+    /// Returns the snapshot the storage of the [Env] reads ledger entries from
+    /// that have not been written, if the [Env] was created from a snapshot.
+    ///
+    /// The snapshot is found through the storage, so it is shared by all
+    /// [Env]s with the same host.
+    pub(crate) fn snapshot(&self) -> Option<Rc<LedgerSnapshot>> {
+        self.env_impl
+            .with_mut_storage(|storage| match &storage.mode {
+                internal::storage::FootprintMode::Recording(src) => {
+                    Ok(LedgerSnapshot::from_source(src))
+                }
+                internal::storage::FootprintMode::Enforcing => Ok(None),
+            })
+            .unwrap()
+    }
+
+    /// Create a snapshot of the ledger entries in the [Env] and write it to the
+    /// file, replacing the file if it exists.
+    ///
+    /// See [Env::to_snapshot] for what is included in the snapshot, and
+    /// [LedgerSnapshot] for the format of the file.
+    ///
+    /// ### Panics
+    ///
+    /// If the file cannot be written.
+    pub fn to_snapshot_file(&self, path: impl AsRef<Path>) {
+        self.to_snapshot().write_file(path).unwrap();
+    }
+
+    /// Returns the [Footprint] of ledger keys read and written in the [Env]
+    /// since it was created or the footprint was last reset with
+    /// [Env::reset_footprint].
+    pub fn footprint(&self) -> Footprint {
+        self.env_impl
+            .with_mut_storage(|storage| Ok(Footprint::from(&storage.footprint)))
+            .unwrap()
+    }
+
+    /// Reset the footprint of ledger keys read and written in the [Env].
+    ///
+    /// Call before invoking a contract to limit [Env::footprint] to the ledger
+    /// keys touched by the invocation.
+    pub fn reset_footprint(&self) {
+        self.env_impl
+            .with_mut_storage(|storage| {
+                storage.footprint = internal::storage::Footprint::default();
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    /// Call the function and return its result along with the [Footprint] of
+    /// ledger keys read and written while it was executing.
+    ///
+    /// Ledger keys touched by the function are also retained in the footprint
+    /// returned by [Env::footprint].
+    ///
+    /// ### Examples
+    /// ```
+    /// use soroban_sdk::{contractimpl, symbol, Env};
+    ///
+    /// pub struct Contract;
+    ///
+    /// #[contractimpl]
+    /// impl Contract {
+    ///     pub fn set(env: Env) {
+    ///         env.data().set(symbol!("key"), 1);
+    ///     }
+    /// }
+    ///
+    /// # fn main() {
+    /// let env = Env::default();
+    /// let contract_id = env.register_contract(None, Contract);
+    /// let client = ContractClient::new(&env, &contract_id);
+    ///
+    /// let (_, footprint) = env.with_recorded_footprint(|| client.set());
+    /// assert_eq!(footprint.read_write.len(), 1);
+    /// # }
+    /// ```
+    pub fn with_recorded_footprint<T>(&self, f: impl FnOnce() -> T) -> (T, Footprint) {
+        let outer = self
+            .env_impl
+            .with_mut_storage(|storage| Ok(core::mem::take(&mut storage.footprint)))
+            .unwrap();
+        let t = f();
+        let footprint = self
+            .env_impl
+            .with_mut_storage(|storage| {
+                let inner = core::mem::replace(&mut storage.footprint, outer);
+                for (k, ty) in inner.0.iter() {
+                    storage.footprint.record_access(k, ty.clone());
+                }
+                Ok(Footprint::from(&inner))
+            })
+            .unwrap();
+        (t, footprint)
+    }
+
+    /// Call the function with storage in the [Env] restricted to the ledger
+    /// keys in the [Footprint].
+    ///
+    /// Contract data reads, writes, and existence checks, as well as calls to
+    /// contracts whose code is stored in the ledger, that touch a ledger key
+    /// not in the footprint fail, the same as they would in a transaction
+    /// submitted to the network with the footprint. Writes to a key that is
+    /// only in the read-only keys of the footprint also fail.
+    ///
+    /// Contracts registered with [Env::register_contract] are not stored in
+    /// the ledger and can be called without their code being in the footprint.
+    ///
+    /// ### Examples
+    /// ```
+    /// use soroban_sdk::{contractimpl, symbol, testutils::Footprint, Env};
+    ///
+    /// pub struct Contract;
+    ///
+    /// #[contractimpl]
+    /// impl Contract {
+    ///     pub fn set(env: Env) {
+    ///         env.data().set(symbol!("key"), 1);
+    ///     }
+    /// }
+    ///
+    /// # fn main() {
+    /// let env = Env::default();
+    /// let contract_id = env.register_contract(None, Contract);
+    /// let client = ContractClient::new(&env, &contract_id);
+    ///
+    /// let (_, footprint) = env.with_recorded_footprint(|| client.set());
+    /// env.with_enforced_footprint(&footprint, || client.set());
+    ///
+    /// let result = env.with_enforced_footprint(&Footprint::default(), || client.try_set());
+    /// assert!(result.is_err());
+    /// # }
+    /// ```
+    pub fn with_enforced_footprint<T>(&self, footprint: &Footprint, f: impl FnOnce() -> T) -> T {
+        use internal::storage::{AccessType, FootprintMode};
+
+        // Restores the storage of the Env when dropped, including when the
+        // function panics, so that the Env is usable after a failed test
+        // assertion is caught.
+        struct Restore<'a> {
+            env: &'a Env,
+            mode: Option<FootprintMode>,
+            footprint: Option<internal::storage::Footprint>,
+            loaded: std::vec::Vec<(xdr::LedgerKey, Option<xdr::LedgerEntry>)>,
+        }
+        impl Drop for Restore<'_> {
+            fn drop(&mut self) {
+                self.env
+                    .env_impl
+                    .with_mut_storage(|storage| {
+                        // Entries loaded for the footprint that were not
+                        // written are removed, so that they are read again
+                        // from the snapshot when next touched.
+                        for (k, v) in self.loaded.drain(..) {
+                            if storage.map.get(&k) == Some(&v) {
+                                storage.map.remove(&k);
+                            }
+                        }
+                        storage.mode = self.mode.take().unwrap();
+                        storage.footprint = self.footprint.take().unwrap();
+                        Ok(())
+                    })
+                    .unwrap();
+            }
+        }
+
+        let mut enforced = internal::storage::Footprint::default();
+        for k in footprint.read_only.iter() {
+            enforced.record_access(k, AccessType::ReadOnly);
+        }
+        for k in footprint.read_write.iter() {
+            enforced.record_access(k, AccessType::ReadWrite);
+        }
+        let _restore = self
+            .env_impl
+            .with_mut_storage(|storage| {
+                // Enforcing storage only has the entries loaded into it, so load
+                // the entries in the footprint that have not been read yet from
+                // the snapshot, as the host does before applying a transaction.
+                let mut loaded = std::vec::Vec::new();
+                if let FootprintMode::Recording(src) = &storage.mode {
+                    for k in footprint
+                        .read_only
+                        .iter()
+                        .chain(footprint.read_write.iter())
+                    {
+                        if !storage.map.contains_key(k) {
+                            let v = if src.has(k)? { Some(src.get(k)?) } else { None };
+                            loaded.push((k.clone(), v));
+                        }
+                    }
+                }
+                for (k, v) in loaded.iter() {
+                    storage.map.insert(k.clone(), v.clone());
+                }
+                Ok(Restore {
+                    env: self,
+                    mode: Some(core::mem::replace(
+                        &mut storage.mode,
+                        FootprintMode::Enforcing,
+                    )),
+                    footprint: Some(core::mem::replace(&mut storage.footprint, enforced)),
+                    loaded,
+                })
+            })
+            .unwrap();
+        f()
+    }
+
     /// Sets the source account in the [Env].
     ///
     /// The source account will be accessible via [Env::invoker] when a contract
@@ -431,6 +712,11 @@ impl Env {
     ///
     /// Returns the contract ID of the registered contract.
     ///
+    /// ### Panics
+    ///
+    /// If the contract was built for an env interface version that is not
+    /// compatible with the env interface version of the [Env].
+    ///
     /// ### Examples
     /// ```
     /// use soroban_sdk::{BytesN, Env};
@@ -448,6 +734,15 @@ impl Env {
         contract_id: impl Into<Option<&'a BytesN<32>>>,
         contract_wasm: &[u8],
     ) -> BytesN<32> {
+        match crate::testutils::meta::interface_version(contract_wasm) {
+            Some(contract) if contract != meta::INTERFACE_VERSION => panic!(
+                "registering contract wasm: contract was built for env interface version \
+                {contract}, which is not compatible with env interface version {}, rebuild the \
+                contract with a compatible soroban-sdk",
+                meta::INTERFACE_VERSION
+            ),
+            _ => (),
+        }
         let contract_id = if let Some(contract_id) = contract_id.into() {
             contract_id.clone()
         } else {
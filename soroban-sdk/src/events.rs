@@ -118,12 +118,13 @@ impl Events {
 }
 
 #[cfg(any(test, feature = "testutils"))]
-use crate::{testutils, xdr, TryIntoVal};
+use crate::{testutils, xdr, BytesN, TryFromVal, TryIntoVal};
 
 #[cfg(any(test, feature = "testutils"))]
-#[cfg_attr(feature = "docs", doc(cfg(feature = "testutils")))]
-impl testutils::Events for Events {
-    fn all(&self) -> Vec<(crate::BytesN<32>, Vec<RawVal>, RawVal)> {
+impl Events {
+    /// Returns the events published by contracts, skipping the first `skip`
+    /// events recorded by the host.
+    fn contract_events(&self, skip: usize) -> Vec<(BytesN<32>, Vec<RawVal>, RawVal)> {
         let env = self.env();
         let mut vec = Vec::new(env);
         self.env()
@@ -132,6 +133,7 @@ impl testutils::Events for Events {
             .unwrap()
             .0
             .into_iter()
+            .skip(skip)
             .for_each(|e| {
                 if let internal::events::HostEvent::Contract(xdr::ContractEvent {
                     type_: xdr::ContractEventType::Contract,
@@ -149,4 +151,85 @@ impl testutils::Events for Events {
             });
         vec
     }
+
+    fn filter<F>(
+        &self,
+        events: Vec<(BytesN<32>, Vec<RawVal>, RawVal)>,
+        f: F,
+    ) -> Vec<(BytesN<32>, Vec<RawVal>, RawVal)>
+    where
+        F: Fn(&(BytesN<32>, Vec<RawVal>, RawVal)) -> bool,
+    {
+        let mut vec = Vec::new(self.env());
+        for e in events.iter_unchecked() {
+            if f(&e) {
+                vec.push_back(e);
+            }
+        }
+        vec
+    }
+}
+
+#[cfg(any(test, feature = "testutils"))]
+#[cfg_attr(feature = "docs", doc(cfg(feature = "testutils")))]
+impl testutils::Events for Events {
+    fn all(&self) -> Vec<(BytesN<32>, Vec<RawVal>, RawVal)> {
+        self.contract_events(0)
+    }
+
+    fn checkpoint(&self) -> testutils::EventsCheckpoint {
+        testutils::EventsCheckpoint(self.env().host().get_events().unwrap().0.len())
+    }
+
+    fn since(
+        &self,
+        checkpoint: &testutils::EventsCheckpoint,
+    ) -> Vec<(BytesN<32>, Vec<RawVal>, RawVal)> {
+        self.contract_events(checkpoint.0)
+    }
+
+    fn by_contract(
+        &self,
+        events: Vec<(BytesN<32>, Vec<RawVal>, RawVal)>,
+        contract_id: &BytesN<32>,
+    ) -> Vec<(BytesN<32>, Vec<RawVal>, RawVal)> {
+        self.filter(events, |(id, _, _)| id == contract_id)
+    }
+
+    fn by_topic_prefix<T>(
+        &self,
+        events: Vec<(BytesN<32>, Vec<RawVal>, RawVal)>,
+        prefix: T,
+    ) -> Vec<(BytesN<32>, Vec<RawVal>, RawVal)>
+    where
+        T: IntoVal<Env, Vec<RawVal>>,
+    {
+        let prefix = prefix.into_val(self.env());
+        self.filter(events, |(_, topics, _)| {
+            topics.len() >= prefix.len() && topics.slice(..prefix.len()) == prefix
+        })
+    }
+
+    fn decode<T, D>(
+        &self,
+        events: Vec<(BytesN<32>, Vec<RawVal>, RawVal)>,
+    ) -> std::vec::Vec<(BytesN<32>, T, D)>
+    where
+        T: TryFromVal<Env, RawVal>,
+        T::Error: Debug,
+        D: TryFromVal<Env, RawVal>,
+        D::Error: Debug,
+    {
+        let env = self.env();
+        events
+            .iter_unchecked()
+            .map(|(id, topics, data)| {
+                (
+                    id,
+                    T::try_from_val(env, topics.to_raw()).unwrap(),
+                    D::try_from_val(env, data).unwrap(),
+                )
+            })
+            .collect()
+    }
 }
@@ -86,9 +86,70 @@ pub use bytes_lit::bytes as __bytes_lit_bytes;
 pub use bytes_lit::bytesmin as __bytes_lit_bytesmin;
 
 pub use soroban_sdk_macros::{
-    contractclient, contracterror, contractfile, contractimpl, contractimport, contracttype,
+    contractclient, contracterror, contractfile, contractimpl, contractimport, contractstorage,
+    contracttype,
 };
 
+/// Generates a `publish` function that publishes the struct as a contract
+/// event.
+///
+/// Fields with a `#[topic]` attribute are published as topics of the event,
+/// following the name of the event as the first topic. A maximum of 3 fields
+/// can be topics. All other fields are published as the data of the event, as
+/// a map from field name to value, the same as structs with [`contracttype`].
+///
+/// The name of the event is the struct name in snake case, or the `name`
+/// argument if set. The name must be 10-characters or less in length.
+///
+/// Includes the event in the contract spec so that clients know the shape of
+/// the events the contract publishes. The event is described by a struct named
+/// `event.` followed by the event name, e.g. `event.transfer`, whose fields are
+/// the topics in the order they are published followed by a field named `data`.
+/// The `data` field has the type of a second struct, e.g.
+/// `event.transfer.data`, that describes the fields of the data. Topic fields
+/// cannot be named `data`. The structs are not types of the contract, and
+/// events cannot be passed to or returned from contract functions.
+///
+/// ### Examples
+///
+/// ```
+/// use soroban_sdk::{contractevent, contractimpl, AccountId, Env};
+///
+/// #[contractevent]
+/// pub struct Transfer {
+///     #[topic]
+///     pub from: AccountId,
+///     #[topic]
+///     pub to: AccountId,
+///     pub amount: i64,
+/// }
+///
+/// pub struct Contract;
+///
+/// #[contractimpl]
+/// impl Contract {
+///     pub fn transfer(env: Env, from: AccountId, to: AccountId, amount: i64) {
+///         Transfer { from, to, amount }.publish(&env);
+///     }
+/// }
+///
+/// # #[cfg(feature = "testutils")]
+/// # fn main() {
+/// #     use soroban_sdk::testutils::{Accounts, Events};
+/// let env = Env::default();
+/// let contract_id = env.register_contract(None, Contract);
+/// let client = ContractClient::new(&env, &contract_id);
+///
+/// let from = env.accounts().generate();
+/// let to = env.accounts().generate();
+/// client.transfer(&from, &to, &5);
+/// assert_eq!(env.events().all().len(), 1);
+/// # }
+/// # #[cfg(not(feature = "testutils"))]
+/// # fn main() { }
+/// ```
+pub use soroban_sdk_macros::contractevent;
+
 /// Create a [Symbol] with the given string.
 ///
 /// A symbol's maximum length is 10 characters.
@@ -204,6 +265,7 @@ mod operators;
 
 pub mod accounts;
 mod bigint;
+pub mod budget;
 mod bytes;
 pub mod data;
 pub mod deploy;
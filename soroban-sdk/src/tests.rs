@@ -1,16 +1,33 @@
 #![cfg(test)]
 
+mod budget;
 mod contract_add_i32;
 mod contract_assert;
 mod contract_call_stack;
+mod contract_data_all;
+mod contract_data_try;
+mod contract_deploy;
+mod contract_docs;
+mod contract_events;
+mod contract_footprint;
 mod contract_invoke;
 mod contract_invoker_account;
 mod contract_invoker_client;
+mod contract_meta;
+mod contract_snapshot;
 mod contract_store;
 mod contract_udt_enum;
+mod contract_udt_enum_fields;
+mod contract_udt_generic;
 mod contract_udt_struct;
+mod contract_udt_struct_field_attrs;
+mod contract_udt_struct_transparent;
 mod contract_udt_struct_tuple;
+mod contract_udt_struct_unit;
+mod contract_udt_struct_versioned;
+mod contractevent;
 mod contractfile_with_sha256;
 mod contractimport;
 mod contractimport_with_error;
 mod contractimport_with_sha256;
+mod contractstorage;
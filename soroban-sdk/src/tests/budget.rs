@@ -0,0 +1,44 @@
+use crate as soroban_sdk;
+use soroban_sdk::{testutils::Budget, Env};
+
+mod addcontract {
+    use crate as soroban_sdk;
+    soroban_sdk::contractimport!(
+        file = "../target/wasm32-unknown-unknown/release/test_add_u64.wasm"
+    );
+}
+
+#[test]
+fn test_budget_consumed() {
+    let e = Env::default();
+    let contract_id = e.register_contract_wasm(None, addcontract::WASM);
+    let client = addcontract::Client::new(&e, &contract_id);
+
+    e.budget().reset_default();
+    assert_eq!(e.budget().cpu_instruction_cost(), 0);
+    assert_eq!(e.budget().memory_bytes_cost(), 0);
+
+    assert_eq!(client.add(&10, &12), 22);
+    let cpu = e.budget().cpu_instruction_cost();
+    let mem = e.budget().memory_bytes_cost();
+    assert!(cpu > 0);
+    assert!(mem > 0);
+
+    // Invoking again without resetting accumulates the cost.
+    client.add(&10, &12);
+    assert!(e.budget().cpu_instruction_cost() > cpu);
+    assert!(e.budget().memory_bytes_cost() > mem);
+}
+
+#[test]
+fn test_budget_limits() {
+    let e = Env::default();
+    let contract_id = e.register_contract_wasm(None, addcontract::WASM);
+    let client = addcontract::Client::new(&e, &contract_id);
+
+    e.budget().reset_limits(1, 1);
+    assert!(client.try_add(&10, &12).is_err());
+
+    e.budget().reset_unlimited();
+    assert_eq!(client.add(&10, &12), 22);
+}
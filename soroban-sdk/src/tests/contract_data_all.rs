@@ -0,0 +1,91 @@
+use crate as soroban_sdk;
+use soroban_sdk::{
+    contractimpl, map, symbol, testutils::Data as _, BytesN, Env, IntoVal, Map, RawVal, Symbol,
+};
+
+pub struct Contract;
+
+#[contractimpl]
+impl Contract {
+    pub fn set(env: Env, key: Symbol, value: i32) {
+        env.data().set(key, value)
+    }
+
+    pub fn remove(env: Env, key: Symbol) {
+        env.data().remove(key)
+    }
+}
+
+#[test]
+fn test_all() {
+    let e = Env::default();
+    let contract_id_a = BytesN::from_array(&e, &[0; 32]);
+    e.register_contract(&contract_id_a, Contract);
+    let client_a = ContractClient::new(&e, &contract_id_a);
+    let contract_id_b = BytesN::from_array(&e, &[1; 32]);
+    e.register_contract(&contract_id_b, Contract);
+    let client_b = ContractClient::new(&e, &contract_id_b);
+
+    client_a.set(&symbol!("a"), &1);
+    client_a.set(&symbol!("b"), &2);
+    client_a.set(&symbol!("c"), &3);
+    client_a.remove(&symbol!("b"));
+    client_b.set(&symbol!("d"), &4);
+
+    let expect: Map<RawVal, RawVal> = map![
+        &e,
+        (symbol!("a").into_val(&e), 1.into_val(&e)),
+        (symbol!("c").into_val(&e), 3.into_val(&e)),
+    ];
+    assert_eq!(e.data().all(&contract_id_a), expect);
+
+    let expect: Map<RawVal, RawVal> = map![&e, (symbol!("d").into_val(&e), 4.into_val(&e))];
+    assert_eq!(e.data().all(&contract_id_b), expect);
+
+    let contract_id_c = BytesN::from_array(&e, &[2; 32]);
+    assert_eq!(e.data().all(&contract_id_c), Map::new(&e));
+}
+
+#[test]
+fn test_all_includes_snapshot() {
+    use soroban_sdk::testutils::LedgerSnapshot;
+    use stellar_xdr::{
+        ContractDataEntry, Hash, LedgerEntry, LedgerEntryData, LedgerEntryExt, LedgerKey,
+        LedgerKeyContractData, ScVal,
+    };
+
+    let key = ScVal::Symbol("a".try_into().unwrap());
+    let snapshot = LedgerSnapshot::from_entries([(
+        LedgerKey::ContractData(LedgerKeyContractData {
+            contract_id: Hash([0; 32]),
+            key: key.clone(),
+        }),
+        LedgerEntry {
+            last_modified_ledger_seq: 0,
+            data: LedgerEntryData::ContractData(ContractDataEntry {
+                contract_id: Hash([0; 32]),
+                key,
+                val: ScVal::I32(1),
+            }),
+            ext: LedgerEntryExt::V0,
+        },
+    )]);
+    let e = Env::from_snapshot(snapshot);
+    let contract_id = BytesN::from_array(&e, &[0; 32]);
+    e.register_contract(&contract_id, Contract);
+    let client = ContractClient::new(&e, &contract_id);
+
+    // Entries in the snapshot that have not been read are included.
+    client.set(&symbol!("b"), &2);
+    let expect: Map<RawVal, RawVal> = map![
+        &e,
+        (symbol!("a").into_val(&e), 1.into_val(&e)),
+        (symbol!("b").into_val(&e), 2.into_val(&e)),
+    ];
+    assert_eq!(e.data().all(&contract_id), expect);
+
+    // Entries in the snapshot that have been removed are not.
+    client.remove(&symbol!("a"));
+    let expect: Map<RawVal, RawVal> = map![&e, (symbol!("b").into_val(&e), 2.into_val(&e))];
+    assert_eq!(e.data().all(&contract_id), expect);
+}
@@ -0,0 +1,119 @@
+use crate as soroban_sdk;
+use soroban_sdk::{contracterror, contractimpl, data::DataError, symbol, Env, Symbol};
+use stellar_xdr::ScVal;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    NotFound = 1,
+    Corrupt = 2,
+}
+
+impl From<DataError> for Error {
+    fn from(e: DataError) -> Self {
+        match e {
+            DataError::NotFound => Error::NotFound,
+            DataError::ConversionError => Error::Corrupt,
+        }
+    }
+}
+
+pub struct Contract;
+
+#[contractimpl]
+impl Contract {
+    pub fn set_i32(env: Env, k: Symbol, v: i32) {
+        env.data().set(k, v)
+    }
+
+    pub fn set_sym(env: Env, k: Symbol, v: Symbol) {
+        env.data().set(k, v)
+    }
+
+    pub fn get(env: Env, k: Symbol) -> Result<i32, Error> {
+        Ok(env.data().try_get_unchecked(k)?)
+    }
+
+    pub fn remove(env: Env, k: Symbol) -> Result<(), Error> {
+        Ok(env.data().try_remove(k)?)
+    }
+}
+
+#[test]
+fn test_try_get() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, Contract);
+    let client = ContractClient::new(&e, &contract_id);
+
+    client.set_i32(&symbol!("a"), &1);
+    client.set_sym(&symbol!("b"), &symbol!("b"));
+
+    e.as_contract(&contract_id, || {
+        let data = e.data();
+        assert_eq!(data.try_get::<_, i32>(symbol!("a")), Ok(Some(1)));
+        assert_eq!(data.try_get::<_, i32>(symbol!("c")), Ok(None));
+        assert_eq!(
+            data.try_get::<_, i32>(symbol!("b")),
+            Err(DataError::ConversionError)
+        );
+
+        assert_eq!(data.try_get_unchecked::<_, i32>(symbol!("a")), Ok(1));
+        assert_eq!(
+            data.try_get_unchecked::<_, i32>(symbol!("c")),
+            Err(DataError::NotFound)
+        );
+        assert_eq!(
+            data.try_get_unchecked::<_, i32>(symbol!("b")),
+            Err(DataError::ConversionError)
+        );
+    });
+}
+
+#[test]
+fn test_try_set() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, Contract);
+
+    e.as_contract(&contract_id, || {
+        let data = e.data();
+        assert_eq!(data.try_set(symbol!("a"), ScVal::I32(1)), Ok(()));
+        assert_eq!(data.try_get_unchecked::<_, i32>(symbol!("a")), Ok(1));
+
+        assert_eq!(
+            data.try_set(symbol!("b"), ScVal::U63(-1)),
+            Err(DataError::ConversionError)
+        );
+        assert!(!data.has(symbol!("b")));
+    });
+}
+
+#[test]
+fn test_try_remove() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, Contract);
+    let client = ContractClient::new(&e, &contract_id);
+
+    client.set_i32(&symbol!("a"), &1);
+
+    e.as_contract(&contract_id, || {
+        let data = e.data();
+        assert_eq!(data.try_remove(symbol!("a")), Ok(()));
+        assert_eq!(data.try_remove(symbol!("a")), Err(DataError::NotFound));
+        assert!(!data.has(symbol!("a")));
+    });
+}
+
+#[test]
+fn test_map_to_contract_error() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, Contract);
+    let client = ContractClient::new(&e, &contract_id);
+
+    client.set_i32(&symbol!("a"), &1);
+    client.set_sym(&symbol!("b"), &symbol!("b"));
+
+    assert_eq!(client.try_get(&symbol!("a")), Ok(Ok(1)));
+    assert_eq!(client.try_get(&symbol!("b")), Err(Ok(Error::Corrupt)));
+    assert_eq!(client.try_get(&symbol!("c")), Err(Ok(Error::NotFound)));
+    assert_eq!(client.try_remove(&symbol!("c")), Err(Ok(Error::NotFound)));
+}
@@ -0,0 +1,95 @@
+use crate as soroban_sdk;
+use soroban_sdk::{contractimpl, Bytes, BytesN, Env};
+
+const WASM: &[u8] = include_bytes!("../../doctest_fixtures/contract.wasm");
+
+pub struct Contract;
+
+#[contractimpl]
+impl Contract {
+    pub fn deploy(env: Env, salt: BytesN<32>) -> (BytesN<32>, BytesN<32>) {
+        let deployer = env.deployer().with_current_contract(salt);
+        let id = deployer.id();
+        let wasm = Bytes::from_slice(&env, WASM);
+        (id, deployer.deploy(wasm))
+    }
+
+    pub fn deploy_tkn(env: Env, salt: BytesN<32>) -> (BytesN<32>, BytesN<32>) {
+        let deployer = env.deployer().with_current_contract(salt);
+        (deployer.id(), deployer.deploy_token())
+    }
+}
+
+#[test]
+fn test_id_matches_deployed() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, Contract);
+    let client = ContractClient::new(&e, &contract_id);
+
+    let (id, deployed_id) = client.deploy(&BytesN::from_array(&e, &[1; 32]));
+    assert_eq!(id, deployed_id);
+
+    let (id, deployed_id) = client.deploy_tkn(&BytesN::from_array(&e, &[2; 32]));
+    assert_eq!(id, deployed_id);
+}
+
+#[test]
+fn test_id_matches_other_contract_id() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, Contract);
+    let client = ContractClient::new(&e, &contract_id);
+
+    let salt = BytesN::from_array(&e, &[3; 32]);
+    let (_, deployed_id) = client.deploy_tkn(&salt);
+
+    let id = e.deployer().with_other_contract(&contract_id, &salt).id();
+    assert_eq!(id, deployed_id);
+}
+
+#[test]
+fn test_id_matches_ed25519_deployed() {
+    use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer};
+
+    let e = Env::default();
+    let secret = SecretKey::from_bytes(&[4; 32]).unwrap();
+    let public = PublicKey::from(&secret);
+    let keypair = Keypair { secret, public };
+    let public_key = BytesN::from_array(&e, &keypair.public.to_bytes());
+    let salt = BytesN::from_array(&e, &[5; 32]);
+    let wasm = Bytes::from_slice(&e, WASM);
+
+    // The host verifies a signature over the hash of the deploy parameters.
+    let mut params = Bytes::from_slice(
+        &e,
+        b"create_contract_from_ed25519(contract: Vec<u8>, salt: u256, key: u256, sig: Vec<u8>)",
+    );
+    params.append(&salt.clone().into());
+    params.append(&wasm);
+    let hash = e.compute_hash_sha256(&params);
+    let signature = keypair.sign(&hash.to_array()).to_bytes();
+
+    let deployer = e.deployer().with_ed25519(&public_key, &salt);
+    let id = deployer.id();
+    let deployed_id = deployer.deploy(wasm, &BytesN::from_array(&e, &signature));
+    assert_eq!(id, deployed_id);
+}
+
+#[test]
+fn test_id_matches_ed25519_preimage() {
+    use stellar_xdr::{Hash, HashIdPreimage, HashIdPreimageEd25519ContractId, Uint256, WriteXdr};
+
+    let e = Env::default();
+    let public_key = [4; 32];
+    let salt = [5; 32];
+
+    let id = e.deployer().with_ed25519(&public_key, &salt).id();
+
+    let network_id = e.compute_hash_sha256(&e.ledger().network_passphrase());
+    let preimage = HashIdPreimage::ContractIdFromEd25519(HashIdPreimageEd25519ContractId {
+        network_id: Hash(network_id.to_array()),
+        ed25519: Uint256(public_key),
+        salt: Uint256(salt),
+    });
+    let preimage = Bytes::from_slice(&e, &preimage.to_xdr().unwrap());
+    assert_eq!(id, e.compute_hash_sha256(&preimage));
+}
@@ -0,0 +1,32 @@
+use crate as soroban_sdk;
+use soroban_sdk::{contractimpl, contracttype};
+use soroban_spec::docs::Docs;
+
+/// A transfer of an amount.
+#[contracttype]
+pub struct Transfer {
+    /// The amount transferred.
+    pub amount: i64,
+}
+
+pub struct Contract;
+
+#[contractimpl]
+impl Contract {
+    /// Returns the amount of the transfer.
+    ///
+    /// * `transfer` - The transfer.
+    pub fn amount(transfer: Transfer) -> i64 {
+        transfer.amount
+    }
+}
+
+#[test]
+fn test_docs_of_fn_and_type() {
+    let docs = Docs::from_xdr(&__DOCS_XDR_TYPE_TRANSFER).unwrap();
+    assert_eq!(docs.get("Transfer"), Some("A transfer of an amount."));
+    assert_eq!(docs.get("Transfer.amount"), Some("The amount transferred."));
+
+    let docs = Docs::from_xdr(&__DOCS_XDR_FN_AMOUNT).unwrap();
+    assert_eq!(docs.get("amount.transfer"), Some("The transfer."));
+}
@@ -0,0 +1,133 @@
+use crate as soroban_sdk;
+use soroban_sdk::{
+    assert_events, contractimpl, symbol, testutils::Events, vec, BytesN, Env, IntoVal, Symbol,
+};
+
+pub struct Contract;
+
+#[contractimpl]
+impl Contract {
+    pub fn transfer(env: Env, to: Symbol, amount: i64) {
+        env.events().publish((symbol!("transfer"), to), amount);
+    }
+
+    pub fn mint(env: Env, amount: i64) {
+        env.events().publish((symbol!("mint"),), amount);
+    }
+}
+
+fn setup(e: &Env) -> (BytesN<32>, ContractClient, BytesN<32>, ContractClient) {
+    let contract_id_a = e.register_contract(None, Contract);
+    let contract_id_b = e.register_contract(None, Contract);
+    let client_a = ContractClient::new(e, &contract_id_a);
+    let client_b = ContractClient::new(e, &contract_id_b);
+    (contract_id_a, client_a, contract_id_b, client_b)
+}
+
+#[test]
+fn test_filters() {
+    let e = Env::default();
+    let (contract_id_a, client_a, contract_id_b, client_b) = setup(&e);
+
+    client_a.transfer(&symbol!("alice"), &1);
+    client_b.mint(&2);
+    client_b.transfer(&symbol!("bob"), &3);
+
+    let all = e.events().all();
+    assert_eq!(all.len(), 3);
+    assert_eq!(
+        e.events().by_contract(all.clone(), &contract_id_a),
+        vec![
+            &e,
+            (
+                contract_id_a.clone(),
+                (symbol!("transfer"), symbol!("alice")).into_val(&e),
+                1i64.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(e.events().by_contract(all.clone(), &contract_id_b).len(), 2);
+    assert_eq!(
+        e.events()
+            .by_topic_prefix(all.clone(), (symbol!("transfer"),))
+            .len(),
+        2
+    );
+    assert_eq!(
+        e.events()
+            .by_topic_prefix(all.clone(), (symbol!("transfer"), symbol!("bob")))
+            .len(),
+        1
+    );
+    assert_eq!(e.events().by_topic_prefix(all.clone(), ()).len(), 3);
+    assert_eq!(
+        e.events()
+            .by_topic_prefix(
+                all.clone(),
+                (symbol!("transfer"), symbol!("bob"), symbol!("x"))
+            )
+            .len(),
+        0
+    );
+
+    let decoded = e
+        .events()
+        .decode::<(Symbol, Symbol), i64>(e.events().by_topic_prefix(all, (symbol!("transfer"),)));
+    assert_eq!(
+        decoded,
+        std::vec![
+            (contract_id_a, (symbol!("transfer"), symbol!("alice")), 1),
+            (contract_id_b, (symbol!("transfer"), symbol!("bob")), 3),
+        ]
+    );
+}
+
+#[test]
+fn test_checkpoint() {
+    let e = Env::default();
+    let (contract_id_a, client_a, _, _) = setup(&e);
+
+    client_a.mint(&1);
+    let checkpoint = e.events().checkpoint();
+    assert_eq!(e.events().since(&checkpoint).len(), 0);
+
+    client_a.mint(&2);
+    assert_events!(
+        &e,
+        e.events().since(&checkpoint),
+        [(&contract_id_a, (symbol!("mint"),), 2i64)],
+    );
+
+    // Filters apply to the events since the checkpoint.
+    let since = e.events().since(&checkpoint);
+    assert_eq!(
+        e.events().by_contract(since.clone(), &contract_id_a).len(),
+        1
+    );
+    assert_eq!(
+        e.events().by_topic_prefix(since, (symbol!("mint"),)).len(),
+        1
+    );
+    assert_events!(
+        &e,
+        e.events().all(),
+        [
+            (&contract_id_a, (symbol!("mint"),), 1i64),
+            (&contract_id_a, (symbol!("mint"),), 2i64),
+        ],
+    );
+}
+
+#[test]
+#[should_panic(expected = "events do not match")]
+fn test_assert_events_mismatch() {
+    let e = Env::default();
+    let (contract_id_a, client_a, _, _) = setup(&e);
+
+    client_a.mint(&1);
+    assert_events!(
+        &e,
+        e.events().all(),
+        [(&contract_id_a, (symbol!("mint"),), 2i64)],
+    );
+}
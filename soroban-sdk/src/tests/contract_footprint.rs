@@ -0,0 +1,115 @@
+use crate as soroban_sdk;
+use soroban_sdk::{contractimpl, symbol, testutils::Footprint, xdr::LedgerFootprint, BytesN, Env};
+use stellar_xdr::{Hash, LedgerKey, LedgerKeyContractData, ScVal};
+
+pub struct Contract;
+
+#[contractimpl]
+impl Contract {
+    pub fn get(env: Env) -> Option<i32> {
+        env.data().get(symbol!("a")).map(Result::unwrap)
+    }
+
+    pub fn set(env: Env, v: i32) {
+        env.data().set(symbol!("b"), v)
+    }
+}
+
+fn key(contract_id: [u8; 32], symbol: &str) -> LedgerKey {
+    LedgerKey::ContractData(LedgerKeyContractData {
+        contract_id: Hash(contract_id),
+        key: ScVal::Symbol(symbol.try_into().unwrap()),
+    })
+}
+
+#[test]
+fn test_with_recorded_footprint() {
+    let e = Env::default();
+    let contract_id = BytesN::from_array(&e, &[0; 32]);
+    e.register_contract(&contract_id, Contract);
+    let client = ContractClient::new(&e, &contract_id);
+
+    let (v, footprint) = e.with_recorded_footprint(|| client.get());
+    assert_eq!(v, None);
+    assert!(footprint.read_only.contains(&key([0; 32], "a")));
+    assert!(!footprint.read_only.contains(&key([0; 32], "b")));
+    assert!(footprint.read_write.is_empty());
+
+    let ((), footprint) = e.with_recorded_footprint(|| client.set(&1));
+    assert!(footprint.read_write.contains(&key([0; 32], "b")));
+    assert!(!footprint.read_only.contains(&key([0; 32], "a")));
+
+    // Keys recorded within the closure are retained in the Env footprint.
+    let footprint = e.footprint();
+    assert!(footprint.read_only.contains(&key([0; 32], "a")));
+    assert!(footprint.read_write.contains(&key([0; 32], "b")));
+}
+
+#[test]
+fn test_reset_footprint() {
+    let e = Env::default();
+    let contract_id = BytesN::from_array(&e, &[0; 32]);
+    e.register_contract(&contract_id, Contract);
+    let client = ContractClient::new(&e, &contract_id);
+
+    client.get();
+    assert!(e.footprint().read_only.contains(&key([0; 32], "a")));
+
+    e.reset_footprint();
+    assert!(e.footprint().is_empty());
+
+    client.set(&1);
+    let footprint = e.footprint();
+    assert!(!footprint.read_only.contains(&key([0; 32], "a")));
+    assert!(footprint.read_write.contains(&key([0; 32], "b")));
+
+    let ledger_footprint: LedgerFootprint = footprint.clone().try_into().unwrap();
+    assert_eq!(ledger_footprint.read_only.to_vec(), footprint.read_only);
+    assert_eq!(ledger_footprint.read_write.to_vec(), footprint.read_write);
+}
+
+#[test]
+fn test_with_enforced_footprint() {
+    let e = Env::default();
+    let contract_id = BytesN::from_array(&e, &[0; 32]);
+    e.register_contract(&contract_id, Contract);
+    let client = ContractClient::new(&e, &contract_id);
+
+    let ((), footprint) = e.with_recorded_footprint(|| client.set(&1));
+    e.with_enforced_footprint(&footprint, || client.set(&2));
+
+    // Keys outside the footprint cannot be read.
+    assert!(e
+        .with_enforced_footprint(&footprint, || client.try_get())
+        .is_err());
+
+    // Keys in the read-only footprint cannot be written.
+    let read_only = Footprint {
+        read_only: footprint.read_write.clone(),
+        read_write: vec![],
+    };
+    assert!(e
+        .with_enforced_footprint(&read_only, || client.try_set(&3))
+        .is_err());
+
+    // Storage is back to recording once the closure returns.
+    assert_eq!(client.get(), None);
+    client.set(&3);
+}
+
+#[test]
+fn test_with_enforced_footprint_restores_on_panic() {
+    let e = Env::default();
+    let contract_id = BytesN::from_array(&e, &[0; 32]);
+    e.register_contract(&contract_id, Contract);
+    let client = ContractClient::new(&e, &contract_id);
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        e.with_enforced_footprint(&Footprint::default(), || client.get())
+    }));
+    assert!(result.is_err());
+
+    // Storage is back to recording after the closure panicked.
+    assert_eq!(client.get(), None);
+    assert!(e.footprint().read_only.contains(&key([0; 32], "a")));
+}
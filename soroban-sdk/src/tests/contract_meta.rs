@@ -0,0 +1,25 @@
+use crate as soroban_sdk;
+use soroban_sdk::{env::meta, testutils::meta::interface_version};
+
+mod addcontract {
+    use crate as soroban_sdk;
+    soroban_sdk::contractimport!(
+        file = "../target/wasm32-unknown-unknown/release/test_add_u64.wasm"
+    );
+}
+
+#[test]
+fn test_interface_version() {
+    assert_eq!(
+        interface_version(addcontract::WASM),
+        Some(meta::INTERFACE_VERSION)
+    );
+}
+
+#[test]
+fn test_interface_version_of_unreadable_wasm() {
+    assert_eq!(interface_version(&[]), None);
+    assert_eq!(interface_version(b"\0asm\x01\0\0\0"), None);
+    // A section that claims to be longer than the wasm.
+    assert_eq!(interface_version(b"\0asm\x01\0\0\0\0\x7f\x01a"), None);
+}
@@ -0,0 +1,140 @@
+use crate as soroban_sdk;
+use soroban_sdk::{contractimpl, symbol, testutils::LedgerSnapshot, BytesN, Env};
+use stellar_xdr::{
+    ContractDataEntry, Hash, LedgerEntry, LedgerEntryData, LedgerEntryExt, LedgerKey,
+    LedgerKeyContractData, ScVal,
+};
+
+pub struct Contract;
+
+#[contractimpl]
+impl Contract {
+    pub fn get(env: Env) -> Option<i32> {
+        env.data().get(symbol!("count")).map(Result::unwrap)
+    }
+
+    pub fn set(env: Env, count: i32) {
+        env.data().set(symbol!("count"), count)
+    }
+}
+
+fn snapshot_with_count(contract_id: [u8; 32], count: i32) -> LedgerSnapshot {
+    let key = ScVal::Symbol("count".try_into().unwrap());
+    LedgerSnapshot::from_entries([(
+        LedgerKey::ContractData(LedgerKeyContractData {
+            contract_id: Hash(contract_id),
+            key: key.clone(),
+        }),
+        LedgerEntry {
+            last_modified_ledger_seq: 0,
+            data: LedgerEntryData::ContractData(ContractDataEntry {
+                contract_id: Hash(contract_id),
+                key,
+                val: ScVal::I32(count),
+            }),
+            ext: LedgerEntryExt::V0,
+        },
+    )])
+}
+
+#[test]
+fn test_reads_from_snapshot() {
+    let e = Env::from_snapshot(snapshot_with_count([0; 32], 7));
+    let contract_id = BytesN::from_array(&e, &[0; 32]);
+    e.register_contract(&contract_id, Contract);
+    let client = ContractClient::new(&e, &contract_id);
+
+    assert_eq!(client.get(), Some(7));
+}
+
+#[test]
+fn test_missing_from_snapshot() {
+    let e = Env::from_snapshot(snapshot_with_count([0; 32], 7));
+    let contract_id = BytesN::from_array(&e, &[1; 32]);
+    e.register_contract(&contract_id, Contract);
+    let client = ContractClient::new(&e, &contract_id);
+
+    assert_eq!(client.get(), None);
+}
+
+#[test]
+fn test_snapshot_roundtrip() {
+    let snapshot = snapshot_with_count([0; 32], 7);
+
+    let mut buf = std::vec::Vec::new();
+    snapshot.write(&mut buf).unwrap();
+    let roundtrip = LedgerSnapshot::read(buf.as_slice()).unwrap();
+
+    assert_eq!(
+        roundtrip.entries().collect::<std::vec::Vec<_>>(),
+        snapshot.entries().collect::<std::vec::Vec<_>>(),
+    );
+}
+
+#[test]
+fn test_to_snapshot() {
+    let e = Env::default();
+    let contract_id = BytesN::from_array(&e, &[0; 32]);
+    e.register_contract(&contract_id, Contract);
+    ContractClient::new(&e, &contract_id).set(&7);
+
+    let snapshot = e.to_snapshot();
+    let key = LedgerKey::ContractData(LedgerKeyContractData {
+        contract_id: Hash([0; 32]),
+        key: ScVal::Symbol("count".try_into().unwrap()),
+    });
+    assert!(matches!(
+        snapshot.get(&key).map(|e| &e.data),
+        Some(LedgerEntryData::ContractData(ContractDataEntry {
+            val: ScVal::I32(7),
+            ..
+        }))
+    ));
+
+    let e = Env::from_snapshot(snapshot);
+    let contract_id = BytesN::from_array(&e, &[0; 32]);
+    e.register_contract(&contract_id, Contract);
+    let client = ContractClient::new(&e, &contract_id);
+    assert_eq!(client.get(), Some(7));
+
+    client.set(&8);
+    assert_eq!(client.get(), Some(8));
+
+    let e = Env::from_snapshot(e.to_snapshot());
+    let contract_id = BytesN::from_array(&e, &[0; 32]);
+    e.register_contract(&contract_id, Contract);
+    let client = ContractClient::new(&e, &contract_id);
+    assert_eq!(client.get(), Some(8));
+}
+
+#[test]
+fn test_to_snapshot_keeps_unread_entries() {
+    let snapshot = snapshot_with_count([0; 32], 7);
+    let e = Env::from_snapshot(snapshot.clone());
+    let contract_id = BytesN::from_array(&e, &[1; 32]);
+    e.register_contract(&contract_id, Contract);
+    ContractClient::new(&e, &contract_id).set(&8);
+
+    // The entry in the snapshot was never read, but is still in the snapshot
+    // of the Env alongside the entry written.
+    let key = snapshot.entries().next().unwrap().0;
+    assert!(e.footprint().read_only.iter().all(|k| k != key));
+    let to = e.to_snapshot();
+    assert_eq!(to.get(key), snapshot.get(key));
+    let written = LedgerKey::ContractData(LedgerKeyContractData {
+        contract_id: Hash([1; 32]),
+        key: ScVal::Symbol("count".try_into().unwrap()),
+    });
+    assert!(to.get(&written).is_some());
+}
+
+#[test]
+fn test_to_snapshot_from_env_with_same_host() {
+    let snapshot = snapshot_with_count([0; 32], 7);
+    let e = Env::from_snapshot(snapshot.clone());
+
+    // An Env created from the host of another Env shares its snapshot.
+    let key = snapshot.entries().next().unwrap().0;
+    let e = Env::with_impl(e.host().clone());
+    assert_eq!(e.to_snapshot().get(key), snapshot.get(key));
+}
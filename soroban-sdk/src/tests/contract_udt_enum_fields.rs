@@ -0,0 +1,135 @@
+use crate as soroban_sdk;
+use soroban_sdk::{
+    contractimpl, contracttype, map, symbol, vec, ConversionError, Env, IntoVal, Map, RawVal,
+    Symbol, TryFromVal, TryIntoVal, Vec,
+};
+use stellar_xdr::{
+    ScSpecEntry, ScSpecTypeDef, ScSpecTypeUdt, ScSpecUdtStructFieldV0, ScSpecUdtStructV0,
+    ScSpecUdtUnionCaseV0, ScSpecUdtUnionV0, ScVal,
+};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Listing {
+    Closed,
+    Sale(i64),
+    Auction { reserve: i64, ends: u64 },
+}
+
+pub struct Contract;
+
+#[contractimpl]
+impl Contract {
+    pub fn extend(listing: Listing, by: u64) -> Listing {
+        match listing {
+            Listing::Auction { reserve, ends } => Listing::Auction {
+                reserve,
+                ends: ends + by,
+            },
+            l => l,
+        }
+    }
+}
+
+#[test]
+fn test_functional() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Contract);
+    let client = ContractClient::new(&env, &contract_id);
+
+    assert_eq!(client.extend(&Listing::Closed, &5), Listing::Closed);
+    assert_eq!(client.extend(&Listing::Sale(3), &5), Listing::Sale(3));
+    assert_eq!(
+        client.extend(
+            &Listing::Auction {
+                reserve: 3,
+                ends: 10
+            },
+            &5
+        ),
+        Listing::Auction {
+            reserve: 3,
+            ends: 15
+        },
+    );
+}
+
+#[test]
+fn test_conversion() {
+    let env = Env::default();
+
+    // Variants with named fields are a vec of the variant name followed by a
+    // map of the fields.
+    let auction = Listing::Auction {
+        reserve: 3,
+        ends: 10,
+    };
+    let r: RawVal = auction.clone().into_val(&env);
+    let v: Vec<RawVal> = r.try_into_val(&env).unwrap();
+    assert_eq!(v.len(), 2);
+    let fields: Map<Symbol, RawVal> = v.get_unchecked(1).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(fields.len(), 2);
+    assert_eq!(Listing::try_from_val(&env, r), Ok(auction.clone()));
+
+    // The map of fields must have a value for each field.
+    let r = vec![
+        &env,
+        symbol!("Auction").into_val(&env),
+        map![&env, (symbol!("reserve"), 3i64)].into_val(&env),
+    ]
+    .to_raw();
+    assert_eq!(Listing::try_from_val(&env, r), Err(ConversionError));
+
+    // The XDR representation round trips.
+    let scval: ScVal = (&auction).try_into().unwrap();
+    assert_eq!(Listing::try_from_val(&env, scval), Ok(auction));
+}
+
+#[test]
+fn test_spec() {
+    // Variants with named fields are described as having a struct value, with
+    // the struct following the union in the spec of the enum.
+    let entries = soroban_spec::read::parse_raw(&__SPEC_XDR_LISTING).unwrap();
+    let expect = std::vec![
+        ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 {
+            lib: "".try_into().unwrap(),
+            name: "Listing".try_into().unwrap(),
+            cases: std::vec![
+                ScSpecUdtUnionCaseV0 {
+                    name: "Closed".try_into().unwrap(),
+                    type_: None,
+                },
+                ScSpecUdtUnionCaseV0 {
+                    name: "Sale".try_into().unwrap(),
+                    type_: Some(ScSpecTypeDef::I64),
+                },
+                ScSpecUdtUnionCaseV0 {
+                    name: "Auction".try_into().unwrap(),
+                    type_: Some(ScSpecTypeDef::Udt(ScSpecTypeUdt {
+                        name: "Listing.Auction".try_into().unwrap(),
+                    })),
+                },
+            ]
+            .try_into()
+            .unwrap(),
+        }),
+        ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+            lib: "".try_into().unwrap(),
+            name: "Listing.Auction".try_into().unwrap(),
+            fields: std::vec![
+                ScSpecUdtStructFieldV0 {
+                    name: "reserve".try_into().unwrap(),
+                    type_: ScSpecTypeDef::I64,
+                },
+                ScSpecUdtStructFieldV0 {
+                    name: "ends".try_into().unwrap(),
+                    type_: ScSpecTypeDef::U64,
+                },
+            ]
+            .try_into()
+            .unwrap(),
+        }),
+    ];
+    assert_eq!(entries, expect);
+    assert_eq!(Listing::spec_xdr(), __SPEC_XDR_LISTING);
+}
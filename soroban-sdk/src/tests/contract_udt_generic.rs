@@ -0,0 +1,221 @@
+use crate as soroban_sdk;
+use soroban_sdk::{
+    contractimpl, contracttype, symbol, vec, ConversionError, Env, IntoVal, RawVal, TryFromVal,
+    TryIntoVal, Vec,
+};
+use stellar_xdr::{
+    ReadXdr, ScSpecEntry, ScSpecFunctionInputV0, ScSpecFunctionV0, ScSpecTypeDef, ScSpecTypeTuple,
+    ScSpecTypeUdt, ScSpecTypeVec, ScSpecUdtStructFieldV0, ScSpecUdtStructV0, ScSpecUdtUnionCaseV0,
+    ScSpecUdtUnionV0,
+};
+
+#[contracttype]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next: Option<u32>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Pair<A, B>(pub A, pub B);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Shape<T> {
+    Point,
+    Circle(T),
+    Rect(T, T),
+}
+
+pub struct Contract;
+
+#[contractimpl]
+impl Contract {
+    pub fn swap(pair: Pair<u32, i64>) -> Pair<i64, u32> {
+        Pair(pair.1, pair.0)
+    }
+
+    pub fn scale(shape: Shape<u32>, by: u32) -> Shape<u32> {
+        match shape {
+            Shape::Point => Shape::Point,
+            Shape::Circle(r) => Shape::Circle(r * by),
+            Shape::Rect(w, h) => Shape::Rect(w * by, h * by),
+        }
+    }
+
+    pub fn count(page: Page<i64>) -> u32 {
+        page.items.len()
+    }
+}
+
+#[test]
+fn test_functional() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Contract);
+    let client = ContractClient::new(&env, &contract_id);
+
+    assert_eq!(client.swap(&Pair(1, -2)), Pair(-2, 1));
+    assert_eq!(client.scale(&Shape::Point, &2), Shape::Point);
+    assert_eq!(client.scale(&Shape::Circle(3), &2), Shape::Circle(6));
+    assert_eq!(client.scale(&Shape::Rect(3, 4), &2), Shape::Rect(6, 8));
+
+    let page = Page {
+        items: vec![&env, 1, 2, 3],
+        next: Some(3),
+    };
+    assert_eq!(client.count(&page), 3);
+}
+
+#[test]
+fn test_conversion() {
+    let env = Env::default();
+
+    // Variants with multiple fields are a vec of the variant name followed by
+    // a vec of the fields.
+    let r: RawVal = Shape::Rect(3u32, 4u32).into_val(&env);
+    let v: Vec<RawVal> = r.try_into_val(&env).unwrap();
+    assert_eq!(v.len(), 2);
+    let fields: Vec<u32> = v.get_unchecked(1).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(fields, vec![&env, 3, 4]);
+    assert_eq!(Shape::<u32>::try_from_val(&env, r), Ok(Shape::Rect(3, 4)));
+
+    // The vec of fields must have a value for each field.
+    let r = vec![
+        &env,
+        symbol!("Rect").into_val(&env),
+        vec![&env, 3u32].into_val(&env),
+    ]
+    .to_raw();
+    assert_eq!(Shape::<u32>::try_from_val(&env, r), Err(ConversionError));
+
+    let page = Page {
+        items: vec![&env, 1i64, 2],
+        next: None,
+    };
+    let r: RawVal = page.into_val(&env);
+    let roundtrip = Page::<i64>::try_from_val(&env, r).unwrap();
+    assert_eq!(roundtrip.items, vec![&env, 1, 2]);
+    assert_eq!(roundtrip.next, None);
+}
+
+fn udt(name: &str) -> ScSpecTypeDef {
+    ScSpecTypeDef::Udt(ScSpecTypeUdt {
+        name: name.try_into().unwrap(),
+    })
+}
+
+#[test]
+fn test_spec() {
+    // Generic types are named with their type parameters, which the types of
+    // fields refer to by name.
+    let entry = ScSpecEntry::from_xdr(__SPEC_XDR_PAGE).unwrap();
+    let expect = ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+        lib: "".try_into().unwrap(),
+        name: "Page<T>".try_into().unwrap(),
+        fields: std::vec![
+            ScSpecUdtStructFieldV0 {
+                name: "items".try_into().unwrap(),
+                type_: ScSpecTypeDef::Vec(Box::new(ScSpecTypeVec {
+                    element_type: Box::new(udt("T")),
+                })),
+            },
+            ScSpecUdtStructFieldV0 {
+                name: "next".try_into().unwrap(),
+                type_: ScSpecTypeDef::Option(Box::new(stellar_xdr::ScSpecTypeOption {
+                    value_type: Box::new(ScSpecTypeDef::U32),
+                })),
+            },
+        ]
+        .try_into()
+        .unwrap(),
+    });
+    assert_eq!(entry, expect);
+
+    // Variants with multiple fields are described as having a tuple value.
+    let entry = ScSpecEntry::from_xdr(__SPEC_XDR_SHAPE).unwrap();
+    let expect = ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 {
+        lib: "".try_into().unwrap(),
+        name: "Shape<T>".try_into().unwrap(),
+        cases: std::vec![
+            ScSpecUdtUnionCaseV0 {
+                name: "Point".try_into().unwrap(),
+                type_: None,
+            },
+            ScSpecUdtUnionCaseV0 {
+                name: "Circle".try_into().unwrap(),
+                type_: Some(udt("T")),
+            },
+            ScSpecUdtUnionCaseV0 {
+                name: "Rect".try_into().unwrap(),
+                type_: Some(ScSpecTypeDef::Tuple(Box::new(ScSpecTypeTuple {
+                    value_types: std::vec![udt("T"), udt("T")].try_into().unwrap(),
+                }))),
+            },
+        ]
+        .try_into()
+        .unwrap(),
+    });
+    assert_eq!(entry, expect);
+
+    // Uses of generic types are named with their type arguments.
+    let entry = ScSpecEntry::from_xdr(__SPEC_XDR_SWAP).unwrap();
+    let expect = ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+        name: "swap".try_into().unwrap(),
+        inputs: std::vec![ScSpecFunctionInputV0 {
+            name: "pair".try_into().unwrap(),
+            type_: udt("Pair<u32, i64>"),
+        }]
+        .try_into()
+        .unwrap(),
+        outputs: std::vec![udt("Pair<i64, u32>")].try_into().unwrap(),
+    });
+    assert_eq!(entry, expect);
+}
+
+#[test]
+fn test_spec_instances() {
+    // The instances of generic types used by the contract are described by
+    // the generic types with their type parameters replaced.
+    let entries = soroban_spec::read::parse_raw(
+        &[
+            __SPEC_XDR_PAIR.as_slice(),
+            __SPEC_XDR_SHAPE.as_slice(),
+            __SPEC_XDR_PAGE.as_slice(),
+            __SPEC_XDR_SWAP.as_slice(),
+            __SPEC_XDR_SCALE.as_slice(),
+            __SPEC_XDR_COUNT.as_slice(),
+        ]
+        .concat(),
+    )
+    .unwrap();
+    assert_eq!(soroban_spec::validate::validate(&entries), Ok(()));
+    let instances = soroban_spec::generic::instantiate(&entries);
+    let names = instances
+        .iter()
+        .filter_map(|e| match e {
+            ScSpecEntry::UdtStructV0(s) => Some(s.name.to_string_lossy()),
+            ScSpecEntry::UdtUnionV0(u) => Some(u.name.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<std::vec::Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "Pair<u32, i64>",
+            "Pair<i64, u32>",
+            "Shape<u32>",
+            "Page<i64>"
+        ]
+    );
+
+    let page = instances.iter().find_map(|e| match e {
+        ScSpecEntry::UdtStructV0(s) if s.name.to_string_lossy() == "Page<i64>" => Some(s),
+        _ => None,
+    });
+    assert_eq!(
+        page.unwrap().fields[0].type_,
+        ScSpecTypeDef::Vec(Box::new(ScSpecTypeVec {
+            element_type: Box::new(ScSpecTypeDef::I64),
+        }))
+    );
+}
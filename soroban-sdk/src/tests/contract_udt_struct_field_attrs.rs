@@ -0,0 +1,120 @@
+use crate as soroban_sdk;
+use soroban_sdk::{
+    contractimpl, contracttype, map, symbol, ConversionError, Env, IntoVal, Map, RawVal, Symbol,
+    TryFromVal, TryIntoVal,
+};
+use stellar_xdr::{ReadXdr, ScSpecEntry, ScSpecTypeDef, ScSpecUdtStructFieldV0, ScSpecUdtStructV0};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Udt {
+    pub a: i32,
+    #[contracttype(rename = "bal")]
+    pub balance_of_account: i32,
+    #[contracttype(default)]
+    pub c: i32,
+    #[contracttype(skip)]
+    pub cached: i32,
+}
+
+pub struct Contract;
+
+#[contractimpl]
+impl Contract {
+    pub fn sum(u: Udt) -> i32 {
+        u.a + u.balance_of_account + u.c + u.cached
+    }
+}
+
+#[test]
+fn test_functional() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Contract);
+    let client = ContractClient::new(&env, &contract_id);
+
+    // Skipped fields are not passed to the contract.
+    let u = Udt {
+        a: 1,
+        balance_of_account: 2,
+        c: 3,
+        cached: 4,
+    };
+    assert_eq!(client.sum(&u), 6);
+}
+
+#[test]
+fn test_conversion() {
+    let env = Env::default();
+
+    // Renamed fields use the name given, and skipped fields are left out.
+    let u = Udt {
+        a: 1,
+        balance_of_account: 2,
+        c: 3,
+        cached: 4,
+    };
+    let r: RawVal = u.into_val(&env);
+    let m: Map<Symbol, i32> = r.try_into_val(&env).unwrap();
+    assert_eq!(
+        m,
+        map![
+            &env,
+            (symbol!("a"), 1),
+            (symbol!("bal"), 2),
+            (symbol!("c"), 3)
+        ]
+    );
+
+    // Fields with a default are their default if missing.
+    let r = map![&env, (symbol!("a"), 1), (symbol!("bal"), 2)].to_raw();
+    assert_eq!(
+        Udt::try_from_val(&env, r),
+        Ok(Udt {
+            a: 1,
+            balance_of_account: 2,
+            c: 0,
+            cached: 0,
+        })
+    );
+
+    // Fields without a default are required.
+    let r = map![&env, (symbol!("a"), 1), (symbol!("c"), 3)].to_raw();
+    assert_eq!(Udt::try_from_val(&env, r), Err(ConversionError));
+
+    // Fields that are not fields of the struct are an error, even if the map
+    // is missing fields that have a default.
+    let r = map![
+        &env,
+        (symbol!("a"), 1),
+        (symbol!("bal"), 2),
+        (symbol!("d"), 3)
+    ]
+    .to_raw();
+    assert_eq!(Udt::try_from_val(&env, r), Err(ConversionError));
+}
+
+#[test]
+fn test_spec() {
+    let entry = ScSpecEntry::from_xdr(__SPEC_XDR_UDT).unwrap();
+    let expect = ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+        lib: "".try_into().unwrap(),
+        name: "Udt".try_into().unwrap(),
+        fields: std::vec![
+            ScSpecUdtStructFieldV0 {
+                name: "a".try_into().unwrap(),
+                type_: ScSpecTypeDef::I32,
+            },
+            ScSpecUdtStructFieldV0 {
+                name: "bal".try_into().unwrap(),
+                type_: ScSpecTypeDef::I32,
+            },
+            ScSpecUdtStructFieldV0 {
+                name: "c".try_into().unwrap(),
+                type_: ScSpecTypeDef::I32,
+            },
+        ]
+        .try_into()
+        .unwrap(),
+    });
+    assert_eq!(entry, expect);
+}
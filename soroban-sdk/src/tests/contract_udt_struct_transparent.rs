@@ -0,0 +1,55 @@
+use crate as soroban_sdk;
+use soroban_sdk::{contractimpl, contracttype, Env, IntoVal, RawVal, TryFromVal, TryIntoVal};
+use stellar_xdr::{ReadXdr, ScSpecEntry, ScSpecTypeDef, ScSpecUdtStructFieldV0, ScSpecUdtStructV0};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[contracttype(transparent)]
+pub struct Amount(pub i64);
+
+pub struct Contract;
+
+#[contractimpl]
+impl Contract {
+    pub fn double(a: Amount) -> Amount {
+        Amount(a.0 * 2)
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let env = Env::default();
+
+    // Transparent structs convert the same as their field.
+    let r: RawVal = Amount(5).into_val(&env);
+    let v: i64 = r.try_into_val(&env).unwrap();
+    assert_eq!(v, 5);
+    let r: RawVal = 7i64.into_val(&env);
+    assert_eq!(Amount::try_from_val(&env, r), Ok(Amount(7)));
+}
+
+#[test]
+fn test_functional() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Contract);
+    let client = ContractClient::new(&env, &contract_id);
+
+    assert_eq!(client.double(&Amount(5)), Amount(10));
+}
+
+#[test]
+fn test_spec() {
+    // Transparent structs are described as a struct with a single field that
+    // has no name.
+    let entry = ScSpecEntry::from_xdr(__SPEC_XDR_AMOUNT).unwrap();
+    let expect = ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+        lib: "".try_into().unwrap(),
+        name: "Amount".try_into().unwrap(),
+        fields: std::vec![ScSpecUdtStructFieldV0 {
+            name: "".try_into().unwrap(),
+            type_: ScSpecTypeDef::I64,
+        }]
+        .try_into()
+        .unwrap(),
+    });
+    assert_eq!(entry, expect);
+}
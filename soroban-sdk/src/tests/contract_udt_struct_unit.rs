@@ -0,0 +1,49 @@
+use crate as soroban_sdk;
+use soroban_sdk::{contractimpl, contracttype, ConversionError, Env, IntoVal, RawVal, TryFromVal};
+use stellar_xdr::{ReadXdr, ScSpecEntry, ScSpecUdtStructV0};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Marker;
+
+pub struct Contract;
+
+#[contractimpl]
+impl Contract {
+    pub fn mark(m: Marker) -> Marker {
+        m
+    }
+}
+
+#[test]
+fn test_conversion() {
+    let env = Env::default();
+
+    // Unit structs are void.
+    let r: RawVal = Marker.into_val(&env);
+    assert!(r.is_void());
+    assert_eq!(Marker::try_from_val(&env, r), Ok(Marker));
+    let r: RawVal = 1u32.into_val(&env);
+    assert_eq!(Marker::try_from_val(&env, r), Err(ConversionError));
+}
+
+#[test]
+fn test_functional() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Contract);
+    let client = ContractClient::new(&env, &contract_id);
+
+    assert_eq!(client.mark(&Marker), Marker);
+}
+
+#[test]
+fn test_spec() {
+    // Unit structs are described as a struct with no fields.
+    let entry = ScSpecEntry::from_xdr(__SPEC_XDR_MARKER).unwrap();
+    let expect = ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+        lib: "".try_into().unwrap(),
+        name: "Marker".try_into().unwrap(),
+        fields: std::vec![].try_into().unwrap(),
+    });
+    assert_eq!(entry, expect);
+}
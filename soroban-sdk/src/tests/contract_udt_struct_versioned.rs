@@ -0,0 +1,209 @@
+use crate as soroban_sdk;
+use soroban_sdk::{
+    contractimpl, contracttype, data::Migrate, map, symbol, ConversionError, Env, IntoVal, RawVal,
+    Symbol, TryFromVal, TryIntoVal, Vec,
+};
+use stellar_xdr::{
+    ScSpecEntry, ScSpecTypeDef, ScSpecTypeUdt, ScSpecUdtStructFieldV0, ScSpecUdtStructV0,
+    ScSpecUdtUnionCaseV0, ScSpecUdtUnionV0,
+};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype(version = 1, version_of = "Balance")]
+pub struct BalanceV1 {
+    pub amount: i64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype(version = 2, version_of = "Balance")]
+pub struct BalanceV2 {
+    pub amount: i64,
+    pub frozen: bool,
+}
+
+impl Migrate for BalanceV2 {
+    type Previous = BalanceV1;
+
+    fn migrate(_env: &Env, previous: BalanceV1) -> Self {
+        BalanceV2 {
+            amount: previous.amount,
+            frozen: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype(version = 3)]
+pub struct Balance {
+    pub amount: i64,
+    pub frozen: bool,
+    pub limit: Option<i64>,
+}
+
+impl Migrate for Balance {
+    type Previous = BalanceV2;
+
+    fn migrate(_env: &Env, previous: BalanceV2) -> Self {
+        Balance {
+            amount: previous.amount,
+            frozen: previous.frozen,
+            limit: None,
+        }
+    }
+}
+
+pub struct Contract;
+
+#[contractimpl]
+impl Contract {
+    pub fn get_balance(env: Env) -> Balance {
+        env.data().get(symbol!("balance")).unwrap().unwrap()
+    }
+}
+
+#[test]
+fn test_functional() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Contract);
+    let client = ContractClient::new(&env, &contract_id);
+
+    // Values stored by an earlier version are migrated when read.
+    env.as_contract(&contract_id, || {
+        env.data().set(symbol!("balance"), BalanceV1 { amount: 5 })
+    });
+    assert_eq!(
+        client.get_balance(),
+        Balance {
+            amount: 5,
+            frozen: false,
+            limit: None,
+        }
+    );
+
+    env.as_contract(&contract_id, || {
+        env.data().set(
+            symbol!("balance"),
+            BalanceV2 {
+                amount: 6,
+                frozen: true,
+            },
+        )
+    });
+    assert_eq!(
+        client.get_balance(),
+        Balance {
+            amount: 6,
+            frozen: true,
+            limit: None,
+        }
+    );
+}
+
+#[test]
+fn test_conversion() {
+    let env = Env::default();
+
+    // Versioned structs are a vec of the version followed by a map of the
+    // fields.
+    let b = Balance {
+        amount: 1,
+        frozen: false,
+        limit: Some(2),
+    };
+    let r: RawVal = b.clone().into_val(&env);
+    let v: Vec<RawVal> = r.try_into_val(&env).unwrap();
+    assert_eq!(v.len(), 2);
+    let version: Symbol = v.get_unchecked(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(version, symbol!("V3"));
+    assert_eq!(Balance::try_from_val(&env, r), Ok(b));
+
+    // Values of later versions cannot be converted into earlier versions.
+    let r: RawVal = BalanceV2 {
+        amount: 1,
+        frozen: false,
+    }
+    .into_val(&env);
+    assert_eq!(BalanceV1::try_from_val(&env, r), Err(ConversionError));
+}
+
+#[test]
+fn test_untagged() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Contract);
+    let client = ContractClient::new(&env, &contract_id);
+
+    // Values stored before the struct was versioned are read as version 1.
+    env.as_contract(&contract_id, || {
+        env.data()
+            .set(symbol!("balance"), map![&env, (symbol!("amount"), 7i64)])
+    });
+    assert_eq!(
+        client.get_balance(),
+        Balance {
+            amount: 7,
+            frozen: false,
+            limit: None,
+        }
+    );
+}
+
+#[test]
+fn test_spec() {
+    // Versioned structs are described as a union with a case for each version,
+    // that has the fields of the version as a struct.
+    let entries = soroban_spec::read::parse_raw(&__SPEC_XDR_BALANCE).unwrap();
+    let expect = std::vec![
+        ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 {
+            lib: "".try_into().unwrap(),
+            name: "Balance".try_into().unwrap(),
+            cases: ["V1", "V2", "V3"]
+                .iter()
+                .map(|v| ScSpecUdtUnionCaseV0 {
+                    name: (*v).try_into().unwrap(),
+                    type_: Some(ScSpecTypeDef::Udt(ScSpecTypeUdt {
+                        name: std::format!("Balance.{}", v).try_into().unwrap(),
+                    })),
+                })
+                .collect::<std::vec::Vec<_>>()
+                .try_into()
+                .unwrap(),
+        }),
+        ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+            lib: "".try_into().unwrap(),
+            name: "Balance.V3".try_into().unwrap(),
+            fields: std::vec![
+                ScSpecUdtStructFieldV0 {
+                    name: "amount".try_into().unwrap(),
+                    type_: ScSpecTypeDef::I64,
+                },
+                ScSpecUdtStructFieldV0 {
+                    name: "frozen".try_into().unwrap(),
+                    type_: ScSpecTypeDef::Bool,
+                },
+                ScSpecUdtStructFieldV0 {
+                    name: "limit".try_into().unwrap(),
+                    type_: ScSpecTypeDef::Option(Box::new(stellar_xdr::ScSpecTypeOption {
+                        value_type: Box::new(ScSpecTypeDef::I64),
+                    })),
+                },
+            ]
+            .try_into()
+            .unwrap(),
+        }),
+    ];
+    assert_eq!(entries, expect);
+
+    // Earlier versions are described only by the struct of their fields.
+    let entries = soroban_spec::read::parse_raw(&__SPEC_XDR_BALANCEV1).unwrap();
+    let expect = std::vec![ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+        lib: "".try_into().unwrap(),
+        name: "Balance.V1".try_into().unwrap(),
+        fields: std::vec![ScSpecUdtStructFieldV0 {
+            name: "amount".try_into().unwrap(),
+            type_: ScSpecTypeDef::I64,
+        }]
+        .try_into()
+        .unwrap(),
+    })];
+    assert_eq!(entries, expect);
+}
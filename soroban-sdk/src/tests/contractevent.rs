@@ -0,0 +1,151 @@
+use crate as soroban_sdk;
+use soroban_sdk::{
+    contractevent, contractimpl, map, symbol, testutils::Events, vec, Env, IntoVal, Symbol,
+};
+use soroban_spec::docs::Docs;
+use stellar_xdr::{
+    ScSpecEntry, ScSpecTypeDef, ScSpecTypeUdt, ScSpecUdtStructFieldV0, ScSpecUdtStructV0,
+};
+
+/// A transfer between two accounts.
+#[contractevent]
+pub struct Transfer {
+    /// The account transferred from.
+    #[topic]
+    pub from: Symbol,
+    #[topic]
+    pub to: Symbol,
+    /// The amount transferred.
+    pub amount: i64,
+    pub memo: u32,
+}
+
+#[contractevent(name = "clawed")]
+pub struct ClawbackEvent {
+    pub amount: i64,
+}
+
+pub struct Contract;
+
+#[contractimpl]
+impl Contract {
+    pub fn transfer(env: Env, from: Symbol, to: Symbol, amount: i64) {
+        Transfer {
+            from,
+            to,
+            amount,
+            memo: 7,
+        }
+        .publish(&env);
+    }
+
+    pub fn clawback(env: Env, amount: i64) {
+        ClawbackEvent { amount }.publish(&env);
+    }
+}
+
+#[test]
+fn test_publish() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, Contract);
+    let client = ContractClient::new(&e, &contract_id);
+
+    client.transfer(&symbol!("alice"), &symbol!("bob"), &5);
+    client.clawback(&3);
+
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                contract_id.clone(),
+                (symbol!("transfer"), symbol!("alice"), symbol!("bob")).into_val(&e),
+                map![
+                    &e,
+                    (symbol!("amount"), 5i64.into_val(&e)),
+                    (symbol!("memo"), 7u32.into_val(&e)),
+                ]
+                .into_val(&e),
+            ),
+            (
+                contract_id.clone(),
+                (symbol!("clawed"),).into_val(&e),
+                map![&e, (symbol!("amount"), 3i64.into_val(&e))].into_val(&e),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_spec() {
+    let entries = soroban_spec::read::parse_raw(&__SPEC_XDR_EVENT_TRANSFER).unwrap();
+    let expect = std::vec![
+        ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+            lib: "".try_into().unwrap(),
+            name: "event.transfer".try_into().unwrap(),
+            fields: std::vec![
+                ScSpecUdtStructFieldV0 {
+                    name: "from".try_into().unwrap(),
+                    type_: ScSpecTypeDef::Symbol,
+                },
+                ScSpecUdtStructFieldV0 {
+                    name: "to".try_into().unwrap(),
+                    type_: ScSpecTypeDef::Symbol,
+                },
+                ScSpecUdtStructFieldV0 {
+                    name: "data".try_into().unwrap(),
+                    type_: ScSpecTypeDef::Udt(ScSpecTypeUdt {
+                        name: "event.transfer.data".try_into().unwrap(),
+                    }),
+                },
+            ]
+            .try_into()
+            .unwrap(),
+        }),
+        ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+            lib: "".try_into().unwrap(),
+            name: "event.transfer.data".try_into().unwrap(),
+            fields: std::vec![
+                ScSpecUdtStructFieldV0 {
+                    name: "amount".try_into().unwrap(),
+                    type_: ScSpecTypeDef::I64,
+                },
+                ScSpecUdtStructFieldV0 {
+                    name: "memo".try_into().unwrap(),
+                    type_: ScSpecTypeDef::U32,
+                },
+            ]
+            .try_into()
+            .unwrap(),
+        }),
+    ];
+    assert_eq!(entries, expect);
+
+    let entries = soroban_spec::read::parse_raw(&ClawbackEvent::spec_xdr()).unwrap();
+    let names = entries
+        .iter()
+        .map(|e| match e {
+            ScSpecEntry::UdtStructV0(s) => s.name.to_string().unwrap(),
+            _ => panic!("event described by an entry other than a struct"),
+        })
+        .collect::<std::vec::Vec<_>>();
+    assert_eq!(names, ["event.clawed", "event.clawed.data"]);
+}
+
+#[test]
+fn test_docs() {
+    let docs = Docs::from_xdr(&__DOCS_XDR_EVENT_TRANSFER).unwrap();
+    assert_eq!(
+        docs.get("event.transfer"),
+        Some("A transfer between two accounts.")
+    );
+    assert_eq!(
+        docs.get("event.transfer.from"),
+        Some("The account transferred from.")
+    );
+    assert_eq!(docs.get("event.transfer.to"), None);
+    assert_eq!(
+        docs.get("event.transfer.data.amount"),
+        Some("The amount transferred.")
+    );
+}
@@ -0,0 +1,105 @@
+use crate as soroban_sdk;
+use soroban_sdk::{contractimpl, contractstorage, contracttype, symbol, Env, Symbol};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub limit: u32,
+}
+
+#[contractstorage]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    #[value(Config)]
+    Config,
+    #[value(i64)]
+    Balance(Symbol),
+    #[value(u32)]
+    Nonce { id: Symbol },
+}
+
+pub struct Contract;
+
+#[contractimpl]
+impl Contract {
+    pub fn init(env: Env, limit: u32) {
+        env.data().set_config(Config { limit });
+    }
+
+    pub fn limit(env: Env) -> Option<u32> {
+        env.data().get_config().map(|c| c.limit)
+    }
+
+    pub fn deposit(env: Env, id: Symbol, amount: i64) -> i64 {
+        let balance = env.data().get_balance(id).unwrap_or(0) + amount;
+        env.data().set_balance(id, balance);
+        balance
+    }
+
+    pub fn has(env: Env, id: Symbol) -> bool {
+        env.data().has_balance(id)
+    }
+
+    pub fn close(env: Env, id: Symbol) {
+        env.data().remove_balance(id);
+    }
+
+    pub fn next_nonce(env: Env, id: Symbol) -> u32 {
+        let nonce = env.data().get_nonce(id).unwrap_or(0);
+        env.data().set_nonce(id, nonce + 1);
+        nonce
+    }
+}
+
+#[test]
+fn test_functional() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, Contract);
+    let client = ContractClient::new(&e, &contract_id);
+
+    assert_eq!(client.limit(), None);
+    client.init(&5);
+    assert_eq!(client.limit(), Some(5));
+
+    let a = symbol!("a");
+    let b = symbol!("b");
+    assert!(!client.has(&a));
+    assert_eq!(client.deposit(&a, &10), 10);
+    assert_eq!(client.deposit(&a, &5), 15);
+    assert_eq!(client.deposit(&b, &1), 1);
+    assert!(client.has(&a));
+
+    client.close(&a);
+    assert!(!client.has(&a));
+    assert!(client.has(&b));
+}
+
+#[test]
+fn test_keys_are_stored_as_contract_types() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, Contract);
+    let client = ContractClient::new(&e, &contract_id);
+
+    client.deposit(&symbol!("a"), &10);
+
+    e.as_contract(&contract_id, || {
+        let balance = e.data().get::<_, i64>(DataKey::Balance(symbol!("a")));
+        assert_eq!(balance, Some(Ok(10)));
+    });
+}
+
+#[test]
+fn test_key_with_named_field() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, Contract);
+    let client = ContractClient::new(&e, &contract_id);
+
+    assert_eq!(client.next_nonce(&symbol!("a")), 0);
+    assert_eq!(client.next_nonce(&symbol!("a")), 1);
+    assert_eq!(client.next_nonce(&symbol!("b")), 0);
+
+    e.as_contract(&contract_id, || {
+        let nonce = e.data().get::<_, u32>(DataKey::Nonce { id: symbol!("a") });
+        assert_eq!(nonce, Some(Ok(2)));
+    });
+}
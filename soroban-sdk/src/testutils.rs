@@ -6,9 +6,21 @@
 mod sign;
 pub use sign::ed25519;
 
+mod events;
+#[doc(hidden)]
+pub use events::assert_events_eq;
+
+mod footprint;
+pub use footprint::Footprint;
+
+mod snapshot;
+pub use snapshot::LedgerSnapshot;
+
+pub(crate) mod meta;
+
 pub use crate::env::testutils::*;
 
-use crate::{AccountId, BytesN, Env, RawVal, Symbol, Vec};
+use crate::{AccountId, BytesN, Env, IntoVal, Map, RawVal, Symbol, TryFromVal, Vec};
 
 #[doc(hidden)]
 pub trait ContractFunctionSet {
@@ -26,6 +38,49 @@ pub trait Ledger {
         F: FnMut(&mut LedgerInfo);
 }
 
+/// Test utilities for [`Budget`][crate::budget::Budget].
+pub trait Budget {
+    /// Reset the budget to the default limits, and reset the CPU instructions
+    /// and memory bytes consumed to zero.
+    fn reset_default(&self);
+
+    /// Reset the budget to unlimited, and reset the CPU instructions and
+    /// memory bytes consumed to zero.
+    fn reset_unlimited(&self);
+
+    /// Reset the budget to the CPU instruction and memory byte limits, and
+    /// reset the CPU instructions and memory bytes consumed to zero.
+    ///
+    /// Contract invocations that exceed the limits fail.
+    fn reset_limits(&self, cpu: u64, mem: u64);
+
+    /// Returns the CPU instructions consumed since the budget was last reset.
+    fn cpu_instruction_cost(&self) -> u64;
+
+    /// Returns the memory bytes consumed since the budget was last reset.
+    fn memory_bytes_cost(&self) -> u64;
+
+    /// Returns the input consumed of the cost type since the budget was last
+    /// reset.
+    fn input(&self, cost_type: CostType) -> u64;
+
+    /// Prints the budget, including a breakdown of the inputs consumed per
+    /// cost type, to stdout.
+    fn print(&self);
+}
+
+/// Test utilities for [`Data`][crate::data::Data].
+pub trait Data {
+    /// Returns all keys and values stored in the contract data of the contract
+    /// ID.
+    ///
+    /// Entries in the snapshot the [Env][crate::Env] was created from that
+    /// have not been read are included, the same as in
+    /// [Env::to_snapshot][crate::Env::to_snapshot]. The contract's code, if
+    /// stored in the ledger, is not included.
+    fn all(&self, contract_id: &BytesN<32>) -> Map<RawVal, RawVal>;
+}
+
 /// Test utilities for [`Events`][crate::events::Events].
 pub trait Events {
     /// Returns all events that have been published by contracts.
@@ -35,8 +90,72 @@ pub trait Events {
     /// - Event Topics as a [`Vec<RawVal>`]
     /// - Event Data as a [`RawVal`]
     fn all(&self) -> Vec<(crate::BytesN<32>, Vec<RawVal>, RawVal)>;
+
+    /// Returns a checkpoint marking the events that have been published so
+    /// far.
+    ///
+    /// Use with [`Events::since`] to get only the events published after the
+    /// checkpoint, such as the events of a single step of a test.
+    fn checkpoint(&self) -> EventsCheckpoint;
+
+    /// Returns all events that have been published by contracts since the
+    /// checkpoint.
+    ///
+    /// Returns a [`Vec`] in the same form as [`Events::all`].
+    fn since(&self, checkpoint: &EventsCheckpoint)
+        -> Vec<(crate::BytesN<32>, Vec<RawVal>, RawVal)>;
+
+    /// Returns the events, of those given, that were published by the
+    /// contract.
+    ///
+    /// The events are usually from [`Events::all`] or [`Events::since`].
+    ///
+    /// Returns a [`Vec`] in the same form as [`Events::all`].
+    fn by_contract(
+        &self,
+        events: Vec<(crate::BytesN<32>, Vec<RawVal>, RawVal)>,
+        contract_id: &BytesN<32>,
+    ) -> Vec<(crate::BytesN<32>, Vec<RawVal>, RawVal)>;
+
+    /// Returns the events, of those given, with topics that start with the
+    /// topics in the prefix.
+    ///
+    /// The events are usually from [`Events::all`] or [`Events::since`].
+    ///
+    /// Returns a [`Vec`] in the same form as [`Events::all`].
+    fn by_topic_prefix<T>(
+        &self,
+        events: Vec<(crate::BytesN<32>, Vec<RawVal>, RawVal)>,
+        prefix: T,
+    ) -> Vec<(crate::BytesN<32>, Vec<RawVal>, RawVal)>
+    where
+        T: IntoVal<Env, Vec<RawVal>>;
+
+    /// Decodes the topics and data of the events into the types.
+    ///
+    /// The topics type is usually a tuple with the type of each topic, such as
+    /// `(Symbol, AccountId)`.
+    ///
+    /// ### Panics
+    ///
+    /// If the topics or data of any event cannot be converted into the types.
+    fn decode<T, D>(
+        &self,
+        events: Vec<(crate::BytesN<32>, Vec<RawVal>, RawVal)>,
+    ) -> std::vec::Vec<(crate::BytesN<32>, T, D)>
+    where
+        T: TryFromVal<Env, RawVal>,
+        T::Error: core::fmt::Debug,
+        D: TryFromVal<Env, RawVal>,
+        D::Error: core::fmt::Debug;
 }
 
+/// Marks the events that had been published when it was created.
+///
+/// See [`Events::checkpoint`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct EventsCheckpoint(pub(crate) usize);
+
 /// Test utilities for [`Logger`][crate::logging::Logger].
 pub trait Logger {
     /// Returns all debug events that have been logged.
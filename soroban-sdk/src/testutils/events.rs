@@ -0,0 +1,120 @@
+#![cfg(any(test, feature = "testutils"))]
+
+use core::fmt::Write;
+
+use crate::{xdr, BytesN, Env, RawVal, TryFromVal, Vec};
+
+/// Assert that the events are equal to the expected events.
+///
+/// The first argument is a reference to an [`Env`][crate::Env].
+///
+/// The second argument is the events, such as returned by
+/// [`Events::all`][crate::testutils::Events::all].
+///
+/// The third argument is a list of the expected events, each a three element
+/// tuple of the contract ID, the topics as a tuple, and the data.
+///
+/// When the events are not equal, panics with a diff of the events, with
+/// values rendered as XDR.
+///
+/// ### Examples
+///
+/// ```
+/// use soroban_sdk::{assert_events, contractimpl, symbol, testutils::Events, Env};
+///
+/// pub struct Contract;
+///
+/// #[contractimpl]
+/// impl Contract {
+///     pub fn hello(env: Env) {
+///         env.events().publish((symbol!("greetings"),), symbol!("hello"));
+///     }
+/// }
+///
+/// # fn main() {
+/// let env = Env::default();
+/// let contract_id = env.register_contract(None, Contract);
+/// ContractClient::new(&env, &contract_id).hello();
+///
+/// assert_events!(
+///     &env,
+///     env.events().all(),
+///     [(&contract_id, (symbol!("greetings"),), symbol!("hello"))],
+/// );
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(feature = "docs", doc(cfg(feature = "testutils")))]
+macro_rules! assert_events {
+    ($env:expr, $events:expr, [$(($contract_id:expr, $topics:expr, $data:expr $(,)?)),* $(,)?] $(,)?) => {{
+        let env: &$crate::Env = $env;
+        let expected: $crate::Vec<($crate::BytesN<32>, $crate::Vec<$crate::RawVal>, $crate::RawVal)> =
+            $crate::vec![
+                env,
+                $((
+                    ::core::clone::Clone::clone($contract_id),
+                    $crate::IntoVal::<$crate::Env, $crate::Vec<$crate::RawVal>>::into_val($topics, env),
+                    $crate::IntoVal::<$crate::Env, $crate::RawVal>::into_val($data, env),
+                )),*
+            ];
+        $crate::testutils::assert_events_eq(env, &$events, &expected);
+    }};
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_events_eq(
+    env: &Env,
+    events: &Vec<(BytesN<32>, Vec<RawVal>, RawVal)>,
+    expected: &Vec<(BytesN<32>, Vec<RawVal>, RawVal)>,
+) {
+    if events == expected {
+        return;
+    }
+    let mut diff = String::new();
+    let _ = writeln!(
+        diff,
+        "events do not match (- expected, + actual): expected {} events, got {} events",
+        expected.len(),
+        events.len()
+    );
+    for i in 0..events.len().max(expected.len()) {
+        let left = expected.get(i).map(Result::unwrap);
+        let right = events.get(i).map(Result::unwrap);
+        if i < expected.len() && i < events.len() && expected.slice(i..=i) == events.slice(i..=i) {
+            if let Some(e) = left {
+                let _ = writeln!(diff, "  {}: {}", i, fmt_event(env, &e));
+            }
+        } else {
+            if let Some(e) = left {
+                let _ = writeln!(diff, "- {}: {}", i, fmt_event(env, &e));
+            }
+            if let Some(e) = right {
+                let _ = writeln!(diff, "+ {}: {}", i, fmt_event(env, &e));
+            }
+        }
+    }
+    panic!("{}", diff);
+}
+
+fn fmt_event(env: &Env, (contract_id, topics, data): &(BytesN<32>, Vec<RawVal>, RawVal)) -> String {
+    let contract_id: String = contract_id
+        .to_array()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    let topics: std::vec::Vec<String> = topics.iter_unchecked().map(|t| fmt_val(env, t)).collect();
+    format!(
+        "contract: {}, topics: [{}], data: {}",
+        contract_id,
+        topics.join(", "),
+        fmt_val(env, *data)
+    )
+}
+
+fn fmt_val(env: &Env, v: RawVal) -> String {
+    match xdr::ScVal::try_from_val(env, v) {
+        Ok(v) => format!("{:?}", v),
+        Err(_) => format!("{:?}", v),
+    }
+}
@@ -0,0 +1,54 @@
+#![cfg(any(test, feature = "testutils"))]
+
+use crate::env::{
+    internal::storage::{self, AccessType},
+    xdr,
+};
+
+/// Footprint is the set of ledger keys read and written by contract
+/// invocations in an [`Env`][crate::Env].
+///
+/// Footprints are recorded with
+/// [`Env::with_recorded_footprint`][crate::Env::with_recorded_footprint] and
+/// [`Env::footprint`][crate::Env::footprint].
+///
+/// A footprint can be converted into a [`LedgerFootprint`][xdr::LedgerFootprint]
+/// for building a transaction that invokes the same contract functions.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Footprint {
+    /// Ledger keys that were read and not written, ordered by key.
+    pub read_only: std::vec::Vec<xdr::LedgerKey>,
+    /// Ledger keys that were written, ordered by key.
+    pub read_write: std::vec::Vec<xdr::LedgerKey>,
+}
+
+impl Footprint {
+    /// Returns true if no ledger keys were read or written.
+    pub fn is_empty(&self) -> bool {
+        self.read_only.is_empty() && self.read_write.is_empty()
+    }
+}
+
+impl From<&storage::Footprint> for Footprint {
+    fn from(f: &storage::Footprint) -> Self {
+        let mut footprint = Footprint::default();
+        for (k, ty) in f.0.iter() {
+            match ty {
+                AccessType::ReadOnly => footprint.read_only.push(k.clone()),
+                AccessType::ReadWrite => footprint.read_write.push(k.clone()),
+            }
+        }
+        footprint
+    }
+}
+
+impl TryFrom<Footprint> for xdr::LedgerFootprint {
+    type Error = xdr::Error;
+
+    fn try_from(f: Footprint) -> Result<Self, Self::Error> {
+        Ok(xdr::LedgerFootprint {
+            read_only: f.read_only.try_into()?,
+            read_write: f.read_write.try_into()?,
+        })
+    }
+}
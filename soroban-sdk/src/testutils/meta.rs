@@ -0,0 +1,59 @@
+#![cfg(any(test, feature = "testutils"))]
+
+use std::io::Cursor;
+
+use crate::env::xdr::{ReadXdr, ScEnvMetaEntry};
+
+/// Name of the custom section containing the env meta of a contract.
+const ENV_META_SECTION: &str = "contractenvmetav0";
+
+/// Returns the env interface version the contract was built for, or [None] if
+/// the contract does not declare one, or if the wasm or its env meta cannot be
+/// read. Wasm that cannot be read is left for the host to reject when the
+/// contract is registered.
+pub(crate) fn interface_version(wasm: &[u8]) -> Option<u64> {
+    let meta = custom_section(wasm, ENV_META_SECTION)?;
+    let mut cursor = Cursor::new(meta);
+    match ScEnvMetaEntry::read_xdr_iter(&mut cursor).next()? {
+        Ok(ScEnvMetaEntry::ScEnvMetaKindInterfaceVersion(v)) => Some(v),
+        Err(_) => None,
+    }
+}
+
+/// Returns the contents of the first custom section with the name, or [None]
+/// if there is no such section or the wasm cannot be read.
+///
+/// Only the outer structure of the module is read: the header, followed by
+/// sections that are each an id byte and a LEB128 size. Custom sections have
+/// id 0 and start with their name, a LEB128 length followed by the bytes.
+fn custom_section<'a>(wasm: &'a [u8], name: &str) -> Option<&'a [u8]> {
+    let mut rest = wasm.strip_prefix(b"\0asm")?.get(4..)?;
+    while let Some((&id, after_id)) = rest.split_first() {
+        let (size, after_size) = leb128_u32(after_id)?;
+        let size = usize::try_from(size).ok()?;
+        let section = after_size.get(..size)?;
+        rest = &after_size[size..];
+        if id == 0 {
+            let (name_len, after_name_len) = leb128_u32(section)?;
+            let name_len = usize::try_from(name_len).ok()?;
+            let section_name = after_name_len.get(..name_len)?;
+            if section_name == name.as_bytes() {
+                return Some(&after_name_len[name_len..]);
+            }
+        }
+    }
+    None
+}
+
+/// Reads an unsigned LEB128 encoded u32, returning the value and the bytes that
+/// follow it.
+fn leb128_u32(bytes: &[u8]) -> Option<(u32, &[u8])> {
+    let mut value = 0u32;
+    for (i, b) in bytes.iter().enumerate().take(5) {
+        value |= u32::from(b & 0x7f).checked_shl(7 * i as u32)?;
+        if b & 0x80 == 0 {
+            return Some((value, &bytes[i + 1..]));
+        }
+    }
+    None
+}
@@ -0,0 +1,153 @@
+#![cfg(any(test, feature = "testutils"))]
+
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fs::File,
+    io::{Cursor, Read, Write},
+    path::Path,
+    rc::{Rc, Weak},
+};
+
+use crate::env::{
+    internal::{self, storage::SnapshotSource},
+    xdr::{self, ReadXdr, ScHostStorageErrorCode, ScStatus, WriteXdr},
+};
+
+/// Ledger snapshot is a set of ledger entries that an [`Env`][crate::Env] can
+/// be created from, using [`Env::from_snapshot`][crate::Env::from_snapshot].
+///
+/// Ledger entries can be accounts, contract code, contract data, or any other
+/// entry the host is able to read.
+///
+/// Snapshots can be read from and written to files. The file format is a
+/// sequence of XDR encoded [`LedgerKey`][xdr::LedgerKey] and
+/// [`LedgerEntry`][xdr::LedgerEntry] pairs.
+#[derive(Clone, Debug, Default)]
+pub struct LedgerSnapshot {
+    entries: BTreeMap<xdr::LedgerKey, xdr::LedgerEntry>,
+}
+
+impl LedgerSnapshot {
+    /// Create an empty snapshot.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a snapshot containing the ledger entries.
+    pub fn from_entries(
+        entries: impl IntoIterator<Item = (xdr::LedgerKey, xdr::LedgerEntry)>,
+    ) -> Self {
+        Self {
+            entries: entries.into_iter().collect(),
+        }
+    }
+
+    /// Insert a ledger entry, replacing any entry already stored with the key.
+    pub fn insert(&mut self, key: xdr::LedgerKey, entry: xdr::LedgerEntry) {
+        self.entries.insert(key, entry);
+    }
+
+    /// Remove the ledger entry stored with the key.
+    pub fn remove(&mut self, key: &xdr::LedgerKey) -> Option<xdr::LedgerEntry> {
+        self.entries.remove(key)
+    }
+
+    /// Returns the ledger entry stored with the key.
+    pub fn get(&self, key: &xdr::LedgerKey) -> Option<&xdr::LedgerEntry> {
+        self.entries.get(key)
+    }
+
+    /// Returns an iterator over all ledger entries in the snapshot, ordered by
+    /// key.
+    pub fn entries(&self) -> impl Iterator<Item = (&xdr::LedgerKey, &xdr::LedgerEntry)> {
+        self.entries.iter()
+    }
+
+    /// Read a snapshot from a reader.
+    pub fn read(mut r: impl Read) -> Result<LedgerSnapshot, xdr::Error> {
+        let mut buf = std::vec::Vec::new();
+        r.read_to_end(&mut buf)?;
+        let mut cursor = Cursor::new(buf.as_slice());
+        let mut snapshot = LedgerSnapshot::new();
+        while (cursor.position() as usize) < buf.len() {
+            let key = xdr::LedgerKey::read_xdr(&mut cursor)?;
+            let entry = xdr::LedgerEntry::read_xdr(&mut cursor)?;
+            snapshot.insert(key, entry);
+        }
+        Ok(snapshot)
+    }
+
+    /// Read a snapshot from a file.
+    pub fn read_file(path: impl AsRef<Path>) -> Result<LedgerSnapshot, xdr::Error> {
+        Self::read(File::open(path)?)
+    }
+
+    /// Write the snapshot to a writer.
+    pub fn write(&self, mut w: impl Write) -> Result<(), xdr::Error> {
+        for (key, entry) in self.entries.iter() {
+            key.write_xdr(&mut w)?;
+            entry.write_xdr(&mut w)?;
+        }
+        Ok(())
+    }
+
+    /// Write the snapshot to a file, replacing the file if it exists.
+    pub fn write_file(&self, path: impl AsRef<Path>) -> Result<(), xdr::Error> {
+        self.write(File::create(path)?)
+    }
+}
+
+thread_local! {
+    // Snapshots in use as the snapshot source of the storage of an Env. The
+    // storage only holds the source as a SnapshotSource trait object, so the
+    // snapshots are kept here to be able to get back to the entries of the
+    // snapshot from the storage.
+    static SOURCES: RefCell<std::vec::Vec<Weak<LedgerSnapshot>>> =
+        const { RefCell::new(std::vec::Vec::new()) };
+}
+
+impl LedgerSnapshot {
+    /// Returns the snapshot as a snapshot source for the storage of an Env,
+    /// that can be found again from the storage with
+    /// [LedgerSnapshot::from_source].
+    pub(crate) fn into_source(self) -> Rc<LedgerSnapshot> {
+        let source = Rc::new(self);
+        SOURCES.with(|sources| {
+            let mut sources = sources.borrow_mut();
+            sources.retain(|s| s.strong_count() > 0);
+            sources.push(Rc::downgrade(&source));
+        });
+        source
+    }
+
+    /// Returns the snapshot that is the snapshot source, if the source was
+    /// created with [LedgerSnapshot::into_source].
+    pub(crate) fn from_source(source: &Rc<dyn SnapshotSource>) -> Option<Rc<LedgerSnapshot>> {
+        let ptr = Rc::as_ptr(source) as *const u8;
+        SOURCES.with(|sources| {
+            sources
+                .borrow()
+                .iter()
+                .filter_map(Weak::upgrade)
+                .find(|s| Rc::as_ptr(s) as *const u8 == ptr)
+        })
+    }
+}
+
+impl SnapshotSource for LedgerSnapshot {
+    fn get(&self, key: &xdr::LedgerKey) -> Result<xdr::LedgerEntry, soroban_env_host::HostError> {
+        match self.entries.get(key) {
+            Some(entry) => Ok(entry.clone()),
+            None => {
+                let status: internal::Status =
+                    ScStatus::HostStorageError(ScHostStorageErrorCode::MissingKeyInGet).into();
+                Err(status.into())
+            }
+        }
+    }
+
+    fn has(&self, key: &xdr::LedgerKey) -> Result<bool, soroban_env_host::HostError> {
+        Ok(self.entries.contains_key(key))
+    }
+}
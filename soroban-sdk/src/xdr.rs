@@ -38,4 +38,7 @@ pub use super::env::xdr::{
 };
 
 // XDR for ledger entries.
-pub use super::env::xdr::{AccountEntry, AccountId};
+pub use super::env::xdr::{
+    AccountEntry, AccountId, ContractDataEntry, Hash, LedgerEntry, LedgerEntryData, LedgerEntryExt,
+    LedgerFootprint, LedgerKey, LedgerKeyAccount, LedgerKeyContractData,
+};
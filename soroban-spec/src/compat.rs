@@ -0,0 +1,553 @@
+//! Compare the specs of two versions of a contract, to find the differences
+//! that break callers of the old version when the contract is upgraded.
+
+use serde::Serialize;
+use stellar_xdr::{
+    ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef, ScSpecUdtEnumV0, ScSpecUdtErrorEnumV0,
+    ScSpecUdtStructV0, ScSpecUdtUnionV0,
+};
+
+use crate::{
+    gen::type_name,
+    read::{from_wasm, FromWasmError},
+};
+
+/// Whether callers of the old version of a contract continue to work with the
+/// new version.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Compatibility {
+    Compatible,
+    Breaking,
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Change {
+    Added,
+    Removed,
+    Modified,
+    Reordered,
+}
+
+/// A difference between the specs of two versions of a contract.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Difference {
+    /// Path of the function, type, input, field, or case that is different,
+    /// e.g. `add`, `add.a`, `UdtStruct.c`.
+    pub path: String,
+    pub change: Change,
+    pub compatibility: Compatibility,
+    pub description: String,
+}
+
+/// Report of the differences between the specs of two versions of a contract.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    pub differences: Vec<Difference>,
+}
+
+impl Report {
+    /// Returns true if none of the differences are breaking.
+    pub fn is_compatible(&self) -> bool {
+        self.compatibility() == Compatibility::Compatible
+    }
+
+    /// Returns the compatibility of the new version, which is breaking if any
+    /// of the differences are breaking.
+    pub fn compatibility(&self) -> Compatibility {
+        self.differences
+            .iter()
+            .map(|d| d.compatibility)
+            .max()
+            .unwrap_or(Compatibility::Compatible)
+    }
+
+    /// Returns the differences that are breaking.
+    pub fn breaking(&self) -> impl Iterator<Item = &Difference> {
+        self.differences
+            .iter()
+            .filter(|d| d.compatibility == Compatibility::Breaking)
+    }
+
+    /// Returns the report as JSON.
+    pub fn to_json(&self) -> String {
+        #[derive(Serialize)]
+        struct Json<'a> {
+            compatibility: Compatibility,
+            differences: &'a [Difference],
+        }
+        serde_json::to_string_pretty(&Json {
+            compatibility: self.compatibility(),
+            differences: &self.differences,
+        })
+        .expect("serialization of the report should not have any failure cases as all keys are strings and the serialize implementations are derived")
+    }
+
+    fn push(
+        &mut self,
+        path: impl Into<String>,
+        change: Change,
+        compatibility: Compatibility,
+        description: impl Into<String>,
+    ) {
+        self.differences.push(Difference {
+            path: path.into(),
+            change,
+            compatibility,
+            description: description.into(),
+        });
+    }
+}
+
+pub fn compare_wasm(old: &[u8], new: &[u8]) -> Result<Report, FromWasmError> {
+    let old = from_wasm(old)?;
+    let new = from_wasm(new)?;
+    Ok(compare(&old, &new))
+}
+
+/// Compares the spec of the old version of a contract with the spec of the new
+/// version, and classifies each difference as compatible or breaking for
+/// callers of the old version.
+pub fn compare(old: &[ScSpecEntry], new: &[ScSpecEntry]) -> Report {
+    let mut report = Report::default();
+    for o in old {
+        let name = entry_name(o);
+        match new.iter().find(|n| entry_name(n) == name) {
+            Some(n) => match (o, n) {
+                (ScSpecEntry::FunctionV0(o), ScSpecEntry::FunctionV0(n)) => {
+                    compare_function(&mut report, o, n);
+                }
+                (ScSpecEntry::UdtStructV0(o), ScSpecEntry::UdtStructV0(n)) => {
+                    compare_struct(&mut report, o, n);
+                }
+                (ScSpecEntry::UdtUnionV0(o), ScSpecEntry::UdtUnionV0(n)) => {
+                    compare_union(&mut report, o, n);
+                }
+                (ScSpecEntry::UdtEnumV0(o), ScSpecEntry::UdtEnumV0(n)) => {
+                    compare_enum(&mut report, o, n);
+                }
+                (ScSpecEntry::UdtErrorEnumV0(o), ScSpecEntry::UdtErrorEnumV0(n)) => {
+                    compare_error_enum(&mut report, o, n);
+                }
+                _ => report.push(
+                    &name,
+                    Change::Modified,
+                    Compatibility::Breaking,
+                    format!("{} changed to {}", entry_kind(o), entry_kind(n)),
+                ),
+            },
+            None => report.push(
+                &name,
+                Change::Removed,
+                Compatibility::Breaking,
+                format!("{} removed", entry_kind(o)),
+            ),
+        }
+    }
+    for n in new {
+        let name = entry_name(n);
+        if !old.iter().any(|o| entry_name(o) == name) {
+            report.push(
+                &name,
+                Change::Added,
+                Compatibility::Compatible,
+                format!("{} added", entry_kind(n)),
+            );
+        }
+    }
+    report
+}
+
+fn entry_name(entry: &ScSpecEntry) -> String {
+    match entry {
+        ScSpecEntry::FunctionV0(f) => f.name.to_string_lossy(),
+        ScSpecEntry::UdtStructV0(s) => s.name.to_string_lossy(),
+        ScSpecEntry::UdtUnionV0(u) => u.name.to_string_lossy(),
+        ScSpecEntry::UdtEnumV0(e) => e.name.to_string_lossy(),
+        ScSpecEntry::UdtErrorEnumV0(e) => e.name.to_string_lossy(),
+    }
+}
+
+fn entry_kind(entry: &ScSpecEntry) -> &'static str {
+    match entry {
+        ScSpecEntry::FunctionV0(_) => "function",
+        ScSpecEntry::UdtStructV0(_) => "struct",
+        ScSpecEntry::UdtUnionV0(_) => "union",
+        ScSpecEntry::UdtEnumV0(_) => "enum",
+        ScSpecEntry::UdtErrorEnumV0(_) => "error enum",
+    }
+}
+
+fn compare_function(report: &mut Report, old: &ScSpecFunctionV0, new: &ScSpecFunctionV0) {
+    let name = old.name.to_string_lossy();
+    // Inputs are passed by position, so inputs are compared by position and
+    // the names of inputs do not affect callers.
+    for (i, o) in old.inputs.iter().enumerate() {
+        let path = format!("{name}.{}", o.name.to_string_lossy());
+        match new.inputs.get(i) {
+            Some(n) if n.type_ != o.type_ => report.push(
+                path,
+                Change::Modified,
+                Compatibility::Breaking,
+                format!(
+                    "input type changed from {} to {}",
+                    type_name(&o.type_),
+                    type_name(&n.type_)
+                ),
+            ),
+            Some(n) if n.name != o.name => report.push(
+                path,
+                Change::Modified,
+                Compatibility::Compatible,
+                format!("input renamed to {}", n.name.to_string_lossy()),
+            ),
+            Some(_) => {}
+            None => report.push(
+                path,
+                Change::Removed,
+                Compatibility::Breaking,
+                "input removed",
+            ),
+        }
+    }
+    for n in new.inputs.iter().skip(old.inputs.len()) {
+        report.push(
+            format!("{name}.{}", n.name.to_string_lossy()),
+            Change::Added,
+            Compatibility::Breaking,
+            "input added",
+        );
+    }
+    if old.outputs != new.outputs {
+        let outputs = |f: &ScSpecFunctionV0| match f.outputs.first() {
+            Some(t) => type_name(t),
+            None => "()".to_string(),
+        };
+        report.push(
+            name,
+            Change::Modified,
+            Compatibility::Breaking,
+            format!(
+                "output type changed from {} to {}",
+                outputs(old),
+                outputs(new)
+            ),
+        );
+    }
+}
+
+fn compare_struct(report: &mut Report, old: &ScSpecUdtStructV0, new: &ScSpecUdtStructV0) {
+    let name = old.name.to_string_lossy();
+    // Structs with named fields are stored as maps keyed by field name, so the
+    // order of the fields does not affect callers. Structs with unnamed fields
+    // are stored as vecs and their field names are their positions, so
+    // reordering their fields appears as changes to the types of the fields.
+    // Values must have all fields, so adding or removing fields is breaking.
+    for (i, o) in old.fields.iter().enumerate() {
+        let path = format!("{name}.{}", o.name.to_string_lossy());
+        match new.fields.iter().position(|n| n.name == o.name) {
+            Some(j) if new.fields[j].type_ != o.type_ => report.push(
+                path,
+                Change::Modified,
+                Compatibility::Breaking,
+                format!(
+                    "field type changed from {} to {}",
+                    type_name(&o.type_),
+                    type_name(&new.fields[j].type_)
+                ),
+            ),
+            Some(j) if j != i => report.push(
+                path,
+                Change::Reordered,
+                Compatibility::Compatible,
+                format!("field moved from position {i} to {j}"),
+            ),
+            Some(_) => {}
+            None => report.push(
+                path,
+                Change::Removed,
+                Compatibility::Breaking,
+                "field removed",
+            ),
+        }
+    }
+    for n in new.fields.iter() {
+        if !old.fields.iter().any(|o| o.name == n.name) {
+            report.push(
+                format!("{name}.{}", n.name.to_string_lossy()),
+                Change::Added,
+                Compatibility::Breaking,
+                "field added",
+            );
+        }
+    }
+}
+
+fn compare_union(report: &mut Report, old: &ScSpecUdtUnionV0, new: &ScSpecUdtUnionV0) {
+    let name = old.name.to_string_lossy();
+    // Unions are stored with the name of the case, so the order of the cases
+    // does not affect callers.
+    for o in old.cases.iter() {
+        let path = format!("{name}.{}", o.name.to_string_lossy());
+        match new.cases.iter().find(|n| n.name == o.name) {
+            Some(n) if n.type_ != o.type_ => {
+                let case_type =
+                    |t: &Option<ScSpecTypeDef>| t.as_ref().map_or("()".to_string(), type_name);
+                report.push(
+                    path,
+                    Change::Modified,
+                    Compatibility::Breaking,
+                    format!(
+                        "case type changed from {} to {}",
+                        case_type(&o.type_),
+                        case_type(&n.type_)
+                    ),
+                );
+            }
+            Some(_) => {}
+            None => report.push(
+                path,
+                Change::Removed,
+                Compatibility::Breaking,
+                "case removed",
+            ),
+        }
+    }
+    for n in new.cases.iter() {
+        if !old.cases.iter().any(|o| o.name == n.name) {
+            report.push(
+                format!("{name}.{}", n.name.to_string_lossy()),
+                Change::Added,
+                Compatibility::Compatible,
+                "case added",
+            );
+        }
+    }
+}
+
+fn compare_enum(report: &mut Report, old: &ScSpecUdtEnumV0, new: &ScSpecUdtEnumV0) {
+    let name = old.name.to_string_lossy();
+    let old_cases = old
+        .cases
+        .iter()
+        .map(|c| (c.name.to_string_lossy(), c.value));
+    let new_cases = new
+        .cases
+        .iter()
+        .map(|c| (c.name.to_string_lossy(), c.value));
+    compare_cases(report, &name, "case", old_cases, new_cases);
+}
+
+fn compare_error_enum(report: &mut Report, old: &ScSpecUdtErrorEnumV0, new: &ScSpecUdtErrorEnumV0) {
+    let name = old.name.to_string_lossy();
+    let old_cases = old
+        .cases
+        .iter()
+        .map(|c| (c.name.to_string_lossy(), c.value));
+    let new_cases = new
+        .cases
+        .iter()
+        .map(|c| (c.name.to_string_lossy(), c.value));
+    compare_cases(report, &name, "error", old_cases, new_cases);
+}
+
+/// Compares the cases of enums and error enums, which are stored as their
+/// value, so changing the value of a case is breaking.
+fn compare_cases(
+    report: &mut Report,
+    name: &str,
+    kind: &str,
+    old: impl Iterator<Item = (String, u32)>,
+    new: impl Iterator<Item = (String, u32)>,
+) {
+    let new = new.collect::<Vec<_>>();
+    let mut old_names = Vec::new();
+    for (case, value) in old {
+        let path = format!("{name}.{case}");
+        match new.iter().find(|(n, _)| n == &case) {
+            Some((_, new_value)) if *new_value != value => report.push(
+                path,
+                Change::Modified,
+                Compatibility::Breaking,
+                format!("{kind} value changed from {value} to {new_value}"),
+            ),
+            Some(_) => {}
+            None => report.push(
+                path,
+                Change::Removed,
+                Compatibility::Breaking,
+                format!("{kind} removed"),
+            ),
+        }
+        old_names.push(case);
+    }
+    for (case, _) in new.iter() {
+        if !old_names.contains(case) {
+            report.push(
+                format!("{name}.{case}"),
+                Change::Added,
+                Compatibility::Compatible,
+                format!("{kind} added"),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use stellar_xdr::{ScSpecEntry, ScSpecTypeDef};
+
+    use super::{compare, Change, Compatibility, Difference};
+
+    const EXAMPLE_WASM: &[u8] =
+        include_bytes!("../../target/wasm32-unknown-unknown/release/test_udt.wasm");
+
+    fn diff(
+        path: &str,
+        change: Change,
+        compatibility: Compatibility,
+        description: &str,
+    ) -> Difference {
+        Difference {
+            path: path.to_string(),
+            change,
+            compatibility,
+            description: description.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_identical() {
+        let entries = crate::read::from_wasm(EXAMPLE_WASM).unwrap();
+        let report = compare(&entries, &entries);
+        assert_eq!(report.differences, vec![]);
+        assert!(report.is_compatible());
+    }
+
+    #[test]
+    fn test_compatible() {
+        let old = crate::read::from_wasm(EXAMPLE_WASM).unwrap();
+        let mut new = old.clone();
+        for e in new.iter_mut() {
+            match e {
+                ScSpecEntry::UdtStructV0(s) if s.name.to_string_lossy() == "UdtStruct" => {
+                    let mut fields = s.fields.to_vec();
+                    fields.swap(0, 1);
+                    s.fields = fields.try_into().unwrap();
+                }
+                ScSpecEntry::UdtUnionV0(u) => {
+                    let mut cases = u.cases.to_vec();
+                    let mut case = cases[0].clone();
+                    case.name = "UdtE".try_into().unwrap();
+                    cases.push(case);
+                    u.cases = cases.try_into().unwrap();
+                }
+                _ => {}
+            }
+        }
+        let report = compare(&old, &new);
+        assert_eq!(
+            report.differences,
+            vec![
+                diff(
+                    "UdtEnum.UdtE",
+                    Change::Added,
+                    Compatibility::Compatible,
+                    "case added"
+                ),
+                diff(
+                    "UdtStruct.a",
+                    Change::Reordered,
+                    Compatibility::Compatible,
+                    "field moved from position 0 to 1"
+                ),
+                diff(
+                    "UdtStruct.b",
+                    Change::Reordered,
+                    Compatibility::Compatible,
+                    "field moved from position 1 to 0"
+                ),
+            ]
+        );
+        assert!(report.is_compatible());
+    }
+
+    #[test]
+    fn test_breaking() {
+        let old = crate::read::from_wasm(EXAMPLE_WASM).unwrap();
+        let mut new = old.clone();
+        for e in new.iter_mut() {
+            match e {
+                ScSpecEntry::FunctionV0(f) => {
+                    let mut inputs = f.inputs.to_vec();
+                    inputs[1].type_ = ScSpecTypeDef::U64;
+                    f.inputs = inputs.try_into().unwrap();
+                }
+                ScSpecEntry::UdtEnumV0(e) => {
+                    let mut cases = e.cases.to_vec();
+                    cases[1].value = 20;
+                    e.cases = cases.try_into().unwrap();
+                }
+                _ => {}
+            }
+        }
+        new.retain(
+            |e| !matches!(e, ScSpecEntry::UdtStructV0(s) if s.name.to_string_lossy() == "UdtTuple"),
+        );
+        let report = compare(&old, &new);
+        assert_eq!(
+            report.differences,
+            vec![
+                diff(
+                    "UdtEnum2.B",
+                    Change::Modified,
+                    Compatibility::Breaking,
+                    "case value changed from 15 to 20"
+                ),
+                diff(
+                    "UdtTuple",
+                    Change::Removed,
+                    Compatibility::Breaking,
+                    "struct removed"
+                ),
+                diff(
+                    "add.b",
+                    Change::Modified,
+                    Compatibility::Breaking,
+                    "input type changed from UdtEnum to u64"
+                ),
+            ]
+        );
+        assert!(!report.is_compatible());
+        assert_eq!(
+            report.to_json(),
+            r#"{
+  "compatibility": "breaking",
+  "differences": [
+    {
+      "path": "UdtEnum2.B",
+      "change": "modified",
+      "compatibility": "breaking",
+      "description": "case value changed from 15 to 20"
+    },
+    {
+      "path": "UdtTuple",
+      "change": "removed",
+      "compatibility": "breaking",
+      "description": "struct removed"
+    },
+    {
+      "path": "add.b",
+      "change": "modified",
+      "compatibility": "breaking",
+      "description": "input type changed from UdtEnum to u64"
+    }
+  ]
+}"#
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! Doc strings of the functions and types of a contract.
+//!
+//! The contract spec does not have fields for doc strings, so they are stored
+//! alongside the spec in the `contractdocsv0` custom section. The section is a
+//! sequence of XDR [`ScMapEntry`]s, where the key is the path of the documented
+//! item and the value is the doc string, both as bytes.
+//!
+//! The path of a function or type is its name. The path of a function input,
+//! struct field, union case, or enum case is the name of the function or type
+//! and the name of the input, field, or case, separated by a `.`, e.g. `add.a`
+//! or `UdtEnum.UdtB`.
+
+use std::{collections::BTreeMap, io::Cursor};
+
+use stellar_xdr::{ReadXdr, ScMapEntry, ScObject, ScVal, WriteXdr};
+
+/// Doc strings keyed by the path of the item they document.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Docs(BTreeMap<String, String>);
+
+impl Docs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Inserts the doc string for the path. Empty doc strings are ignored.
+    pub fn insert(&mut self, path: impl Into<String>, doc: impl Into<String>) {
+        let doc = doc.into();
+        if !doc.is_empty() {
+            self.0.insert(path.into(), doc);
+        }
+    }
+
+    /// Returns the doc string for the path, if there is one.
+    pub fn get(&self, path: &str) -> Option<&str> {
+        self.0.get(path).map(String::as_str)
+    }
+
+    /// Adds the doc strings from the other docs.
+    pub fn extend(&mut self, other: Docs) {
+        self.0.extend(other.0);
+    }
+
+    /// Returns the doc strings encoded as they are stored in the
+    /// `contractdocsv0` custom section.
+    pub fn to_xdr(&self) -> Result<Vec<u8>, stellar_xdr::Error> {
+        let mut xdr = Vec::new();
+        for (path, doc) in &self.0 {
+            let entry = ScMapEntry {
+                key: ScVal::Object(Some(ScObject::Bytes(path.as_bytes().to_vec().try_into()?))),
+                val: ScVal::Object(Some(ScObject::Bytes(doc.as_bytes().to_vec().try_into()?))),
+            };
+            xdr.extend(entry.to_xdr()?);
+        }
+        Ok(xdr)
+    }
+
+    /// Parses the doc strings stored in the `contractdocsv0` custom section.
+    pub fn from_xdr(xdr: &[u8]) -> Result<Self, stellar_xdr::Error> {
+        let mut cursor = Cursor::new(xdr);
+        let mut docs = Docs::new();
+        for entry in ScMapEntry::read_xdr_iter(&mut cursor) {
+            match entry? {
+                ScMapEntry {
+                    key: ScVal::Object(Some(ScObject::Bytes(path))),
+                    val: ScVal::Object(Some(ScObject::Bytes(doc))),
+                } => docs.insert(
+                    String::from_utf8_lossy(&path).into_owned(),
+                    String::from_utf8_lossy(&doc).into_owned(),
+                ),
+                _ => return Err(stellar_xdr::Error::Invalid),
+            }
+        }
+        Ok(docs)
+    }
+}
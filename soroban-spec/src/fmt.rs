@@ -0,0 +1,545 @@
+//! Format contract values, events, and debug logs for display, using the
+//! contract spec to name the types, fields, and cases of values.
+
+use itertools::Itertools;
+use stellar_xdr::{
+    ContractEvent, ContractEventBody, ContractEventType, ScBigInt, ScMapEntry, ScObject,
+    ScSpecEntry, ScSpecTypeDef, ScSpecUdtStructV0, ScStatic, ScStatus, ScVal,
+};
+
+use crate::{
+    gen::{events, struct_alias, Event},
+    generic::instantiate,
+    read::{from_wasm, FromWasmError},
+};
+
+/// Formats values and events of a contract for display.
+///
+/// Values are formatted using the types in the contract spec, so that values of
+/// user defined types are displayed with the names of the structs, unions,
+/// enums, and error enums of the contract, along with the names of their
+/// fields and cases. Values that do not match the type expected are displayed
+/// without type information.
+///
+/// Events are formatted using the events described in the contract spec, so
+/// that the topics and the fields of the data of the event are displayed with
+/// their names. Events that are not described are displayed without type
+/// information.
+///
+/// Debug logs are formatted by filling the format string of the log with its
+/// arguments. The types of the arguments of debug logs are not in the spec, so
+/// they are displayed without type information.
+#[derive(Clone, Debug, Default)]
+pub struct Formatter {
+    entries: Vec<ScSpecEntry>,
+}
+
+impl Formatter {
+    /// Create a formatter with the spec entries of a contract. Values of generic
+    /// types are converted as the instances of the types the contract uses.
+    pub fn new(entries: Vec<ScSpecEntry>) -> Self {
+        Self {
+            entries: instantiate(&entries),
+        }
+    }
+
+    /// Create a formatter with the spec entries read from the contract wasm.
+    pub fn from_wasm(wasm: &[u8]) -> Result<Self, FromWasmError> {
+        Ok(Self::new(from_wasm(wasm)?))
+    }
+
+    /// Format the event.
+    ///
+    /// Events described by the contract spec are formatted as the name of the
+    /// event followed by the named topics and the fields of the data, e.g.:
+    ///
+    /// ```text
+    /// contract 0000…0000: transfer(from: alice, to: bob) = { amount: 5 }
+    /// ```
+    ///
+    /// Other events are formatted as the list of topics followed by the data,
+    /// e.g.:
+    ///
+    /// ```text
+    /// contract 0000…0000: [transfer, alice, bob] = 5
+    /// ```
+    pub fn event(&self, event: &ContractEvent) -> String {
+        let source = match (&event.type_, &event.contract_id) {
+            (ContractEventType::Contract, Some(id)) => format!("contract {}", hex(&id.0)),
+            (ContractEventType::Contract, None) => "contract".to_string(),
+            (ContractEventType::System, _) => "system".to_string(),
+        };
+        let ContractEventBody::V0(body) = &event.body;
+        let topics: Vec<&ScVal> = body.topics.iter().collect();
+        let structs = self.structs();
+        let spec = self.event_spec(&structs, &topics);
+        let data = spec.as_ref().and_then(|spec| match &body.data {
+            ScVal::Object(Some(ScObject::Map(entries))) => self.fields(spec.data, entries),
+            _ => None,
+        });
+        let formatted = match (spec, data) {
+            (Some(spec), Some(data)) => format!(
+                "{}({}) = {}",
+                spec.name,
+                spec.topics
+                    .iter()
+                    .zip(topics.iter().skip(1))
+                    .map(|(topic, v)| format!(
+                        "{}: {}",
+                        topic.name.to_string_lossy(),
+                        self.val(&topic.type_, v)
+                    ))
+                    .join(", "),
+                if data.is_empty() {
+                    "{}".to_string()
+                } else {
+                    format!("{{ {} }}", data.join(", "))
+                },
+            ),
+            _ => format!(
+                "[{}] = {}",
+                topics.iter().map(|t| untyped(t)).join(", "),
+                untyped(&body.data)
+            ),
+        };
+        format!("{}: {}", source, formatted)
+    }
+
+    /// Format the debug log, replacing each `{}` in the format string with the
+    /// next argument, e.g.:
+    ///
+    /// ```text
+    /// debug: balance of alice: 5
+    /// ```
+    ///
+    /// Arguments left over once the format string has no more `{}` are
+    /// appended, separated by `, `. A `{}` left over once there are no more
+    /// arguments is displayed as is.
+    pub fn debug(&self, fmt: &str, args: &[ScVal]) -> String {
+        let mut args = args.iter();
+        let mut parts = fmt.split("{}");
+        let mut msg = parts.next().unwrap_or_default().to_string();
+        for part in parts {
+            match args.next() {
+                Some(arg) => msg.push_str(&untyped(arg)),
+                None => msg.push_str("{}"),
+            }
+            msg.push_str(part);
+        }
+        let rest = args.map(untyped).join(", ");
+        if rest.is_empty() {
+            format!("debug: {}", msg)
+        } else if msg.is_empty() {
+            format!("debug: {}", rest)
+        } else {
+            format!("debug: {}, {}", msg, rest)
+        }
+    }
+
+    /// Returns the structs of the spec.
+    fn structs(&self) -> Vec<&ScSpecUdtStructV0> {
+        self.entries
+            .iter()
+            .filter_map(|e| match e {
+                ScSpecEntry::UdtStructV0(s) => Some(s),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the event spec matching the name in the first topic and the
+    /// number of topics.
+    fn event_spec<'a>(
+        &self,
+        structs: &[&'a ScSpecUdtStructV0],
+        topics: &[&ScVal],
+    ) -> Option<Event<'a>> {
+        let name = match topics.first() {
+            Some(ScVal::Symbol(name)) => name.to_string_lossy(),
+            _ => return None,
+        };
+        events(structs)
+            .into_iter()
+            .find(|e| e.name == name && e.topics.len() + 1 == topics.len())
+    }
+
+    /// Format the value as the type.
+    pub fn val(&self, type_: &ScSpecTypeDef, val: &ScVal) -> String {
+        match (type_, val) {
+            (ScSpecTypeDef::Bool, ScVal::Static(ScStatic::True)) => "true".to_string(),
+            (ScSpecTypeDef::Bool, ScVal::Static(ScStatic::False)) => "false".to_string(),
+            (ScSpecTypeDef::Option(_), ScVal::Static(ScStatic::Void)) => "None".to_string(),
+            (ScSpecTypeDef::Option(o), v) => format!("Some({})", self.val(&o.value_type, v)),
+            (ScSpecTypeDef::Vec(t), ScVal::Object(Some(ScObject::Vec(items)))) => format!(
+                "[{}]",
+                items
+                    .iter()
+                    .map(|v| self.val(&t.element_type, v))
+                    .join(", ")
+            ),
+            (ScSpecTypeDef::Set(t), ScVal::Object(Some(ScObject::Map(entries)))) => format!(
+                "{{{}}}",
+                entries
+                    .iter()
+                    .map(|e| self.val(&t.element_type, &e.key))
+                    .join(", ")
+            ),
+            (ScSpecTypeDef::Map(t), ScVal::Object(Some(ScObject::Map(entries)))) => format!(
+                "{{{}}}",
+                entries
+                    .iter()
+                    .map(|e| format!(
+                        "{}: {}",
+                        self.val(&t.key_type, &e.key),
+                        self.val(&t.value_type, &e.val)
+                    ))
+                    .join(", ")
+            ),
+            (ScSpecTypeDef::Tuple(t), ScVal::Object(Some(ScObject::Vec(items))))
+                if t.value_types.len() == items.len() =>
+            {
+                format!(
+                    "({})",
+                    t.value_types
+                        .iter()
+                        .zip(items.iter())
+                        .map(|(t, v)| self.val(t, v))
+                        .join(", ")
+                )
+            }
+            (ScSpecTypeDef::Udt(udt), v) => self
+                .udt(&udt.name.to_string_lossy(), v)
+                .unwrap_or_else(|| untyped(v)),
+            (_, v) => untyped(v),
+        }
+    }
+
+    /// Format the value as the user defined type with the name, or returns
+    /// [None] if the type is not in the spec or the value does not match the
+    /// type.
+    fn udt(&self, name: &str, val: &ScVal) -> Option<String> {
+        let entry = self.entries.iter().find(|e| match e {
+            ScSpecEntry::UdtStructV0(s) => s.name.to_string_lossy() == name,
+            ScSpecEntry::UdtUnionV0(u) => u.name.to_string_lossy() == name,
+            ScSpecEntry::UdtEnumV0(e) => e.name.to_string_lossy() == name,
+            ScSpecEntry::UdtErrorEnumV0(e) => e.name.to_string_lossy() == name,
+            ScSpecEntry::FunctionV0(_) => false,
+        })?;
+        match (entry, val) {
+            // Transparent structs are the value of their single field.
+            (ScSpecEntry::UdtStructV0(s), v) if struct_alias(s).is_some() => {
+                Some(format!("{}({})", name, self.val(struct_alias(s)?, v)))
+            }
+            // Unit structs are void.
+            (ScSpecEntry::UdtStructV0(s), ScVal::Static(ScStatic::Void)) if s.fields.is_empty() => {
+                Some(name.to_string())
+            }
+            // Structs with named fields are maps from field name to value.
+            (ScSpecEntry::UdtStructV0(s), ScVal::Object(Some(ScObject::Map(entries)))) => {
+                let fields = self.fields(s, entries)?;
+                if fields.is_empty() {
+                    Some(name.to_string())
+                } else {
+                    Some(format!("{} {{ {} }}", name, fields.join(", ")))
+                }
+            }
+            // Structs with unnamed fields are vecs of the field values.
+            (ScSpecEntry::UdtStructV0(s), ScVal::Object(Some(ScObject::Vec(items))))
+                if s.fields.len() == items.len() =>
+            {
+                Some(format!(
+                    "{}({})",
+                    name,
+                    s.fields
+                        .iter()
+                        .zip(items.iter())
+                        .map(|(f, v)| self.val(&f.type_, v))
+                        .join(", ")
+                ))
+            }
+            // Unions are vecs of the case name followed by the case value.
+            (ScSpecEntry::UdtUnionV0(u), ScVal::Object(Some(ScObject::Vec(items)))) => {
+                let (case_name, values) = match items.split_first() {
+                    Some((ScVal::Symbol(case_name), values)) => {
+                        (case_name.to_string_lossy(), values)
+                    }
+                    _ => return None,
+                };
+                let case = u
+                    .cases
+                    .iter()
+                    .find(|c| c.name.to_string_lossy() == case_name)?;
+                match (&case.type_, values) {
+                    (None, []) => Some(format!("{}::{}", name, case_name)),
+                    (Some(type_), [v]) => {
+                        Some(format!("{}::{}({})", name, case_name, self.val(type_, v)))
+                    }
+                    _ => None,
+                }
+            }
+            // Enums are u32 values.
+            (ScSpecEntry::UdtEnumV0(e), ScVal::U32(value)) => e
+                .cases
+                .iter()
+                .find(|c| c.value == *value)
+                .map(|c| format!("{}::{}", name, c.name.to_string_lossy())),
+            // Error enums are contract error statuses, or u32 values.
+            (
+                ScSpecEntry::UdtErrorEnumV0(e),
+                ScVal::Status(ScStatus::ContractError(value)) | ScVal::U32(value),
+            ) => e
+                .cases
+                .iter()
+                .find(|c| c.value == *value)
+                .map(|c| format!("{}::{}", name, c.name.to_string_lossy())),
+            _ => None,
+        }
+    }
+
+    /// Format the fields of the struct that are the entries of a map from field
+    /// name to value, or returns [None] if a field is missing.
+    fn fields(&self, s: &ScSpecUdtStructV0, entries: &[ScMapEntry]) -> Option<Vec<String>> {
+        s.fields
+            .iter()
+            .map(|f| {
+                let field_name = f.name.to_string_lossy();
+                let v = entries.iter().find(
+                    |e| matches!(&e.key, ScVal::Symbol(k) if k.to_string_lossy() == field_name),
+                )?;
+                Some(format!("{}: {}", field_name, self.val(&f.type_, &v.val)))
+            })
+            .collect()
+    }
+}
+
+/// Format the value without type information.
+fn untyped(val: &ScVal) -> String {
+    match val {
+        ScVal::U63(v) => v.to_string(),
+        ScVal::U32(v) => v.to_string(),
+        ScVal::I32(v) => v.to_string(),
+        ScVal::Static(ScStatic::Void) => "()".to_string(),
+        ScVal::Static(ScStatic::True) => "true".to_string(),
+        ScVal::Static(ScStatic::False) => "false".to_string(),
+        ScVal::Symbol(v) => v.to_string_lossy(),
+        ScVal::Bitset(v) => format!("{:#b}", v),
+        ScVal::Object(Some(ScObject::Vec(items))) => {
+            format!("[{}]", items.iter().map(untyped).join(", "))
+        }
+        ScVal::Object(Some(ScObject::Map(entries))) => format!(
+            "{{{}}}",
+            entries
+                .iter()
+                .map(|e| format!("{}: {}", untyped(&e.key), untyped(&e.val)))
+                .join(", ")
+        ),
+        ScVal::Object(Some(ScObject::U64(v))) => v.to_string(),
+        ScVal::Object(Some(ScObject::I64(v))) => v.to_string(),
+        ScVal::Object(Some(ScObject::Bytes(v))) => hex(v.as_slice()),
+        ScVal::Object(Some(ScObject::BigInt(v))) => match v {
+            ScBigInt::Zero => "0".to_string(),
+            ScBigInt::Positive(m) => decimal(m.as_slice()),
+            ScBigInt::Negative(m) => format!("-{}", decimal(m.as_slice())),
+        },
+        v => format!("{:?}", v),
+    }
+}
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Converts the big-endian unsigned integer magnitude into a decimal string.
+pub(crate) fn decimal(magnitude: &[u8]) -> String {
+    let mut digits = Vec::<u8>::new();
+    let mut remaining = magnitude.to_vec();
+    while remaining.iter().any(|b| *b != 0) {
+        let mut rem = 0u32;
+        for b in remaining.iter_mut() {
+            let cur = (rem << 8) | u32::from(*b);
+            *b = (cur / 10) as u8;
+            rem = cur % 10;
+        }
+        digits.push(b'0' + rem as u8);
+    }
+    if digits.is_empty() {
+        return "0".to_string();
+    }
+    digits.iter().rev().map(|d| *d as char).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use stellar_xdr::{
+        ContractEvent, ContractEventBody, ContractEventType, ContractEventV0, ExtensionPoint, Hash,
+        ScBigInt, ScMap, ScMapEntry, ScObject, ScSpecEntry, ScSpecTypeDef, ScSpecTypeUdt,
+        ScSpecUdtStructFieldV0, ScSpecUdtStructV0, ScVal, ScVec, StringM,
+    };
+
+    use super::Formatter;
+
+    const EXAMPLE_WASM: &[u8] =
+        include_bytes!("../../target/wasm32-unknown-unknown/release/test_udt.wasm");
+
+    fn sym(s: &str) -> ScVal {
+        ScVal::Symbol(s.try_into().unwrap())
+    }
+
+    fn vec(items: Vec<ScVal>) -> ScVal {
+        ScVal::Object(Some(ScObject::Vec(ScVec(items.try_into().unwrap()))))
+    }
+
+    fn udt(name: &str) -> ScSpecTypeDef {
+        ScSpecTypeDef::Udt(ScSpecTypeUdt {
+            name: name.try_into().unwrap(),
+        })
+    }
+
+    fn udt_struct() -> ScVal {
+        ScVal::Object(Some(ScObject::Map(ScMap(
+            vec![
+                ScMapEntry {
+                    key: sym("a"),
+                    val: ScVal::U63(10),
+                },
+                ScMapEntry {
+                    key: sym("b"),
+                    val: ScVal::U63(12),
+                },
+                ScMapEntry {
+                    key: sym("c"),
+                    val: vec(vec![ScVal::U63(1)]),
+                },
+            ]
+            .try_into()
+            .unwrap(),
+        ))))
+    }
+
+    #[test]
+    fn test_val() {
+        let f = Formatter::from_wasm(EXAMPLE_WASM).unwrap();
+        assert_eq!(
+            f.val(&udt("UdtStruct"), &udt_struct()),
+            "UdtStruct { a: 10, b: 12, c: [1] }"
+        );
+        assert_eq!(
+            f.val(&udt("UdtEnum"), &vec(vec![sym("UdtB"), udt_struct()])),
+            "UdtEnum::UdtB(UdtStruct { a: 10, b: 12, c: [1] })"
+        );
+        assert_eq!(
+            f.val(&udt("UdtEnum"), &vec(vec![sym("UdtC"), ScVal::U32(15)])),
+            "UdtEnum::UdtC(UdtEnum2::B)"
+        );
+        assert_eq!(
+            f.val(&udt("UdtEnum"), &vec(vec![sym("UdtA")])),
+            "UdtEnum::UdtA"
+        );
+        assert_eq!(
+            f.val(
+                &udt("UdtTuple"),
+                &vec(vec![ScVal::U63(1), vec(vec![ScVal::U63(2), ScVal::U63(3)])])
+            ),
+            "UdtTuple(1, [2, 3])"
+        );
+        // Values that do not match the type are formatted without the type.
+        assert_eq!(f.val(&udt("UdtEnum"), &vec(vec![sym("UdtE")])), "[UdtE]");
+        assert_eq!(
+            f.val(
+                &ScSpecTypeDef::Val,
+                &ScVal::Object(Some(ScObject::BigInt(ScBigInt::Negative(
+                    vec![1, 0, 0, 0, 0, 0, 0, 0, 0].try_into().unwrap()
+                ))))
+            ),
+            "-18446744073709551616"
+        );
+    }
+
+    fn event_struct(name: &str, fields: Vec<(&str, ScSpecTypeDef)>) -> ScSpecEntry {
+        ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+            lib: StringM::default(),
+            name: name.try_into().unwrap(),
+            fields: fields
+                .into_iter()
+                .map(|(name, type_)| ScSpecUdtStructFieldV0 {
+                    name: name.try_into().unwrap(),
+                    type_,
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+        })
+    }
+
+    #[test]
+    fn test_event() {
+        let mut entries = crate::read::from_wasm(EXAMPLE_WASM).unwrap();
+        entries.extend([
+            event_struct(
+                "event.added",
+                vec![("a", udt("UdtEnum")), ("data", udt("event.added.data"))],
+            ),
+            event_struct("event.added.data", vec![("value", udt("UdtStruct"))]),
+            event_struct("event.moved", vec![("data", udt("event.moved.data"))]),
+            event_struct("event.moved.data", vec![("to", ScSpecTypeDef::Val)]),
+        ]);
+        let f = Formatter::new(entries);
+
+        let event = |topics: Vec<ScVal>, data: ScVal| ContractEvent {
+            ext: ExtensionPoint::V0,
+            contract_id: Some(Hash([1; 32])),
+            type_: ContractEventType::Contract,
+            body: ContractEventBody::V0(ContractEventV0 {
+                topics: ScVec(topics.try_into().unwrap()),
+                data,
+            }),
+        };
+        let map = |key: &str, val: ScVal| {
+            ScVal::Object(Some(ScObject::Map(ScMap(
+                vec![ScMapEntry { key: sym(key), val }].try_into().unwrap(),
+            ))))
+        };
+
+        assert_eq!(
+            f.event(&event(
+                vec![sym("added"), vec(vec![sym("UdtA")])],
+                map("value", udt_struct())
+            )),
+            "contract 0101010101010101010101010101010101010101010101010101010101010101: \
+            added(a: UdtEnum::UdtA) = { value: UdtStruct { a: 10, b: 12, c: [1] } }"
+        );
+        assert_eq!(
+            f.event(&event(vec![sym("moved")], map("to", ScVal::U63(5)))),
+            "contract 0101010101010101010101010101010101010101010101010101010101010101: \
+            moved() = { to: 5 }"
+        );
+        // Events with data that does not match are formatted without types.
+        assert_eq!(
+            f.event(&event(vec![sym("moved")], ScVal::U63(5))),
+            "contract 0101010101010101010101010101010101010101010101010101010101010101: \
+            [moved] = 5"
+        );
+        // Events not described are formatted without types.
+        assert_eq!(
+            f.event(&event(vec![sym("removed"), ScVal::U32(2)], ScVal::U63(5))),
+            "contract 0101010101010101010101010101010101010101010101010101010101010101: \
+            [removed, 2] = 5"
+        );
+    }
+
+    #[test]
+    fn test_debug() {
+        let f = Formatter::default();
+        assert_eq!(
+            f.debug("balance of {}: {}", &[sym("alice"), ScVal::U63(5)]),
+            "debug: balance of alice: 5"
+        );
+        assert_eq!(
+            f.debug("balance", &[sym("alice"), ScVal::U63(5)]),
+            "debug: balance, alice, 5"
+        );
+        assert_eq!(f.debug("", &[ScVal::U32(1)]), "debug: 1");
+        assert_eq!(f.debug("{} of {}", &[ScVal::U32(1)]), "debug: 1 of {}");
+    }
+}
@@ -1,2 +1,197 @@
 pub mod json;
+pub mod markdown;
+pub mod python;
 pub mod rust;
+pub mod typescript;
+
+use std::{fs, io};
+
+use itertools::Itertools;
+use sha2::{Digest, Sha256};
+use stellar_xdr::{
+    ScSpecTypeDef, ScSpecUdtStructFieldV0, ScSpecUdtStructV0, ScSpecUdtUnionCaseV0,
+    ScSpecUdtUnionV0,
+};
+
+use crate::{
+    read::FromWasmError,
+    validate::{fmt_diagnostics, Diagnostic},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum GenerateFromFileError {
+    #[error("reading file: {0}")]
+    Io(io::Error),
+    #[error("sha256 does not match, expected: {expected}")]
+    VerifySha256 { expected: String },
+    #[error("getting contract spec: {0}")]
+    GetSpec(FromWasmError),
+    #[error("validating contract spec:\n{}", fmt_diagnostics(.0))]
+    Validate(Vec<Diagnostic>),
+}
+
+/// Reads the wasm file. If a sha256 to verify is given, it must match the
+/// sha256 of the wasm.
+pub fn read_wasm_file(
+    file: &str,
+    verify_sha256: Option<&str>,
+) -> Result<Vec<u8>, GenerateFromFileError> {
+    let wasm = fs::read(file).map_err(GenerateFromFileError::Io)?;
+    wasm_sha256(&wasm, verify_sha256)?;
+    Ok(wasm)
+}
+
+/// Returns the sha256 of the wasm in hex. If a sha256 to verify is given, it
+/// must match the sha256 of the wasm.
+pub fn wasm_sha256(
+    wasm: &[u8],
+    verify_sha256: Option<&str>,
+) -> Result<String, GenerateFromFileError> {
+    let sha256 = format!("{:x}", Sha256::digest(wasm));
+    if let Some(verify_sha256) = verify_sha256 {
+        if verify_sha256 != sha256 {
+            return Err(GenerateFromFileError::VerifySha256 { expected: sha256 });
+        }
+    }
+    Ok(sha256)
+}
+
+/// Returns the name of the spec type, as it is written in Rust contracts.
+pub fn type_name(spec: &ScSpecTypeDef) -> String {
+    match spec {
+        ScSpecTypeDef::Val => "RawVal".to_string(),
+        ScSpecTypeDef::U64 => "u64".to_string(),
+        ScSpecTypeDef::I64 => "i64".to_string(),
+        ScSpecTypeDef::U32 => "u32".to_string(),
+        ScSpecTypeDef::I32 => "i32".to_string(),
+        ScSpecTypeDef::Bool => "bool".to_string(),
+        ScSpecTypeDef::Symbol => "Symbol".to_string(),
+        ScSpecTypeDef::Bitset => "Bitset".to_string(),
+        ScSpecTypeDef::Status => "Status".to_string(),
+        ScSpecTypeDef::Bytes => "Bytes".to_string(),
+        ScSpecTypeDef::BigInt => "BigInt".to_string(),
+        ScSpecTypeDef::Invoker => "Invoker".to_string(),
+        ScSpecTypeDef::AccountId => "AccountId".to_string(),
+        ScSpecTypeDef::Option(o) => format!("Option<{}>", type_name(&o.value_type)),
+        ScSpecTypeDef::Result(r) => format!(
+            "Result<{}, {}>",
+            type_name(&r.ok_type),
+            type_name(&r.error_type)
+        ),
+        ScSpecTypeDef::Vec(v) => format!("Vec<{}>", type_name(&v.element_type)),
+        ScSpecTypeDef::Map(m) => format!(
+            "Map<{}, {}>",
+            type_name(&m.key_type),
+            type_name(&m.value_type)
+        ),
+        ScSpecTypeDef::Set(s) => format!("Set<{}>", type_name(&s.element_type)),
+        ScSpecTypeDef::Tuple(t) => format!("({})", t.value_types.iter().map(type_name).join(", ")),
+        ScSpecTypeDef::BytesN(b) => format!("BytesN<{}>", b.n),
+        ScSpecTypeDef::Udt(u) => u.name.to_string_lossy(),
+    }
+}
+
+/// Returns the aliased type if the struct is a transparent newtype, described
+/// as a struct with a single field with no name.
+pub fn struct_alias(s: &ScSpecUdtStructV0) -> Option<&ScSpecTypeDef> {
+    match s.fields.as_slice() {
+        [f] if f.name.len() == 0 => Some(&f.type_),
+        _ => None,
+    }
+}
+
+/// Returns true if the struct describes the fields of a union case that is a
+/// variant with named fields, rather than being a type of its own.
+///
+/// Such structs are named with the path of the case, the name of the union and
+/// the case separated by a `.`, e.g. `State.Auction` for the `Auction` case of
+/// `State`. No other type, other than the structs describing events, can have a
+/// `.` in its name.
+pub fn is_union_case_struct(s: &ScSpecUdtStructV0) -> bool {
+    s.name.to_string_lossy().contains('.') && !is_event_struct(s)
+}
+
+/// Prefix of the names of the structs describing the events of a contract.
+pub const EVENT_PREFIX: &str = "event.";
+
+/// Returns true if the struct describes an event of the contract, or the data
+/// of an event, rather than being a type of its own.
+///
+/// Each event is described by a struct named `event.` followed by the name of
+/// the event, e.g. `event.transfer`. The fields of the struct are the topics
+/// that follow the name of the event, in the order they are published, and a
+/// last field named `data` that has the type of the struct describing the
+/// fields of the data, e.g. `event.transfer.data`.
+pub fn is_event_struct(s: &ScSpecUdtStructV0) -> bool {
+    s.name.to_string_lossy().starts_with(EVENT_PREFIX)
+}
+
+/// An event of a contract, as described by the structs of the spec.
+#[derive(Clone, Debug)]
+pub struct Event<'a> {
+    /// The name of the event, that is the first topic of the event.
+    pub name: String,
+    /// The topics that follow the name of the event.
+    pub topics: &'a [ScSpecUdtStructFieldV0],
+    /// The struct describing the fields of the data of the event.
+    pub data: &'a ScSpecUdtStructV0,
+}
+
+/// Returns the event described by the struct, or [None] if the struct does not
+/// describe an event.
+pub fn event<'a>(s: &'a ScSpecUdtStructV0, structs: &[&'a ScSpecUdtStructV0]) -> Option<Event<'a>> {
+    let struct_name = s.name.to_string_lossy();
+    let name = struct_name.strip_prefix(EVENT_PREFIX)?;
+    if name.contains('.') {
+        return None;
+    }
+    let (data_field, topics) = s.fields.split_last()?;
+    let data_name = match &data_field.type_ {
+        ScSpecTypeDef::Udt(u) if data_field.name.to_string_lossy() == "data" => {
+            u.name.to_string_lossy()
+        }
+        _ => return None,
+    };
+    if data_name != format!("{struct_name}.data") {
+        return None;
+    }
+    let data = structs
+        .iter()
+        .copied()
+        .find(|s| s.name.to_string_lossy() == data_name)?;
+    Some(Event {
+        name: name.to_string(),
+        topics,
+        data,
+    })
+}
+
+/// Returns the events described by the structs.
+pub fn events<'a>(structs: &[&'a ScSpecUdtStructV0]) -> Vec<Event<'a>> {
+    structs.iter().filter_map(|s| event(s, structs)).collect()
+}
+
+/// Returns the struct describing the fields of the union case if the case is a
+/// variant with named fields.
+pub fn union_case_struct<'a>(
+    union: &ScSpecUdtUnionV0,
+    case: &ScSpecUdtUnionCaseV0,
+    structs: &[&'a ScSpecUdtStructV0],
+) -> Option<&'a ScSpecUdtStructV0> {
+    let name = match &case.type_ {
+        Some(ScSpecTypeDef::Udt(u)) => u.name.to_string_lossy(),
+        _ => return None,
+    };
+    let expected = format!(
+        "{}.{}",
+        union.name.to_string_lossy(),
+        case.name.to_string_lossy()
+    );
+    if name != expected {
+        return None;
+    }
+    structs
+        .iter()
+        .copied()
+        .find(|s| s.name.to_string_lossy() == name)
+}
@@ -1,42 +1,20 @@
-use std::{fs, io};
-
 pub mod types;
 
-use sha2::{Digest, Sha256};
 use stellar_xdr::ScSpecEntry;
 
 use types::Entry;
 
-use crate::read::{from_wasm, FromWasmError};
-
-#[derive(thiserror::Error, Debug)]
-pub enum GenerateFromFileError {
-    #[error("reading file: {0}")]
-    Io(io::Error),
-    #[error("sha256 does not match, expected: {expected}")]
-    VerifySha256 { expected: String },
-    #[error("parsing contract spec: {0}")]
-    Parse(stellar_xdr::Error),
-    #[error("getting contract spec: {0}")]
-    GetSpec(FromWasmError),
-}
+use crate::{
+    gen::{is_event_struct, is_union_case_struct, read_wasm_file, GenerateFromFileError},
+    read::{from_wasm, FromWasmError},
+};
 
 pub fn generate_from_file(
     file: &str,
     verify_sha256: Option<&str>,
 ) -> Result<String, GenerateFromFileError> {
     // Read file.
-    let wasm = fs::read(file).map_err(GenerateFromFileError::Io)?;
-
-    // Produce hash for file.
-    let sha256 = Sha256::digest(&wasm);
-    let sha256 = format!("{:x}", sha256);
-
-    if let Some(verify_sha256) = verify_sha256 {
-        if verify_sha256 != sha256 {
-            return Err(GenerateFromFileError::VerifySha256 { expected: sha256 });
-        }
-    }
+    let wasm = read_wasm_file(file, verify_sha256)?;
 
     // Generate code.
     let json = generate_from_wasm(&wasm).map_err(GenerateFromFileError::GetSpec)?;
@@ -50,8 +28,23 @@ pub fn generate_from_wasm(wasm: &[u8]) -> Result<String, FromWasmError> {
 }
 
 pub fn generate(spec: &[ScSpecEntry]) -> String {
+    let structs = spec
+        .iter()
+        .filter_map(|e| match e {
+            ScSpecEntry::UdtStructV0(s) => Some(s),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    // Structs describing the fields of union cases are generated as part of
+    // the union, and structs describing events are not types.
     spec.iter()
-        .map(Entry::from)
+        .filter(|e| {
+            !matches!(e, ScSpecEntry::UdtStructV0(s) if is_union_case_struct(s) || is_event_struct(s))
+        })
+        .map(|e| match e {
+            ScSpecEntry::UdtUnionV0(u) => Entry::from_union(u, &structs),
+            e => Entry::from(e),
+        })
         .map(|e| serde_json::to_string_pretty(&e).expect("serialization of the spec entries should not have any failure cases as all keys are strings and the serialize implementations are derived"))
         .collect()
 }
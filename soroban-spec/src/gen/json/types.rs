@@ -1,9 +1,12 @@
 use serde::Serialize;
 use stellar_xdr::{
     ScSpecEntry, ScSpecFunctionInputV0, ScSpecTypeDef, ScSpecUdtEnumCaseV0,
-    ScSpecUdtErrorEnumCaseV0, ScSpecUdtStructFieldV0, ScSpecUdtUnionCaseV0,
+    ScSpecUdtErrorEnumCaseV0, ScSpecUdtStructFieldV0, ScSpecUdtStructV0, ScSpecUdtUnionCaseV0,
+    ScSpecUdtUnionV0,
 };
 
+use crate::gen::union_case_struct;
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StructField {
@@ -41,13 +44,45 @@ impl From<&ScSpecFunctionInputV0> for FunctionInput {
 pub struct UnionCase {
     name: String,
     values: Vec<Type>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<StructField>,
+}
+
+impl UnionCase {
+    /// Constructs the union case, using the structs to describe the fields of
+    /// cases that are variants with named fields.
+    pub fn from_case(
+        union: &ScSpecUdtUnionV0,
+        c: &ScSpecUdtUnionCaseV0,
+        structs: &[&ScSpecUdtStructV0],
+    ) -> Self {
+        if let Some(s) = union_case_struct(union, c, structs) {
+            UnionCase {
+                name: c.name.to_string_lossy(),
+                values: vec![],
+                fields: s.fields.iter().map(StructField::from).collect(),
+            }
+        } else {
+            UnionCase::from(c)
+        }
+    }
 }
 
 impl From<&ScSpecUdtUnionCaseV0> for UnionCase {
     fn from(c: &ScSpecUdtUnionCaseV0) -> Self {
+        let values = match &c.type_ {
+            None => vec![],
+            // Cases with a tuple of multiple values are variants with a value
+            // for each element, as they are encoded the same.
+            Some(ScSpecTypeDef::Tuple(t)) if t.value_types.len() > 1 => {
+                t.value_types.iter().map(Type::from).collect()
+            }
+            Some(t) => vec![Type::from(t)],
+        };
         UnionCase {
             name: c.name.to_string_lossy(),
-            values: c.type_.as_ref().map(Type::from).into_iter().collect(),
+            values,
+            fields: vec![],
         }
     }
 }
@@ -182,6 +217,21 @@ impl From<&ScSpecTypeDef> for Type {
     }
 }
 
+impl Entry {
+    /// Constructs the union entry, using the structs to describe the fields of
+    /// cases that are variants with named fields.
+    pub fn from_union(u: &ScSpecUdtUnionV0, structs: &[&ScSpecUdtStructV0]) -> Self {
+        Entry::Union {
+            name: u.name.to_string_lossy(),
+            cases: u
+                .cases
+                .iter()
+                .map(|c| UnionCase::from_case(u, c, structs))
+                .collect(),
+        }
+    }
+}
+
 impl From<&ScSpecEntry> for Entry {
     fn from(spec: &ScSpecEntry) -> Self {
         match spec {
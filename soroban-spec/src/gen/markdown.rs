@@ -0,0 +1,336 @@
+use itertools::Itertools;
+use stellar_xdr::{ScSpecEntry, ScSpecFunctionV0};
+
+use crate::{
+    docs::Docs,
+    gen::{events, is_event_struct, read_wasm_file, type_name, Event, GenerateFromFileError},
+    read::{docs_from_wasm, from_wasm, FromWasmError},
+};
+
+pub fn generate_from_file(
+    file: &str,
+    verify_sha256: Option<&str>,
+) -> Result<String, GenerateFromFileError> {
+    // Read file.
+    let wasm = read_wasm_file(file, verify_sha256)?;
+
+    // Generate docs.
+    let md = generate_from_wasm(&wasm).map_err(GenerateFromFileError::GetSpec)?;
+    Ok(md)
+}
+
+pub fn generate_from_wasm(wasm: &[u8]) -> Result<String, FromWasmError> {
+    let spec = from_wasm(wasm)?;
+    let docs = docs_from_wasm(wasm)?;
+    let md = generate(&spec, &docs);
+    Ok(md)
+}
+
+/// Generates Markdown documentation of the interface of a contract, with a
+/// section for each of the functions, events, and types of the contract.
+pub fn generate(specs: &[ScSpecEntry], docs: &Docs) -> String {
+    let mut blocks = vec!["# Contract".to_string()];
+
+    let fns = specs
+        .iter()
+        .filter_map(|s| match s {
+            ScSpecEntry::FunctionV0(f) => Some(f),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if !fns.is_empty() {
+        blocks.push("## Functions".to_string());
+        for f in fns {
+            blocks.extend(generate_function(f, docs));
+        }
+    }
+
+    let structs = specs
+        .iter()
+        .filter_map(|s| match s {
+            ScSpecEntry::UdtStructV0(s) => Some(s),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let events = events(&structs);
+    if !events.is_empty() {
+        blocks.push("## Events".to_string());
+        for e in &events {
+            blocks.extend(generate_event(e, docs));
+        }
+    }
+
+    let types = specs
+        .iter()
+        .filter(|s| match s {
+            ScSpecEntry::FunctionV0(_) => false,
+            ScSpecEntry::UdtStructV0(s) => !is_event_struct(s),
+            _ => true,
+        })
+        .collect::<Vec<_>>();
+    if !types.is_empty() {
+        blocks.push("## Types".to_string());
+        for t in types {
+            blocks.extend(generate_type(t, docs));
+        }
+    }
+
+    blocks.join("\n\n") + "\n"
+}
+
+fn generate_function(spec: &ScSpecFunctionV0, docs: &Docs) -> Vec<String> {
+    let name = spec.name.to_string_lossy();
+    let mut blocks = vec![format!("### `{name}`")];
+    blocks.extend(docs.get(&name).map(str::to_string));
+    let inputs = spec
+        .inputs
+        .iter()
+        .map(|i| format!("{}: {}", i.name.to_string_lossy(), type_name(&i.type_)))
+        .join(", ");
+    let output = match spec.outputs.first() {
+        Some(t) => format!(" -> {}", type_name(t)),
+        None => String::new(),
+    };
+    blocks.push(format!("```text\n{name}({inputs}){output}\n```"));
+    let rows = spec
+        .inputs
+        .iter()
+        .map(|i| {
+            let input = i.name.to_string_lossy();
+            [
+                format!("`{input}`"),
+                format!("`{}`", type_name(&i.type_)),
+                cell(docs.get(&format!("{name}.{input}"))),
+            ]
+        })
+        .collect::<Vec<_>>();
+    blocks.extend(table(["Input", "Type", "Description"], &rows));
+    blocks
+}
+
+fn generate_event(event: &Event, docs: &Docs) -> Vec<String> {
+    let name = &event.name;
+    // The doc strings of an event are at the paths of the structs describing
+    // the event and its data.
+    let path = format!("event.{name}");
+    let mut blocks = vec![format!("### `{name}`")];
+    blocks.extend(docs.get(&path).map(str::to_string));
+    let topics = event
+        .topics
+        .iter()
+        .map(|t| format!("{}: {}", t.name.to_string_lossy(), type_name(&t.type_)))
+        .join(", ");
+    let data = event
+        .data
+        .fields
+        .iter()
+        .map(|f| format!("{}: {}", f.name.to_string_lossy(), type_name(&f.type_)))
+        .join(", ");
+    let data = if data.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{ {data} }}")
+    };
+    blocks.push(format!("```text\n{name}({topics}) = {data}\n```"));
+    let rows = event
+        .topics
+        .iter()
+        .map(|t| {
+            let topic = t.name.to_string_lossy();
+            [
+                format!("`{topic}`"),
+                format!("`{}`", type_name(&t.type_)),
+                cell(docs.get(&format!("{path}.{topic}"))),
+            ]
+        })
+        .collect::<Vec<_>>();
+    blocks.extend(table(["Topic", "Type", "Description"], &rows));
+    let rows = event
+        .data
+        .fields
+        .iter()
+        .map(|f| {
+            let field = f.name.to_string_lossy();
+            [
+                format!("`{field}`"),
+                format!("`{}`", type_name(&f.type_)),
+                cell(docs.get(&format!("{path}.data.{field}"))),
+            ]
+        })
+        .collect::<Vec<_>>();
+    blocks.extend(table(["Data", "Type", "Description"], &rows));
+    blocks
+}
+
+fn generate_type(spec: &ScSpecEntry, docs: &Docs) -> Vec<String> {
+    let (name, header, rows) = match spec {
+        ScSpecEntry::FunctionV0(_) => return vec![],
+        ScSpecEntry::UdtStructV0(s) => {
+            let name = s.name.to_string_lossy();
+            let rows = s
+                .fields
+                .iter()
+                .map(|f| {
+                    let field = f.name.to_string_lossy();
+                    [
+                        format!("`{field}`"),
+                        format!("`{}`", type_name(&f.type_)),
+                        cell(docs.get(&format!("{name}.{field}"))),
+                    ]
+                })
+                .collect::<Vec<_>>();
+            (name, ["Field", "Type", "Description"], rows)
+        }
+        ScSpecEntry::UdtUnionV0(u) => {
+            let name = u.name.to_string_lossy();
+            let rows = u
+                .cases
+                .iter()
+                .map(|c| {
+                    let case = c.name.to_string_lossy();
+                    [
+                        format!("`{case}`"),
+                        c.type_
+                            .as_ref()
+                            .map(|t| format!("`{}`", type_name(t)))
+                            .unwrap_or_default(),
+                        cell(docs.get(&format!("{name}.{case}"))),
+                    ]
+                })
+                .collect::<Vec<_>>();
+            (name, ["Case", "Type", "Description"], rows)
+        }
+        ScSpecEntry::UdtEnumV0(e) => {
+            let name = e.name.to_string_lossy();
+            let rows = e
+                .cases
+                .iter()
+                .map(|c| {
+                    let case = c.name.to_string_lossy();
+                    [
+                        format!("`{case}`"),
+                        c.value.to_string(),
+                        cell(docs.get(&format!("{name}.{case}"))),
+                    ]
+                })
+                .collect::<Vec<_>>();
+            (name, ["Case", "Value", "Description"], rows)
+        }
+        ScSpecEntry::UdtErrorEnumV0(e) => {
+            let name = e.name.to_string_lossy();
+            let rows = e
+                .cases
+                .iter()
+                .map(|c| {
+                    let case = c.name.to_string_lossy();
+                    [
+                        format!("`{case}`"),
+                        c.value.to_string(),
+                        cell(docs.get(&format!("{name}.{case}"))),
+                    ]
+                })
+                .collect::<Vec<_>>();
+            (name, ["Error", "Value", "Description"], rows)
+        }
+    };
+    let mut blocks = vec![format!("### `{name}`")];
+    blocks.extend(docs.get(&name).map(str::to_string));
+    blocks.extend(table(header, &rows));
+    blocks
+}
+
+/// Returns a Markdown table with the header and rows, or [None] if there are no
+/// rows.
+fn table(header: [&str; 3], rows: &[[String; 3]]) -> Option<String> {
+    if rows.is_empty() {
+        return None;
+    }
+    let mut lines = vec![
+        format!("| {} |", header.join(" | ")),
+        "| --- | --- | --- |".to_string(),
+    ];
+    lines.extend(rows.iter().map(|r| format!("| {} |", r.join(" | "))));
+    Some(lines.join("\n"))
+}
+
+/// Returns the doc string escaped for use in a table cell.
+fn cell(doc: Option<&str>) -> String {
+    doc.unwrap_or_default()
+        .replace('|', "\\|")
+        .replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::generate_from_wasm;
+
+    const EXAMPLE_WASM: &[u8] =
+        include_bytes!("../../../target/wasm32-unknown-unknown/release/test_udt.wasm");
+
+    #[test]
+    fn example() {
+        let md = generate_from_wasm(EXAMPLE_WASM).unwrap();
+        assert_eq!(
+            md,
+            r#"# Contract
+
+## Functions
+
+### `add`
+
+Adds the values of the two UDTs.
+
+* `a` - The first value.
+* `b` - The second value.
+
+```text
+add(a: UdtEnum, b: UdtEnum) -> i64
+```
+
+| Input | Type | Description |
+| --- | --- | --- |
+| `a` | `UdtEnum` | The first value. |
+| `b` | `UdtEnum` | The second value. |
+
+## Types
+
+### `UdtEnum2`
+
+| Case | Value | Description |
+| --- | --- | --- |
+| `A` | 10 |  |
+| `B` | 15 |  |
+
+### `UdtEnum`
+
+A union of the other test types.
+
+| Case | Type | Description |
+| --- | --- | --- |
+| `UdtA` |  |  |
+| `UdtB` | `UdtStruct` | A struct value. |
+| `UdtC` | `UdtEnum2` |  |
+| `UdtD` | `UdtTuple` |  |
+
+### `UdtTuple`
+
+| Field | Type | Description |
+| --- | --- | --- |
+| `0` | `i64` |  |
+| `1` | `Vec<i64>` |  |
+
+### `UdtStruct`
+
+A struct with named fields.
+
+| Field | Type | Description |
+| --- | --- | --- |
+| `a` | `i64` |  |
+| `b` | `i64` |  |
+| `c` | `Vec<i64>` | A vec of values. |
+"#,
+        );
+    }
+}
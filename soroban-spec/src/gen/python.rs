@@ -0,0 +1,189 @@
+pub mod types;
+
+use stellar_xdr::ScSpecEntry;
+
+use crate::{
+    gen::{is_event_struct, is_union_case_struct, read_wasm_file, GenerateFromFileError},
+    generic::monomorphize,
+    read::{from_wasm, FromWasmError},
+};
+
+use types::{generate_client, generate_enum, generate_error_enum, generate_struct, generate_union};
+
+/// Type definitions and conversions between Python values and
+/// `xdr.SCVal`s that the generated code depends on.
+pub const PRELUDE: &str = include_str!("python/prelude.py");
+
+pub fn generate_from_file(
+    file: &str,
+    verify_sha256: Option<&str>,
+) -> Result<String, GenerateFromFileError> {
+    // Read file.
+    let wasm = read_wasm_file(file, verify_sha256)?;
+
+    // Generate code.
+    let py = generate_from_wasm(&wasm).map_err(GenerateFromFileError::GetSpec)?;
+    Ok(py)
+}
+
+pub fn generate_from_wasm(wasm: &[u8]) -> Result<String, FromWasmError> {
+    let spec = from_wasm(wasm)?;
+    let py = generate(&spec);
+    Ok(py)
+}
+
+/// Generates a Python module containing a class for each type in the spec, and
+/// a `Client` class with a method for each function in the spec.
+pub fn generate(specs: &[ScSpecEntry]) -> String {
+    // Generic types are generated as each of their instances.
+    let specs = &monomorphize(specs);
+    let mut py = PRELUDE.to_string();
+    let mut spec_fns = Vec::new();
+    let structs = specs
+        .iter()
+        .filter_map(|s| match s {
+            ScSpecEntry::UdtStructV0(s) => Some(s),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    for s in specs {
+        match s {
+            ScSpecEntry::FunctionV0(f) => spec_fns.push(f),
+            // Structs describing the fields of union cases are generated as
+            // part of the union, and structs describing events are not types.
+            ScSpecEntry::UdtStructV0(s) if is_union_case_struct(s) || is_event_struct(s) => {}
+            ScSpecEntry::UdtStructV0(s) => py.push_str(&generate_struct(s)),
+            ScSpecEntry::UdtUnionV0(u) => py.push_str(&generate_union(u, &structs)),
+            ScSpecEntry::UdtEnumV0(e) => py.push_str(&generate_enum(e)),
+            ScSpecEntry::UdtErrorEnumV0(e) => py.push_str(&generate_error_enum(e)),
+        }
+    }
+    py.push_str(&generate_client("Client", &spec_fns));
+    py
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{generate, PRELUDE};
+
+    const EXAMPLE_WASM: &[u8] =
+        include_bytes!("../../../target/wasm32-unknown-unknown/release/test_udt.wasm");
+
+    #[test]
+    fn example() {
+        let entries = crate::read::from_wasm(EXAMPLE_WASM).unwrap();
+        let py = generate(&entries);
+        assert_eq!(
+            py.strip_prefix(PRELUDE).unwrap(),
+            r#"
+
+class UdtEnum2(IntEnum):
+    A = 10
+    B = 15
+
+    def to_scval(self) -> xdr.SCVal:
+        return u32_to_scval(self)
+
+    @staticmethod
+    def from_scval(v: xdr.SCVal) -> UdtEnum2:
+        return UdtEnum2(u32_from_scval(v))
+
+
+@dataclass
+class UdtEnum:
+    tag: str
+    value: Union[None, UdtStruct, UdtEnum2, UdtTuple] = None
+
+    def to_scval(self) -> xdr.SCVal:
+        if self.tag == "UdtA":
+            return vec_of([symbol_to_scval("UdtA")])
+        if self.tag == "UdtB":
+            return vec_of([symbol_to_scval("UdtB"), UdtStruct.to_scval(self.value)])
+        if self.tag == "UdtC":
+            return vec_of([symbol_to_scval("UdtC"), UdtEnum2.to_scval(self.value)])
+        if self.tag == "UdtD":
+            return vec_of([symbol_to_scval("UdtD"), UdtTuple.to_scval(self.value)])
+        raise ValueError(f"unknown case {self.tag} of UdtEnum")
+
+    @staticmethod
+    def from_scval(v: xdr.SCVal) -> UdtEnum:
+        items = vec_items(v)
+        tag = symbol_from_scval(items[0])
+        if tag == "UdtA":
+            return UdtEnum("UdtA")
+        if tag == "UdtB":
+            return UdtEnum("UdtB", UdtStruct.from_scval(items[1]))
+        if tag == "UdtC":
+            return UdtEnum("UdtC", UdtEnum2.from_scval(items[1]))
+        if tag == "UdtD":
+            return UdtEnum("UdtD", UdtTuple.from_scval(items[1]))
+        raise ValueError(f"unknown case {tag} of UdtEnum")
+
+
+@dataclass
+class UdtTuple:
+    _0: int
+    _1: List[int]
+
+    def to_scval(self) -> xdr.SCVal:
+        return vec_of(
+            [
+                i64_to_scval(self._0),
+                vec_to_scval(i64_to_scval)(self._1),
+            ]
+        )
+
+    @staticmethod
+    def from_scval(v: xdr.SCVal) -> UdtTuple:
+        items = vec_items(v)
+        return UdtTuple(
+            i64_from_scval(items[0]),
+            vec_from_scval(i64_from_scval)(items[1]),
+        )
+
+
+@dataclass
+class UdtStruct:
+    a: int
+    b: int
+    c: List[int]
+
+    def to_scval(self) -> xdr.SCVal:
+        return map_of(
+            [
+                (symbol_to_scval("a"), i64_to_scval(self.a)),
+                (symbol_to_scval("b"), i64_to_scval(self.b)),
+                (symbol_to_scval("c"), vec_to_scval(i64_to_scval)(self.c)),
+            ]
+        )
+
+    @staticmethod
+    def from_scval(v: xdr.SCVal) -> UdtStruct:
+        return UdtStruct(
+            a=i64_from_scval(struct_field(v, "a")),
+            b=i64_from_scval(struct_field(v, "b")),
+            c=vec_from_scval(i64_from_scval)(struct_field(v, "c")),
+        )
+
+
+class Client:
+    """Client for invoking the functions of the contract."""
+
+    def __init__(self, invoke: Invoke):
+        self.invoke = invoke
+
+    def add(self, a: UdtEnum, b: UdtEnum) -> int:
+        result = self.invoke(
+            "add",
+            [
+                UdtEnum.to_scval(a),
+                UdtEnum.to_scval(b),
+            ],
+        )
+        return i64_from_scval(result)
+"#,
+        );
+    }
+}
@@ -0,0 +1,286 @@
+# Generated from the spec of a Soroban contract.
+
+from __future__ import annotations
+
+from dataclasses import dataclass
+from enum import IntEnum
+from typing import Any, Callable, Dict, Generic, List, Optional, Set, Tuple, TypeVar, Union
+
+from stellar_sdk import xdr
+
+T = TypeVar("T")
+E = TypeVar("E")
+
+Invoke = Callable[[str, List[xdr.SCVal]], xdr.SCVal]
+"""Invokes the function of the contract with the arguments."""
+
+
+@dataclass
+class Ok(Generic[T]):
+    """The value returned by a function that returns a Result, when ok."""
+
+    value: T
+
+
+@dataclass
+class Err(Generic[E]):
+    """The value returned by a function that returns a Result, when an error."""
+
+    error: E
+
+
+def vec_of(items: List[xdr.SCVal]) -> xdr.SCVal:
+    return xdr.SCVal(
+        xdr.SCValType.SCV_OBJECT,
+        obj=xdr.SCObject(xdr.SCObjectType.SCO_VEC, vec=xdr.SCVec(items)),
+    )
+
+
+def vec_items(v: xdr.SCVal) -> List[xdr.SCVal]:
+    return v.obj.vec.sc_vec
+
+
+def scval_sort_key(v: xdr.SCVal) -> Tuple[Any, ...]:
+    """Returns a key that orders values the way the host orders the keys of maps."""
+    if v.type == xdr.SCValType.SCV_U63:
+        return (v.type.value, v.u63.int64)
+    if v.type == xdr.SCValType.SCV_U32:
+        return (v.type.value, v.u32.uint32)
+    if v.type == xdr.SCValType.SCV_I32:
+        return (v.type.value, v.i32.int32)
+    if v.type == xdr.SCValType.SCV_SYMBOL:
+        return (v.type.value, v.sym.sc_symbol)
+    if v.type == xdr.SCValType.SCV_OBJECT and v.obj is not None:
+        if v.obj.type == xdr.SCObjectType.SCO_BYTES:
+            return (v.type.value, v.obj.type.value, v.obj.bin)
+        if v.obj.type == xdr.SCObjectType.SCO_U64:
+            return (v.type.value, v.obj.type.value, v.obj.u64.uint64)
+        if v.obj.type == xdr.SCObjectType.SCO_I64:
+            return (v.type.value, v.obj.type.value, v.obj.i64.int64)
+        return (v.type.value, v.obj.type.value, v.to_xdr_bytes())
+    return (v.type.value, v.to_xdr_bytes())
+
+
+def map_of(entries: List[Tuple[xdr.SCVal, xdr.SCVal]]) -> xdr.SCVal:
+    """Returns a map of the entries, ordered by key as the host requires."""
+    entries = sorted(entries, key=lambda e: scval_sort_key(e[0]))
+    return xdr.SCVal(
+        xdr.SCValType.SCV_OBJECT,
+        obj=xdr.SCObject(
+            xdr.SCObjectType.SCO_MAP,
+            map=xdr.SCMap([xdr.SCMapEntry(key=k, val=v) for (k, v) in entries]),
+        ),
+    )
+
+
+def map_entries(v: xdr.SCVal) -> List[Tuple[xdr.SCVal, xdr.SCVal]]:
+    return [(e.key, e.val) for e in v.obj.map.sc_map]
+
+
+def struct_field(v: xdr.SCVal, name: str) -> xdr.SCVal:
+    for (k, val) in map_entries(v):
+        if symbol_from_scval(k) == name:
+            return val
+    raise ValueError(f"missing field {name}")
+
+
+def is_void(v: xdr.SCVal) -> bool:
+    return v.type == xdr.SCValType.SCV_STATIC and v.ic == xdr.SCStatic.SCS_VOID
+
+
+def void_scval() -> xdr.SCVal:
+    return xdr.SCVal(xdr.SCValType.SCV_STATIC, ic=xdr.SCStatic.SCS_VOID)
+
+
+def val_to_scval(v: xdr.SCVal) -> xdr.SCVal:
+    return v
+
+
+def val_from_scval(v: xdr.SCVal) -> xdr.SCVal:
+    return v
+
+
+def bool_to_scval(v: bool) -> xdr.SCVal:
+    return xdr.SCVal(
+        xdr.SCValType.SCV_STATIC,
+        ic=xdr.SCStatic.SCS_TRUE if v else xdr.SCStatic.SCS_FALSE,
+    )
+
+
+def bool_from_scval(v: xdr.SCVal) -> bool:
+    return v.ic == xdr.SCStatic.SCS_TRUE
+
+
+def u32_to_scval(v: int) -> xdr.SCVal:
+    return xdr.SCVal(xdr.SCValType.SCV_U32, u32=xdr.Uint32(v))
+
+
+def u32_from_scval(v: xdr.SCVal) -> int:
+    return v.u32.uint32
+
+
+def i32_to_scval(v: int) -> xdr.SCVal:
+    return xdr.SCVal(xdr.SCValType.SCV_I32, i32=xdr.Int32(v))
+
+
+def i32_from_scval(v: xdr.SCVal) -> int:
+    return v.i32.int32
+
+
+def u64_to_scval(v: int) -> xdr.SCVal:
+    return xdr.SCVal(
+        xdr.SCValType.SCV_OBJECT,
+        obj=xdr.SCObject(xdr.SCObjectType.SCO_U64, u64=xdr.Uint64(v)),
+    )
+
+
+def u64_from_scval(v: xdr.SCVal) -> int:
+    return v.obj.u64.uint64
+
+
+def i64_to_scval(v: int) -> xdr.SCVal:
+    if v >= 0:
+        return xdr.SCVal(xdr.SCValType.SCV_U63, u63=xdr.Int64(v))
+    return xdr.SCVal(
+        xdr.SCValType.SCV_OBJECT,
+        obj=xdr.SCObject(xdr.SCObjectType.SCO_I64, i64=xdr.Int64(v)),
+    )
+
+
+def i64_from_scval(v: xdr.SCVal) -> int:
+    if v.type == xdr.SCValType.SCV_U63:
+        return v.u63.int64
+    return v.obj.i64.int64
+
+
+def big_int_to_scval(v: int) -> xdr.SCVal:
+    if v == 0:
+        big_int = xdr.SCBigInt(xdr.SCNumSign.ZERO)
+    else:
+        magnitude = abs(v).to_bytes((abs(v).bit_length() + 7) // 8, "big")
+        sign = xdr.SCNumSign.NEGATIVE if v < 0 else xdr.SCNumSign.POSITIVE
+        big_int = xdr.SCBigInt(sign, magnitude=magnitude)
+    return xdr.SCVal(
+        xdr.SCValType.SCV_OBJECT,
+        obj=xdr.SCObject(xdr.SCObjectType.SCO_BIG_INT, big_int=big_int),
+    )
+
+
+def big_int_from_scval(v: xdr.SCVal) -> int:
+    big_int = v.obj.big_int
+    if big_int.sign == xdr.SCNumSign.ZERO:
+        return 0
+    magnitude = int.from_bytes(big_int.magnitude, "big")
+    return -magnitude if big_int.sign == xdr.SCNumSign.NEGATIVE else magnitude
+
+
+def symbol_to_scval(v: str) -> xdr.SCVal:
+    return xdr.SCVal(xdr.SCValType.SCV_SYMBOL, sym=xdr.SCSymbol(v.encode()))
+
+
+def symbol_from_scval(v: xdr.SCVal) -> str:
+    return v.sym.sc_symbol.decode()
+
+
+def bitset_to_scval(v: int) -> xdr.SCVal:
+    return xdr.SCVal(xdr.SCValType.SCV_BITSET, bits=xdr.Uint64(v))
+
+
+def bitset_from_scval(v: xdr.SCVal) -> int:
+    return v.bits.uint64
+
+
+def status_to_scval(v: xdr.SCStatus) -> xdr.SCVal:
+    return xdr.SCVal(xdr.SCValType.SCV_STATUS, status=v)
+
+
+def status_from_scval(v: xdr.SCVal) -> xdr.SCStatus:
+    return v.status
+
+
+def contract_error_to_scval(v: int) -> xdr.SCVal:
+    return xdr.SCVal(
+        xdr.SCValType.SCV_STATUS,
+        status=xdr.SCStatus(
+            xdr.SCStatusType.SST_CONTRACT_ERROR, contract_code=xdr.Uint32(v)
+        ),
+    )
+
+
+def contract_error_from_scval(v: xdr.SCVal) -> int:
+    if v.type == xdr.SCValType.SCV_STATUS:
+        return v.status.contract_code.uint32
+    return v.u32.uint32
+
+
+def bytes_to_scval(v: bytes) -> xdr.SCVal:
+    return xdr.SCVal(
+        xdr.SCValType.SCV_OBJECT,
+        obj=xdr.SCObject(xdr.SCObjectType.SCO_BYTES, bin=v),
+    )
+
+
+def bytes_from_scval(v: xdr.SCVal) -> bytes:
+    return v.obj.bin
+
+
+def vec_to_scval(f: Callable[[T], xdr.SCVal]) -> Callable[[List[T]], xdr.SCVal]:
+    return lambda v: vec_of([f(e) for e in v])
+
+
+def vec_from_scval(f: Callable[[xdr.SCVal], T]) -> Callable[[xdr.SCVal], List[T]]:
+    return lambda v: [f(e) for e in vec_items(v)]
+
+
+def map_to_scval(
+    k: Callable[[Any], xdr.SCVal], val: Callable[[Any], xdr.SCVal]
+) -> Callable[[Dict[Any, Any]], xdr.SCVal]:
+    return lambda v: map_of([(k(ek), val(ev)) for (ek, ev) in v.items()])
+
+
+def map_from_scval(
+    k: Callable[[xdr.SCVal], Any], val: Callable[[xdr.SCVal], Any]
+) -> Callable[[xdr.SCVal], Dict[Any, Any]]:
+    return lambda v: {k(ek): val(ev) for (ek, ev) in map_entries(v)}
+
+
+def set_to_scval(f: Callable[[T], xdr.SCVal]) -> Callable[[Set[T]], xdr.SCVal]:
+    return lambda v: map_of([(f(e), void_scval()) for e in v])
+
+
+def set_from_scval(f: Callable[[xdr.SCVal], T]) -> Callable[[xdr.SCVal], Set[T]]:
+    return lambda v: {f(e) for (e, _) in map_entries(v)}
+
+
+def option_to_scval(
+    f: Callable[[T], xdr.SCVal]
+) -> Callable[[Optional[T]], xdr.SCVal]:
+    return lambda v: void_scval() if v is None else f(v)
+
+
+def option_from_scval(
+    f: Callable[[xdr.SCVal], T]
+) -> Callable[[xdr.SCVal], Optional[T]]:
+    return lambda v: None if is_void(v) else f(v)
+
+
+def result_to_scval(
+    ok: Callable[[Any], xdr.SCVal], err: Callable[[Any], xdr.SCVal]
+) -> Callable[[Union[Ok, Err]], xdr.SCVal]:
+    return lambda v: ok(v.value) if isinstance(v, Ok) else err(v.error)
+
+
+def result_from_scval(
+    ok: Callable[[xdr.SCVal], Any], err: Callable[[xdr.SCVal], Any]
+) -> Callable[[xdr.SCVal], Union[Ok, Err]]:
+    return lambda v: (
+        Err(err(v)) if v.type == xdr.SCValType.SCV_STATUS else Ok(ok(v))
+    )
+
+
+def tuple_to_scval(*fs: Callable[[Any], xdr.SCVal]) -> Callable[[tuple], xdr.SCVal]:
+    return lambda v: vec_of([f(e) for (f, e) in zip(fs, v)])
+
+
+def tuple_from_scval(*fs: Callable[[xdr.SCVal], Any]) -> Callable[[xdr.SCVal], tuple]:
+    return lambda v: tuple(f(e) for (f, e) in zip(fs, vec_items(v)))
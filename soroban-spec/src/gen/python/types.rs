@@ -0,0 +1,513 @@
+use itertools::Itertools;
+use stellar_xdr::{
+    ScSpecFunctionV0, ScSpecTypeDef, ScSpecUdtEnumV0, ScSpecUdtErrorEnumV0, ScSpecUdtStructV0,
+    ScSpecUdtUnionV0,
+};
+
+use crate::gen::{struct_alias, union_case_struct};
+
+/// Keywords of Python that cannot be used as identifiers.
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// Returns the name as a Python identifier, with a trailing underscore if the
+/// name is a keyword.
+fn ident(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_string()
+    }
+}
+
+/// Formats the items as a multi-line list literal, with each item on its own
+/// line at the indent.
+fn list(items: &[String], indent: &str) -> String {
+    if items.is_empty() {
+        return "[]".to_string();
+    }
+    let mut s = "[\n".to_string();
+    for item in items {
+        s.push_str(&format!("{indent}    {item},\n"));
+    }
+    s.push_str(&format!("{indent}]"));
+    s
+}
+
+pub fn generate_struct(spec: &ScSpecUdtStructV0) -> String {
+    let name = spec.name.to_string_lossy();
+    // Structs describing the fields of union cases are nested in the class of
+    // the union, so are declared with the name of the case, and referred to
+    // with the name of the union and the case, e.g. `State.Auction`.
+    let class = name.rsplit('.').next().unwrap_or_default();
+    if let Some(t) = struct_alias(spec) {
+        // Transparent structs are a dataclass wrapping the value of their
+        // field, stored as the value.
+        format!(
+            r#"
+
+@dataclass
+class {class}:
+    value: {}
+
+    def to_scval(self) -> xdr.SCVal:
+        return {}(self.value)
+
+    @staticmethod
+    def from_scval(v: xdr.SCVal) -> {name}:
+        return {name}({}(v))
+"#,
+            generate_type_ident(t),
+            generate_to_scval(t),
+            generate_from_scval(t),
+        )
+    } else if spec.fields.is_empty() {
+        // Unit structs are a dataclass without fields, stored as void.
+        format!(
+            r#"
+
+@dataclass
+class {class}:
+
+    def to_scval(self) -> xdr.SCVal:
+        return void_scval()
+
+    @staticmethod
+    def from_scval(v: xdr.SCVal) -> {name}:
+        return {name}()
+"#
+        )
+    } else if spec
+        .fields
+        .iter()
+        .all(|f| f.name.to_string_lossy().parse::<usize>().is_ok())
+    {
+        // If all fields are numeric, generate a dataclass with fields named
+        // after their position, stored as a vec of the values.
+        let fields = spec
+            .fields
+            .iter()
+            .map(|f| {
+                format!(
+                    "    _{}: {}\n",
+                    f.name.to_string_lossy(),
+                    generate_type_ident(&f.type_)
+                )
+            })
+            .join("");
+        let to = spec
+            .fields
+            .iter()
+            .map(|f| {
+                format!(
+                    "{}(self._{})",
+                    generate_to_scval(&f.type_),
+                    f.name.to_string_lossy()
+                )
+            })
+            .collect::<Vec<_>>();
+        let from = spec
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                format!(
+                    "            {}(items[{i}]),\n",
+                    generate_from_scval(&f.type_)
+                )
+            })
+            .join("");
+        format!(
+            r#"
+
+@dataclass
+class {class}:
+{fields}
+    def to_scval(self) -> xdr.SCVal:
+        return vec_of(
+            {}
+        )
+
+    @staticmethod
+    def from_scval(v: xdr.SCVal) -> {name}:
+        items = vec_items(v)
+        return {name}(
+{from}        )
+"#,
+            list(&to, "            "),
+        )
+    } else {
+        // Otherwise generate a dataclass with named fields, stored as a map
+        // from field name to value.
+        let fields = spec
+            .fields
+            .iter()
+            .map(|f| {
+                format!(
+                    "    {}: {}\n",
+                    ident(&f.name.to_string_lossy()),
+                    generate_type_ident(&f.type_)
+                )
+            })
+            .join("");
+        // Maps in the host are ordered by key, so the entries are generated
+        // ordered by field name.
+        let to = spec
+            .fields
+            .iter()
+            .sorted_by_key(|f| f.name.to_string_lossy())
+            .map(|f| {
+                let field = f.name.to_string_lossy();
+                format!(
+                    "(symbol_to_scval(\"{field}\"), {}(self.{}))",
+                    generate_to_scval(&f.type_),
+                    ident(&field)
+                )
+            })
+            .collect::<Vec<_>>();
+        let from = spec
+            .fields
+            .iter()
+            .map(|f| {
+                let field = f.name.to_string_lossy();
+                format!(
+                    "            {}={}(struct_field(v, \"{field}\")),\n",
+                    ident(&field),
+                    generate_from_scval(&f.type_)
+                )
+            })
+            .join("");
+        format!(
+            r#"
+
+@dataclass
+class {class}:
+{fields}
+    def to_scval(self) -> xdr.SCVal:
+        return map_of(
+            {}
+        )
+
+    @staticmethod
+    def from_scval(v: xdr.SCVal) -> {name}:
+        return {name}(
+{from}        )
+"#,
+            list(&to, "            "),
+        )
+    }
+}
+
+/// Generates a dataclass for the union, with the structs describing the fields
+/// of cases that are variants with named fields nested within it.
+pub fn generate_union(spec: &ScSpecUdtUnionV0, structs: &[&ScSpecUdtStructV0]) -> String {
+    let name = spec.name.to_string_lossy();
+    let value_types = spec
+        .cases
+        .iter()
+        .filter_map(|c| c.type_.as_ref())
+        .map(generate_type_ident)
+        .unique()
+        .collect::<Vec<_>>();
+    let value_type = if value_types.is_empty() {
+        "None".to_string()
+    } else {
+        format!("Union[None, {}]", value_types.join(", "))
+    };
+    let to = spec
+        .cases
+        .iter()
+        .map(|c| {
+            let case = c.name.to_string_lossy();
+            let value = match &c.type_ {
+                Some(t) => format!(", {}(self.value)", generate_to_scval(t)),
+                None => String::new(),
+            };
+            format!(
+                "        if self.tag == \"{case}\":\n            return vec_of([symbol_to_scval(\"{case}\"){value}])\n"
+            )
+        })
+        .join("");
+    let from = spec
+        .cases
+        .iter()
+        .map(|c| {
+            let case = c.name.to_string_lossy();
+            let value = match &c.type_ {
+                Some(t) => format!(", {}(items[1])", generate_from_scval(t)),
+                None => String::new(),
+            };
+            format!("        if tag == \"{case}\":\n            return {name}(\"{case}\"{value})\n")
+        })
+        .join("");
+    let nested = spec
+        .cases
+        .iter()
+        .filter_map(|c| union_case_struct(spec, c, structs))
+        .map(|s| {
+            generate_struct(s)
+                .lines()
+                .map(|l| {
+                    if l.is_empty() {
+                        "\n".to_string()
+                    } else {
+                        format!("    {l}\n")
+                    }
+                })
+                .join("")
+        })
+        .join("");
+    format!(
+        r#"
+
+@dataclass
+class {name}:
+    tag: str
+    value: {value_type} = None
+
+    def to_scval(self) -> xdr.SCVal:
+{to}        raise ValueError(f"unknown case {{self.tag}} of {name}")
+
+    @staticmethod
+    def from_scval(v: xdr.SCVal) -> {name}:
+        items = vec_items(v)
+        tag = symbol_from_scval(items[0])
+{from}        raise ValueError(f"unknown case {{tag}} of {name}")
+{nested}"#
+    )
+}
+
+pub fn generate_enum(spec: &ScSpecUdtEnumV0) -> String {
+    let name = spec.name.to_string_lossy();
+    let cases = spec
+        .cases
+        .iter()
+        .map(|c| format!("    {} = {}\n", ident(&c.name.to_string_lossy()), c.value))
+        .join("");
+    format!(
+        r#"
+
+class {name}(IntEnum):
+{cases}
+    def to_scval(self) -> xdr.SCVal:
+        return u32_to_scval(self)
+
+    @staticmethod
+    def from_scval(v: xdr.SCVal) -> {name}:
+        return {name}(u32_from_scval(v))
+"#
+    )
+}
+
+pub fn generate_error_enum(spec: &ScSpecUdtErrorEnumV0) -> String {
+    let name = spec.name.to_string_lossy();
+    let cases = spec
+        .cases
+        .iter()
+        .map(|c| format!("    {} = {}\n", ident(&c.name.to_string_lossy()), c.value))
+        .join("");
+    format!(
+        r#"
+
+class {name}(IntEnum):
+{cases}
+    def to_scval(self) -> xdr.SCVal:
+        return contract_error_to_scval(self)
+
+    @staticmethod
+    def from_scval(v: xdr.SCVal) -> {name}:
+        return {name}(contract_error_from_scval(v))
+"#
+    )
+}
+
+pub fn generate_client(name: &str, specs: &[&ScSpecFunctionV0]) -> String {
+    let fns = specs
+        .iter()
+        .map(|s| {
+            let fn_name = ident(&s.name.to_string_lossy());
+            let params = s
+                .inputs
+                .iter()
+                .map(|i| {
+                    format!(
+                        ", {}: {}",
+                        ident(&i.name.to_string_lossy()),
+                        generate_type_ident(&i.type_)
+                    )
+                })
+                .join("");
+            let args = s
+                .inputs
+                .iter()
+                .map(|i| {
+                    format!(
+                        "{}({})",
+                        generate_to_scval(&i.type_),
+                        ident(&i.name.to_string_lossy())
+                    )
+                })
+                .collect::<Vec<_>>();
+            let invoke = format!(
+                "self.invoke(\n            \"{}\",\n            {},\n        )",
+                s.name.to_string_lossy(),
+                list(&args, "            "),
+            );
+            match s.outputs.first() {
+                Some(t) => format!(
+                    r#"
+    def {fn_name}(self{params}) -> {}:
+        result = {invoke}
+        return {}(result)
+"#,
+                    generate_type_ident(t),
+                    generate_from_scval(t),
+                ),
+                None => format!(
+                    r#"
+    def {fn_name}(self{params}) -> None:
+        {invoke}
+"#
+                ),
+            }
+        })
+        .join("");
+    format!(
+        r#"
+
+class {name}:
+    """Client for invoking the functions of the contract."""
+
+    def __init__(self, invoke: Invoke):
+        self.invoke = invoke
+{fns}"#
+    )
+}
+
+/// Returns the Python type that values of the spec type are represented with.
+pub fn generate_type_ident(spec: &ScSpecTypeDef) -> String {
+    match spec {
+        ScSpecTypeDef::Val => "xdr.SCVal".to_string(),
+        ScSpecTypeDef::U64 => "int".to_string(),
+        ScSpecTypeDef::I64 => "int".to_string(),
+        ScSpecTypeDef::U32 => "int".to_string(),
+        ScSpecTypeDef::I32 => "int".to_string(),
+        ScSpecTypeDef::Bool => "bool".to_string(),
+        ScSpecTypeDef::Symbol => "str".to_string(),
+        ScSpecTypeDef::Bitset => "int".to_string(),
+        ScSpecTypeDef::Status => "xdr.SCStatus".to_string(),
+        ScSpecTypeDef::Bytes => "bytes".to_string(),
+        ScSpecTypeDef::BigInt => "int".to_string(),
+        ScSpecTypeDef::Invoker => "xdr.SCVal".to_string(),
+        ScSpecTypeDef::AccountId => "xdr.SCVal".to_string(),
+        ScSpecTypeDef::Option(o) => format!("Optional[{}]", generate_type_ident(&o.value_type)),
+        ScSpecTypeDef::Result(r) => format!(
+            "Union[Ok[{}], Err[{}]]",
+            generate_type_ident(&r.ok_type),
+            generate_type_ident(&r.error_type)
+        ),
+        ScSpecTypeDef::Vec(v) => format!("List[{}]", generate_type_ident(&v.element_type)),
+        ScSpecTypeDef::Map(m) => format!(
+            "Dict[{}, {}]",
+            generate_type_ident(&m.key_type),
+            generate_type_ident(&m.value_type)
+        ),
+        ScSpecTypeDef::Set(s) => format!("Set[{}]", generate_type_ident(&s.element_type)),
+        ScSpecTypeDef::Tuple(t) => format!(
+            "Tuple[{}]",
+            t.value_types.iter().map(generate_type_ident).join(", ")
+        ),
+        ScSpecTypeDef::BytesN(_) => "bytes".to_string(),
+        ScSpecTypeDef::Udt(u) => u.name.to_string_lossy(),
+    }
+}
+
+/// Returns an expression for a function that converts values of the spec type
+/// into `xdr.SCVal`s.
+pub fn generate_to_scval(spec: &ScSpecTypeDef) -> String {
+    match spec {
+        ScSpecTypeDef::Val => "val_to_scval".to_string(),
+        ScSpecTypeDef::U64 => "u64_to_scval".to_string(),
+        ScSpecTypeDef::I64 => "i64_to_scval".to_string(),
+        ScSpecTypeDef::U32 => "u32_to_scval".to_string(),
+        ScSpecTypeDef::I32 => "i32_to_scval".to_string(),
+        ScSpecTypeDef::Bool => "bool_to_scval".to_string(),
+        ScSpecTypeDef::Symbol => "symbol_to_scval".to_string(),
+        ScSpecTypeDef::Bitset => "bitset_to_scval".to_string(),
+        ScSpecTypeDef::Status => "status_to_scval".to_string(),
+        ScSpecTypeDef::Bytes => "bytes_to_scval".to_string(),
+        ScSpecTypeDef::BigInt => "big_int_to_scval".to_string(),
+        ScSpecTypeDef::Invoker => "val_to_scval".to_string(),
+        ScSpecTypeDef::AccountId => "val_to_scval".to_string(),
+        ScSpecTypeDef::Option(o) => {
+            format!("option_to_scval({})", generate_to_scval(&o.value_type))
+        }
+        ScSpecTypeDef::Result(r) => format!(
+            "result_to_scval({}, {})",
+            generate_to_scval(&r.ok_type),
+            generate_to_scval(&r.error_type)
+        ),
+        ScSpecTypeDef::Vec(v) => format!("vec_to_scval({})", generate_to_scval(&v.element_type)),
+        ScSpecTypeDef::Map(m) => format!(
+            "map_to_scval({}, {})",
+            generate_to_scval(&m.key_type),
+            generate_to_scval(&m.value_type)
+        ),
+        ScSpecTypeDef::Set(s) => format!("set_to_scval({})", generate_to_scval(&s.element_type)),
+        ScSpecTypeDef::Tuple(t) => format!(
+            "tuple_to_scval({})",
+            t.value_types.iter().map(generate_to_scval).join(", ")
+        ),
+        ScSpecTypeDef::BytesN(_) => "bytes_to_scval".to_string(),
+        ScSpecTypeDef::Udt(u) => format!("{}.to_scval", u.name.to_string_lossy()),
+    }
+}
+
+/// Returns an expression for a function that converts `xdr.SCVal`s into values
+/// of the spec type.
+pub fn generate_from_scval(spec: &ScSpecTypeDef) -> String {
+    match spec {
+        ScSpecTypeDef::Val => "val_from_scval".to_string(),
+        ScSpecTypeDef::U64 => "u64_from_scval".to_string(),
+        ScSpecTypeDef::I64 => "i64_from_scval".to_string(),
+        ScSpecTypeDef::U32 => "u32_from_scval".to_string(),
+        ScSpecTypeDef::I32 => "i32_from_scval".to_string(),
+        ScSpecTypeDef::Bool => "bool_from_scval".to_string(),
+        ScSpecTypeDef::Symbol => "symbol_from_scval".to_string(),
+        ScSpecTypeDef::Bitset => "bitset_from_scval".to_string(),
+        ScSpecTypeDef::Status => "status_from_scval".to_string(),
+        ScSpecTypeDef::Bytes => "bytes_from_scval".to_string(),
+        ScSpecTypeDef::BigInt => "big_int_from_scval".to_string(),
+        ScSpecTypeDef::Invoker => "val_from_scval".to_string(),
+        ScSpecTypeDef::AccountId => "val_from_scval".to_string(),
+        ScSpecTypeDef::Option(o) => {
+            format!("option_from_scval({})", generate_from_scval(&o.value_type))
+        }
+        ScSpecTypeDef::Result(r) => format!(
+            "result_from_scval({}, {})",
+            generate_from_scval(&r.ok_type),
+            generate_from_scval(&r.error_type)
+        ),
+        ScSpecTypeDef::Vec(v) => {
+            format!("vec_from_scval({})", generate_from_scval(&v.element_type))
+        }
+        ScSpecTypeDef::Map(m) => format!(
+            "map_from_scval({}, {})",
+            generate_from_scval(&m.key_type),
+            generate_from_scval(&m.value_type)
+        ),
+        ScSpecTypeDef::Set(s) => {
+            format!("set_from_scval({})", generate_from_scval(&s.element_type))
+        }
+        ScSpecTypeDef::Tuple(t) => format!(
+            "tuple_from_scval({})",
+            t.value_types.iter().map(generate_from_scval).join(", ")
+        ),
+        ScSpecTypeDef::BytesN(_) => "bytes_from_scval".to_string(),
+        ScSpecTypeDef::Udt(u) => format!("{}.from_scval", u.name.to_string_lossy()),
+    }
+}
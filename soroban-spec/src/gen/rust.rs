@@ -1,30 +1,22 @@
 pub mod r#trait;
 pub mod types;
 
-use std::{fs, io};
+use std::fs;
 
 use proc_macro2::TokenStream;
 use quote::quote;
-use sha2::{Digest, Sha256};
 use stellar_xdr::{self, ScSpecEntry};
 use syn::Error;
 
-use crate::read::{from_wasm, FromWasmError};
+use crate::{
+    gen::{is_event_struct, is_union_case_struct, wasm_sha256, GenerateFromFileError},
+    generic::monomorphize,
+    read::from_wasm,
+    validate::validate,
+};
 
 use types::{generate_enum, generate_error_enum, generate_struct, generate_union};
 
-#[derive(thiserror::Error, Debug)]
-pub enum GenerateFromFileError {
-    #[error("reading file: {0}")]
-    Io(io::Error),
-    #[error("sha256 does not match, expected: {expected}")]
-    VerifySha256 { expected: String },
-    #[error("parsing contract spec: {0}")]
-    Parse(stellar_xdr::Error),
-    #[error("getting contract spec: {0}")]
-    GetSpec(FromWasmError),
-}
-
 pub fn generate_from_file(
     file: &str,
     verify_sha256: Option<&str>,
@@ -42,20 +34,16 @@ pub fn generate_from_wasm(
     file: &str,
     verify_sha256: Option<&str>,
 ) -> Result<TokenStream, GenerateFromFileError> {
-    let sha256 = Sha256::digest(&wasm);
-    let sha256 = format!("{:x}", sha256);
-    if let Some(verify_sha256) = verify_sha256 {
-        if verify_sha256 != sha256 {
-            return Err(GenerateFromFileError::VerifySha256 { expected: sha256 });
-        }
-    }
-
+    let sha256 = wasm_sha256(wasm, verify_sha256)?;
     let spec = from_wasm(wasm).map_err(GenerateFromFileError::GetSpec)?;
+    validate(&spec).map_err(GenerateFromFileError::Validate)?;
     let code = generate(&spec, file, &sha256);
     Ok(code)
 }
 
 pub fn generate(specs: &[ScSpecEntry], file: &str, sha256: &str) -> TokenStream {
+    // Generic types are generated as each of their instances.
+    let specs = &monomorphize(specs);
     let mut spec_fns = Vec::new();
     let mut spec_structs = Vec::new();
     let mut spec_unions = Vec::new();
@@ -74,8 +62,13 @@ pub fn generate(specs: &[ScSpecEntry], file: &str, sha256: &str) -> TokenStream
     let trait_name = "Contract";
 
     let trait_ = r#trait::generate_trait(trait_name, &spec_fns);
-    let structs = spec_structs.iter().map(|s| generate_struct(s));
-    let unions = spec_unions.iter().map(|s| generate_union(s));
+    // Structs describing the fields of union cases are generated as part of
+    // the union, and structs describing events are not types.
+    let structs = spec_structs
+        .iter()
+        .filter(|s| !is_union_case_struct(s) && !is_event_struct(s))
+        .map(|s| generate_struct(s));
+    let unions = spec_unions.iter().map(|s| generate_union(s, &spec_structs));
     let enums = spec_enums.iter().map(|s| generate_enum(s));
     let error_enums = spec_error_enums.iter().map(|s| generate_error_enum(s));
 
@@ -4,6 +4,8 @@ use stellar_xdr::{
     ScSpecTypeDef, ScSpecUdtEnumV0, ScSpecUdtErrorEnumV0, ScSpecUdtStructV0, ScSpecUdtUnionV0,
 };
 
+use crate::gen::{struct_alias, union_case_struct};
+
 // TODO: Replace the unwrap()s in this code with returning Result.
 // TODO: Create Idents in a way that we can get a Result back and return it too
 // because at the moment the format_ident! calls can panic if the inputs do not
@@ -19,6 +21,21 @@ pub fn generate_struct(spec: &ScSpecUdtStructV0) -> TokenStream {
         quote! {
             type #ident = ::#lib_ident::#ident;
         }
+    } else if let Some(t) = struct_alias(spec) {
+        // If the struct is an alias, generate a transparent newtype.
+        let f_type = generate_type_ident(t);
+        quote! {
+            #[soroban_sdk::contracttype(transparent, export = false)]
+            #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+            pub struct #ident(pub #f_type);
+        }
+    } else if spec.fields.is_empty() {
+        // If there are no fields, generate a unit struct.
+        quote! {
+            #[soroban_sdk::contracttype(export = false)]
+            #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+            pub struct #ident;
+        }
     } else if spec
         .fields
         .iter()
@@ -50,8 +67,9 @@ pub fn generate_struct(spec: &ScSpecUdtStructV0) -> TokenStream {
 }
 
 /// Constructs a token stream containing a single enum that mirrors the union
-/// spec.
-pub fn generate_union(spec: &ScSpecUdtUnionV0) -> TokenStream {
+/// spec. The structs are used to generate the fields of cases that are variants
+/// with named fields.
+pub fn generate_union(spec: &ScSpecUdtUnionV0, structs: &[&ScSpecUdtStructV0]) -> TokenStream {
     let ident = format_ident!("{}", spec.name.to_string().unwrap());
     if spec.lib.len() > 0 {
         let lib_ident = format_ident!("{}", spec.lib.to_string_lossy());
@@ -61,11 +79,27 @@ pub fn generate_union(spec: &ScSpecUdtUnionV0) -> TokenStream {
     } else {
         let variants = spec.cases.iter().map(|c| {
             let v_ident = format_ident!("{}", c.name.to_string().unwrap());
-            let v_type = c
-                .type_
-                .as_ref()
-                .map(generate_type_ident)
-                .map_or_else(|| quote! {}, |t| quote! { (#t) });
+            if let Some(s) = union_case_struct(spec, c, structs) {
+                let fields = s.fields.iter().map(|f| {
+                    let f_ident = format_ident!("{}", f.name.to_string().unwrap());
+                    let f_type = generate_type_ident(&f.type_);
+                    quote! { #f_ident: #f_type }
+                });
+                return quote! { #v_ident { #(#fields,)* } };
+            }
+            let v_type = match &c.type_ {
+                None => quote! {},
+                // Cases with a tuple of multiple values are variants with a
+                // field for each value, as they are encoded the same.
+                Some(ScSpecTypeDef::Tuple(t)) if t.value_types.len() > 1 => {
+                    let v_types = t.value_types.iter().map(generate_type_ident);
+                    quote! { (#(#v_types),*) }
+                }
+                Some(t) => {
+                    let v_type = generate_type_ident(t);
+                    quote! { (#v_type) }
+                }
+            };
             quote! { #v_ident #v_type }
         });
         quote! {
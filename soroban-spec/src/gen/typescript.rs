@@ -0,0 +1,184 @@
+pub mod types;
+
+use stellar_xdr::ScSpecEntry;
+
+use crate::{
+    gen::{is_event_struct, is_union_case_struct, read_wasm_file, GenerateFromFileError},
+    generic::monomorphize,
+    read::{from_wasm, FromWasmError},
+};
+
+use types::{generate_client, generate_enum, generate_error_enum, generate_struct, generate_union};
+
+/// Type definitions and conversions between TypeScript values and
+/// `xdr.ScVal`s that the generated code depends on.
+pub const PRELUDE: &str = include_str!("typescript/prelude.ts");
+
+pub fn generate_from_file(
+    file: &str,
+    verify_sha256: Option<&str>,
+) -> Result<String, GenerateFromFileError> {
+    // Read file.
+    let wasm = read_wasm_file(file, verify_sha256)?;
+
+    // Generate code.
+    let ts = generate_from_wasm(&wasm).map_err(GenerateFromFileError::GetSpec)?;
+    Ok(ts)
+}
+
+pub fn generate_from_wasm(wasm: &[u8]) -> Result<String, FromWasmError> {
+    let spec = from_wasm(wasm)?;
+    let ts = generate(&spec);
+    Ok(ts)
+}
+
+/// Generates a TypeScript module containing a type definition for each type in
+/// the spec, and a `Client` class with a method for each function in the spec.
+pub fn generate(specs: &[ScSpecEntry]) -> String {
+    // Generic types are generated as each of their instances.
+    let specs = &monomorphize(specs);
+    let mut ts = PRELUDE.to_string();
+    let mut spec_fns = Vec::new();
+    let structs = specs
+        .iter()
+        .filter_map(|s| match s {
+            ScSpecEntry::UdtStructV0(s) => Some(s),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    for s in specs {
+        match s {
+            ScSpecEntry::FunctionV0(f) => spec_fns.push(f),
+            // Structs describing the fields of union cases are generated as
+            // part of the union, and structs describing events are not types.
+            ScSpecEntry::UdtStructV0(s) if is_union_case_struct(s) || is_event_struct(s) => {}
+            ScSpecEntry::UdtStructV0(s) => ts.push_str(&generate_struct(s)),
+            ScSpecEntry::UdtUnionV0(u) => ts.push_str(&generate_union(u, &structs)),
+            ScSpecEntry::UdtEnumV0(e) => ts.push_str(&generate_enum(e)),
+            ScSpecEntry::UdtErrorEnumV0(e) => ts.push_str(&generate_error_enum(e)),
+        }
+    }
+    ts.push_str(&generate_client("Client", &spec_fns));
+    ts
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{generate, PRELUDE};
+
+    const EXAMPLE_WASM: &[u8] =
+        include_bytes!("../../../target/wasm32-unknown-unknown/release/test_udt.wasm");
+
+    #[test]
+    fn example() {
+        let entries = crate::read::from_wasm(EXAMPLE_WASM).unwrap();
+        let ts = generate(&entries);
+        assert_eq!(
+            ts.strip_prefix(PRELUDE).unwrap(),
+            r#"
+export enum UdtEnum2 {
+  A = 10,
+  B = 15,
+}
+
+function UdtEnum2ToScVal(v: UdtEnum2): xdr.ScVal {
+  return u32ToScVal(v);
+}
+
+function UdtEnum2FromScVal(v: xdr.ScVal): UdtEnum2 {
+  return u32FromScVal(v);
+}
+
+export type UdtEnum =
+  | { tag: "UdtA" }
+  | { tag: "UdtB"; value: UdtStruct }
+  | { tag: "UdtC"; value: UdtEnum2 }
+  | { tag: "UdtD"; value: UdtTuple };
+
+function UdtEnumToScVal(v: UdtEnum): xdr.ScVal {
+  switch (v.tag) {
+    case "UdtA":
+      return vecOf([symbolToScVal("UdtA")]);
+    case "UdtB":
+      return vecOf([symbolToScVal("UdtB"), UdtStructToScVal(v.value)]);
+    case "UdtC":
+      return vecOf([symbolToScVal("UdtC"), UdtEnum2ToScVal(v.value)]);
+    case "UdtD":
+      return vecOf([symbolToScVal("UdtD"), UdtTupleToScVal(v.value)]);
+  }
+}
+
+function UdtEnumFromScVal(v: xdr.ScVal): UdtEnum {
+  const items = vecItems(v);
+  const tag = symbolFromScVal(items[0]);
+  switch (tag) {
+    case "UdtA":
+      return { tag: "UdtA" };
+    case "UdtB":
+      return { tag: "UdtB", value: UdtStructFromScVal(items[1]) };
+    case "UdtC":
+      return { tag: "UdtC", value: UdtEnum2FromScVal(items[1]) };
+    case "UdtD":
+      return { tag: "UdtD", value: UdtTupleFromScVal(items[1]) };
+    default:
+      throw new Error(`unknown case ${tag} of UdtEnum`);
+  }
+}
+
+export type UdtTuple = [bigint, Array<bigint>];
+
+function UdtTupleToScVal(v: UdtTuple): xdr.ScVal {
+  return vecOf([
+    i64ToScVal(v[0]),
+    vecToScVal(i64ToScVal)(v[1]),
+  ]);
+}
+
+function UdtTupleFromScVal(v: xdr.ScVal): UdtTuple {
+  const items = vecItems(v);
+  return [
+    i64FromScVal(items[0]),
+    vecFromScVal(i64FromScVal)(items[1]),
+  ];
+}
+
+export interface UdtStruct {
+  a: bigint;
+  b: bigint;
+  c: Array<bigint>;
+}
+
+function UdtStructToScVal(v: UdtStruct): xdr.ScVal {
+  return mapOf([
+    [symbolToScVal("a"), i64ToScVal(v.a)],
+    [symbolToScVal("b"), i64ToScVal(v.b)],
+    [symbolToScVal("c"), vecToScVal(i64ToScVal)(v.c)],
+  ]);
+}
+
+function UdtStructFromScVal(v: xdr.ScVal): UdtStruct {
+  return {
+    a: i64FromScVal(structField(v, "a")),
+    b: i64FromScVal(structField(v, "b")),
+    c: vecFromScVal(i64FromScVal)(structField(v, "c")),
+  };
+}
+
+/** Client for invoking the functions of the contract. */
+export class Client {
+  constructor(private readonly invoke: Invoke) {}
+
+  async add(a: UdtEnum, b: UdtEnum): Promise<bigint> {
+    const result = await this.invoke("add", [
+      UdtEnumToScVal(a),
+      UdtEnumToScVal(b),
+    ]);
+    return i64FromScVal(result);
+  }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,201 @@
+// Generated from the spec of a Soroban contract.
+
+import { xdr } from "stellar-base";
+
+/** Invokes the function of the contract with the arguments. */
+export type Invoke = (method: string, args: xdr.ScVal[]) => Promise<xdr.ScVal>;
+
+/** The value returned by a function that returns a Result. */
+export type Result<T, E> = { ok: T } | { err: E };
+
+type ToScVal<T> = (v: T) => xdr.ScVal;
+type FromScVal<T> = (v: xdr.ScVal) => T;
+
+const vecOf = (items: xdr.ScVal[]): xdr.ScVal =>
+  xdr.ScVal.scvObject(xdr.ScObject.scoVec(items));
+const vecItems = (v: xdr.ScVal): xdr.ScVal[] => v.obj()!.vec();
+
+const compareBigInt = (a: bigint, b: bigint): number => (a < b ? -1 : a > b ? 1 : 0);
+
+/** Compares the values in the order the host orders the keys of maps. */
+const compareScVal = (a: xdr.ScVal, b: xdr.ScVal): number => {
+  const byType = a.switch().value - b.switch().value;
+  if (byType !== 0) {
+    return byType;
+  }
+  switch (a.switch().name) {
+    case "scvU63":
+      return compareBigInt(BigInt(a.u63().toString()), BigInt(b.u63().toString()));
+    case "scvU32":
+      return a.u32() - b.u32();
+    case "scvI32":
+      return a.i32() - b.i32();
+    case "scvSymbol":
+      return Buffer.compare(Buffer.from(a.sym()), Buffer.from(b.sym()));
+    case "scvObject": {
+      const [ao, bo] = [a.obj()!, b.obj()!];
+      const byObjType = ao.switch().value - bo.switch().value;
+      if (byObjType !== 0) {
+        return byObjType;
+      }
+      switch (ao.switch().name) {
+        case "scoBytes":
+          return Buffer.compare(ao.bin(), bo.bin());
+        case "scoU64":
+          return compareBigInt(BigInt(ao.u64().toString()), BigInt(bo.u64().toString()));
+        case "scoI64":
+          return compareBigInt(BigInt(ao.i64().toString()), BigInt(bo.i64().toString()));
+      }
+    }
+  }
+  return Buffer.compare(a.toXDR(), b.toXDR());
+};
+
+/** Returns a map of the entries, ordered by key as the host requires. */
+const mapOf = (entries: Array<[xdr.ScVal, xdr.ScVal]>): xdr.ScVal =>
+  xdr.ScVal.scvObject(
+    xdr.ScObject.scoMap(
+      [...entries]
+        .sort(([a], [b]) => compareScVal(a, b))
+        .map(([key, val]) => new xdr.ScMapEntry({ key, val }))
+    )
+  );
+const mapEntries = (v: xdr.ScVal): Array<[xdr.ScVal, xdr.ScVal]> =>
+  v.obj()!.map().map((e): [xdr.ScVal, xdr.ScVal] => [e.key(), e.val()]);
+
+const isVoid = (v: xdr.ScVal): boolean =>
+  v.switch().name === "scvStatic" && v.ic().name === "scsVoid";
+const voidScVal = (): xdr.ScVal => xdr.ScVal.scvStatic(xdr.ScStatic.scsVoid());
+
+const valToScVal: ToScVal<xdr.ScVal> = (v) => v;
+const valFromScVal: FromScVal<xdr.ScVal> = (v) => v;
+
+const boolToScVal: ToScVal<boolean> = (v) =>
+  xdr.ScVal.scvStatic(v ? xdr.ScStatic.scsTrue() : xdr.ScStatic.scsFalse());
+const boolFromScVal: FromScVal<boolean> = (v) => v.ic().name === "scsTrue";
+
+const u32ToScVal: ToScVal<number> = (v) => xdr.ScVal.scvU32(v);
+const u32FromScVal: FromScVal<number> = (v) => v.u32();
+
+const i32ToScVal: ToScVal<number> = (v) => xdr.ScVal.scvI32(v);
+const i32FromScVal: FromScVal<number> = (v) => v.i32();
+
+const u64ToScVal: ToScVal<bigint> = (v) =>
+  xdr.ScVal.scvObject(xdr.ScObject.scoU64(xdr.Uint64.fromString(v.toString())));
+const u64FromScVal: FromScVal<bigint> = (v) => BigInt(v.obj()!.u64().toString());
+
+const i64ToScVal: ToScVal<bigint> = (v) =>
+  v >= 0n
+    ? xdr.ScVal.scvU63(xdr.Int64.fromString(v.toString()))
+    : xdr.ScVal.scvObject(xdr.ScObject.scoI64(xdr.Int64.fromString(v.toString())));
+const i64FromScVal: FromScVal<bigint> = (v) =>
+  v.switch().name === "scvU63"
+    ? BigInt(v.u63().toString())
+    : BigInt(v.obj()!.i64().toString());
+
+const bigIntToScVal: ToScVal<bigint> = (v) => {
+  if (v === 0n) {
+    return xdr.ScVal.scvObject(xdr.ScObject.scoBigInt(xdr.ScBigInt.zero()));
+  }
+  const hex = (v < 0n ? -v : v).toString(16);
+  const magnitude = Buffer.from(hex.length % 2 === 0 ? hex : `0${hex}`, "hex");
+  return xdr.ScVal.scvObject(
+    xdr.ScObject.scoBigInt(
+      v < 0n ? xdr.ScBigInt.negative(magnitude) : xdr.ScBigInt.positive(magnitude)
+    )
+  );
+};
+const bigIntFromScVal: FromScVal<bigint> = (v) => {
+  const b = v.obj()!.bigInt();
+  switch (b.switch().name) {
+    case "positive":
+      return BigInt(`0x${b.magnitude().toString("hex")}`);
+    case "negative":
+      return -BigInt(`0x${b.magnitude().toString("hex")}`);
+    default:
+      return 0n;
+  }
+};
+
+const symbolToScVal: ToScVal<string> = (v) => xdr.ScVal.scvSymbol(v);
+const symbolFromScVal: FromScVal<string> = (v) => v.sym().toString();
+
+const bitsetToScVal: ToScVal<bigint> = (v) =>
+  xdr.ScVal.scvBitset(xdr.Uint64.fromString(v.toString()));
+const bitsetFromScVal: FromScVal<bigint> = (v) => BigInt(v.bits().toString());
+
+const statusToScVal: ToScVal<xdr.ScStatus> = (v) => xdr.ScVal.scvStatus(v);
+const statusFromScVal: FromScVal<xdr.ScStatus> = (v) => v.status();
+
+const contractErrorToScVal: ToScVal<number> = (v) =>
+  xdr.ScVal.scvStatus(xdr.ScStatus.sstContractError(v));
+const contractErrorFromScVal: FromScVal<number> = (v) =>
+  v.switch().name === "scvStatus" ? v.status().contractCode() : v.u32();
+
+const bytesToScVal: ToScVal<Buffer> = (v) =>
+  xdr.ScVal.scvObject(xdr.ScObject.scoBytes(v));
+const bytesFromScVal: FromScVal<Buffer> = (v) => v.obj()!.bin();
+
+const vecToScVal =
+  <T>(f: ToScVal<T>): ToScVal<Array<T>> =>
+  (v) =>
+    vecOf(v.map(f));
+const vecFromScVal =
+  <T>(f: FromScVal<T>): FromScVal<Array<T>> =>
+  (v) =>
+    vecItems(v).map(f);
+
+const mapToScVal =
+  <K, V>(k: ToScVal<K>, val: ToScVal<V>): ToScVal<Map<K, V>> =>
+  (v) =>
+    mapOf(Array.from(v.entries()).map(([ek, ev]): [xdr.ScVal, xdr.ScVal] => [k(ek), val(ev)]));
+const mapFromScVal =
+  <K, V>(k: FromScVal<K>, val: FromScVal<V>): FromScVal<Map<K, V>> =>
+  (v) =>
+    new Map(mapEntries(v).map(([ek, ev]): [K, V] => [k(ek), val(ev)]));
+
+const setToScVal =
+  <T>(f: ToScVal<T>): ToScVal<Set<T>> =>
+  (v) =>
+    mapOf(Array.from(v).map((e): [xdr.ScVal, xdr.ScVal] => [f(e), voidScVal()]));
+const setFromScVal =
+  <T>(f: FromScVal<T>): FromScVal<Set<T>> =>
+  (v) =>
+    new Set(mapEntries(v).map(([e]) => f(e)));
+
+const optionToScVal =
+  <T>(f: ToScVal<T>): ToScVal<T | undefined> =>
+  (v) =>
+    v === undefined ? voidScVal() : f(v);
+const optionFromScVal =
+  <T>(f: FromScVal<T>): FromScVal<T | undefined> =>
+  (v) =>
+    isVoid(v) ? undefined : f(v);
+
+const resultToScVal =
+  <T, E>(ok: ToScVal<T>, err: ToScVal<E>): ToScVal<Result<T, E>> =>
+  (v) =>
+    "ok" in v ? ok(v.ok) : err(v.err);
+const resultFromScVal =
+  <T, E>(ok: FromScVal<T>, err: FromScVal<E>): FromScVal<Result<T, E>> =>
+  (v) =>
+    v.switch().name === "scvStatus" ? { err: err(v) } : { ok: ok(v) };
+
+const tupleToScVal =
+  (...fs: Array<ToScVal<any>>): ToScVal<any[]> =>
+  (v) =>
+    vecOf(fs.map((f, i) => f(v[i])));
+const tupleFromScVal =
+  (...fs: Array<FromScVal<any>>): FromScVal<any[]> =>
+  (v) => {
+    const items = vecItems(v);
+    return fs.map((f, i) => f(items[i]));
+  };
+
+const structField = (v: xdr.ScVal, name: string): xdr.ScVal => {
+  const entry = mapEntries(v).find(([k]) => symbolFromScVal(k) === name);
+  if (entry === undefined) {
+    throw new Error(`missing field ${name}`);
+  }
+  return entry[1];
+};
@@ -0,0 +1,493 @@
+use itertools::Itertools;
+use stellar_xdr::{
+    ScSpecFunctionV0, ScSpecTypeDef, ScSpecUdtEnumV0, ScSpecUdtErrorEnumV0, ScSpecUdtStructV0,
+    ScSpecUdtUnionV0,
+};
+
+use crate::gen::{struct_alias, union_case_struct};
+
+/// Formats the items as a multi-line array literal, with each item on its own
+/// line at the indent.
+fn array(items: &[String], indent: &str) -> String {
+    if items.is_empty() {
+        return "[]".to_string();
+    }
+    let mut s = "[\n".to_string();
+    for item in items {
+        s.push_str(&format!("{indent}  {item},\n"));
+    }
+    s.push_str(&format!("{indent}]"));
+    s
+}
+
+pub fn generate_struct(spec: &ScSpecUdtStructV0) -> String {
+    let name = spec.name.to_string_lossy();
+    if let Some(t) = struct_alias(spec) {
+        // Transparent structs are an alias of the type of their field.
+        format!(
+            r#"
+export type {name} = {};
+
+function {name}ToScVal(v: {name}): xdr.ScVal {{
+  return {}(v);
+}}
+
+function {name}FromScVal(v: xdr.ScVal): {name} {{
+  return {}(v);
+}}
+"#,
+            generate_type_ident(t),
+            generate_to_scval(t),
+            generate_from_scval(t),
+        )
+    } else if spec.fields.is_empty() {
+        // Unit structs are void.
+        format!(
+            r#"
+export type {name} = null;
+
+function {name}ToScVal(_v: {name}): xdr.ScVal {{
+  return voidScVal();
+}}
+
+function {name}FromScVal(_v: xdr.ScVal): {name} {{
+  return null;
+}}
+"#
+        )
+    } else if spec
+        .fields
+        .iter()
+        .all(|f| f.name.to_string_lossy().parse::<usize>().is_ok())
+    {
+        // If all fields are numeric, generate a tuple with unnamed fields.
+        let types = spec
+            .fields
+            .iter()
+            .map(|f| generate_type_ident(&f.type_))
+            .join(", ");
+        let to = spec
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| format!("{}(v[{i}])", generate_to_scval(&f.type_)))
+            .collect::<Vec<_>>();
+        let from = spec
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| format!("{}(items[{i}])", generate_from_scval(&f.type_)))
+            .collect::<Vec<_>>();
+        format!(
+            r#"
+export type {name} = [{types}];
+
+function {name}ToScVal(v: {name}): xdr.ScVal {{
+  return vecOf({});
+}}
+
+function {name}FromScVal(v: xdr.ScVal): {name} {{
+  const items = vecItems(v);
+  return {};
+}}
+"#,
+            array(&to, "  "),
+            array(&from, "  "),
+        )
+    } else {
+        // Otherwise generate an interface with named fields, stored as a map
+        // from field name to value.
+        let fields = spec
+            .fields
+            .iter()
+            .map(|f| {
+                format!(
+                    "  {}: {};\n",
+                    f.name.to_string_lossy(),
+                    generate_type_ident(&f.type_)
+                )
+            })
+            .join("");
+        // Maps in the host are ordered by key, so the entries are generated
+        // ordered by field name.
+        let to = spec
+            .fields
+            .iter()
+            .sorted_by_key(|f| f.name.to_string_lossy())
+            .map(|f| {
+                let field = f.name.to_string_lossy();
+                format!(
+                    "[symbolToScVal(\"{field}\"), {}(v.{field})]",
+                    generate_to_scval(&f.type_)
+                )
+            })
+            .collect::<Vec<_>>();
+        let from = spec
+            .fields
+            .iter()
+            .map(|f| {
+                let field = f.name.to_string_lossy();
+                format!(
+                    "    {field}: {}(structField(v, \"{field}\")),\n",
+                    generate_from_scval(&f.type_)
+                )
+            })
+            .join("");
+        format!(
+            r#"
+export interface {name} {{
+{fields}}}
+
+function {name}ToScVal(v: {name}): xdr.ScVal {{
+  return mapOf({});
+}}
+
+function {name}FromScVal(v: xdr.ScVal): {name} {{
+  return {{
+{from}  }};
+}}
+"#,
+            array(&to, "  "),
+        )
+    }
+}
+
+/// Generates a type for the union. The structs are used to generate the fields
+/// of cases that are variants with named fields, which have an object of the
+/// fields as their value.
+pub fn generate_union(spec: &ScSpecUdtUnionV0, structs: &[&ScSpecUdtStructV0]) -> String {
+    let name = spec.name.to_string_lossy();
+    let variants = spec
+        .cases
+        .iter()
+        .map(|c| {
+            let case = c.name.to_string_lossy();
+            if let Some(s) = union_case_struct(spec, c, structs) {
+                let fields = s
+                    .fields
+                    .iter()
+                    .map(|f| {
+                        format!(
+                            "{}: {}",
+                            f.name.to_string_lossy(),
+                            generate_type_ident(&f.type_)
+                        )
+                    })
+                    .join("; ");
+                return format!("\n  | {{ tag: \"{case}\"; value: {{ {fields} }} }}");
+            }
+            match &c.type_ {
+                Some(t) => format!(
+                    "\n  | {{ tag: \"{case}\"; value: {} }}",
+                    generate_type_ident(t)
+                ),
+                None => format!("\n  | {{ tag: \"{case}\" }}"),
+            }
+        })
+        .join("");
+    let to = spec
+        .cases
+        .iter()
+        .map(|c| {
+            let case = c.name.to_string_lossy();
+            let value = if let Some(s) = union_case_struct(spec, c, structs) {
+                // Maps in the host are ordered by key, so the entries are
+                // generated ordered by field name.
+                let entries = s
+                    .fields
+                    .iter()
+                    .sorted_by_key(|f| f.name.to_string_lossy())
+                    .map(|f| {
+                        let field = f.name.to_string_lossy();
+                        format!(
+                            "[symbolToScVal(\"{field}\"), {}(v.value.{field})]",
+                            generate_to_scval(&f.type_)
+                        )
+                    })
+                    .join(", ");
+                format!(", mapOf([{entries}])")
+            } else {
+                match &c.type_ {
+                    Some(t) => format!(", {}(v.value)", generate_to_scval(t)),
+                    None => String::new(),
+                }
+            };
+            format!(
+                "    case \"{case}\":\n      return vecOf([symbolToScVal(\"{case}\"){value}]);\n"
+            )
+        })
+        .join("");
+    let from = spec
+        .cases
+        .iter()
+        .map(|c| {
+            let case = c.name.to_string_lossy();
+            let value = if let Some(s) = union_case_struct(spec, c, structs) {
+                let fields = s
+                    .fields
+                    .iter()
+                    .map(|f| {
+                        let field = f.name.to_string_lossy();
+                        format!(
+                            "{field}: {}(structField(items[1], \"{field}\"))",
+                            generate_from_scval(&f.type_)
+                        )
+                    })
+                    .join(", ");
+                format!(", value: {{ {fields} }}")
+            } else {
+                match &c.type_ {
+                    Some(t) => format!(", value: {}(items[1])", generate_from_scval(t)),
+                    None => String::new(),
+                }
+            };
+            format!("    case \"{case}\":\n      return {{ tag: \"{case}\"{value} }};\n")
+        })
+        .join("");
+    format!(
+        r#"
+export type {name} ={variants};
+
+function {name}ToScVal(v: {name}): xdr.ScVal {{
+  switch (v.tag) {{
+{to}  }}
+}}
+
+function {name}FromScVal(v: xdr.ScVal): {name} {{
+  const items = vecItems(v);
+  const tag = symbolFromScVal(items[0]);
+  switch (tag) {{
+{from}    default:
+      throw new Error(`unknown case ${{tag}} of {name}`);
+  }}
+}}
+"#
+    )
+}
+
+pub fn generate_enum(spec: &ScSpecUdtEnumV0) -> String {
+    let name = spec.name.to_string_lossy();
+    let cases = spec
+        .cases
+        .iter()
+        .map(|c| format!("  {} = {},\n", c.name.to_string_lossy(), c.value))
+        .join("");
+    format!(
+        r#"
+export enum {name} {{
+{cases}}}
+
+function {name}ToScVal(v: {name}): xdr.ScVal {{
+  return u32ToScVal(v);
+}}
+
+function {name}FromScVal(v: xdr.ScVal): {name} {{
+  return u32FromScVal(v);
+}}
+"#
+    )
+}
+
+pub fn generate_error_enum(spec: &ScSpecUdtErrorEnumV0) -> String {
+    let name = spec.name.to_string_lossy();
+    let cases = spec
+        .cases
+        .iter()
+        .map(|c| format!("  {} = {},\n", c.name.to_string_lossy(), c.value))
+        .join("");
+    format!(
+        r#"
+export enum {name} {{
+{cases}}}
+
+function {name}ToScVal(v: {name}): xdr.ScVal {{
+  return contractErrorToScVal(v);
+}}
+
+function {name}FromScVal(v: xdr.ScVal): {name} {{
+  return contractErrorFromScVal(v);
+}}
+"#
+    )
+}
+
+pub fn generate_client(name: &str, specs: &[&ScSpecFunctionV0]) -> String {
+    let fns = specs
+        .iter()
+        .map(|s| {
+            let fn_name = s.name.to_string_lossy();
+            let params = s
+                .inputs
+                .iter()
+                .map(|i| {
+                    format!(
+                        "{}: {}",
+                        i.name.to_string_lossy(),
+                        generate_type_ident(&i.type_)
+                    )
+                })
+                .join(", ");
+            let args = s
+                .inputs
+                .iter()
+                .map(|i| {
+                    format!(
+                        "{}({})",
+                        generate_to_scval(&i.type_),
+                        i.name.to_string_lossy()
+                    )
+                })
+                .collect::<Vec<_>>();
+            let invoke = format!("await this.invoke(\"{fn_name}\", {})", array(&args, "    "));
+            match s.outputs.first() {
+                Some(t) => format!(
+                    r#"
+  async {fn_name}({params}): Promise<{}> {{
+    const result = {invoke};
+    return {}(result);
+  }}
+"#,
+                    generate_type_ident(t),
+                    generate_from_scval(t),
+                ),
+                None => format!(
+                    r#"
+  async {fn_name}({params}): Promise<void> {{
+    {invoke};
+  }}
+"#
+                ),
+            }
+        })
+        .join("");
+    format!(
+        r#"
+/** Client for invoking the functions of the contract. */
+export class {name} {{
+  constructor(private readonly invoke: Invoke) {{}}
+{fns}}}
+"#
+    )
+}
+
+/// Returns the TypeScript type that values of the spec type are represented
+/// with.
+pub fn generate_type_ident(spec: &ScSpecTypeDef) -> String {
+    match spec {
+        ScSpecTypeDef::Val => "xdr.ScVal".to_string(),
+        ScSpecTypeDef::U64 => "bigint".to_string(),
+        ScSpecTypeDef::I64 => "bigint".to_string(),
+        ScSpecTypeDef::U32 => "number".to_string(),
+        ScSpecTypeDef::I32 => "number".to_string(),
+        ScSpecTypeDef::Bool => "boolean".to_string(),
+        ScSpecTypeDef::Symbol => "string".to_string(),
+        ScSpecTypeDef::Bitset => "bigint".to_string(),
+        ScSpecTypeDef::Status => "xdr.ScStatus".to_string(),
+        ScSpecTypeDef::Bytes => "Buffer".to_string(),
+        ScSpecTypeDef::BigInt => "bigint".to_string(),
+        ScSpecTypeDef::Invoker => "xdr.ScVal".to_string(),
+        ScSpecTypeDef::AccountId => "xdr.ScVal".to_string(),
+        ScSpecTypeDef::Option(o) => format!("{} | undefined", generate_type_ident(&o.value_type)),
+        ScSpecTypeDef::Result(r) => format!(
+            "Result<{}, {}>",
+            generate_type_ident(&r.ok_type),
+            generate_type_ident(&r.error_type)
+        ),
+        ScSpecTypeDef::Vec(v) => format!("Array<{}>", generate_type_ident(&v.element_type)),
+        ScSpecTypeDef::Map(m) => format!(
+            "Map<{}, {}>",
+            generate_type_ident(&m.key_type),
+            generate_type_ident(&m.value_type)
+        ),
+        ScSpecTypeDef::Set(s) => format!("Set<{}>", generate_type_ident(&s.element_type)),
+        ScSpecTypeDef::Tuple(t) => format!(
+            "[{}]",
+            t.value_types.iter().map(generate_type_ident).join(", ")
+        ),
+        ScSpecTypeDef::BytesN(_) => "Buffer".to_string(),
+        ScSpecTypeDef::Udt(u) => u.name.to_string_lossy(),
+    }
+}
+
+/// Returns an expression for a function that converts values of the spec type
+/// into `xdr.ScVal`s.
+pub fn generate_to_scval(spec: &ScSpecTypeDef) -> String {
+    match spec {
+        ScSpecTypeDef::Val => "valToScVal".to_string(),
+        ScSpecTypeDef::U64 => "u64ToScVal".to_string(),
+        ScSpecTypeDef::I64 => "i64ToScVal".to_string(),
+        ScSpecTypeDef::U32 => "u32ToScVal".to_string(),
+        ScSpecTypeDef::I32 => "i32ToScVal".to_string(),
+        ScSpecTypeDef::Bool => "boolToScVal".to_string(),
+        ScSpecTypeDef::Symbol => "symbolToScVal".to_string(),
+        ScSpecTypeDef::Bitset => "bitsetToScVal".to_string(),
+        ScSpecTypeDef::Status => "statusToScVal".to_string(),
+        ScSpecTypeDef::Bytes => "bytesToScVal".to_string(),
+        ScSpecTypeDef::BigInt => "bigIntToScVal".to_string(),
+        ScSpecTypeDef::Invoker => "valToScVal".to_string(),
+        ScSpecTypeDef::AccountId => "valToScVal".to_string(),
+        ScSpecTypeDef::Option(o) => format!("optionToScVal({})", generate_to_scval(&o.value_type)),
+        ScSpecTypeDef::Result(r) => format!(
+            "resultToScVal({}, {})",
+            generate_to_scval(&r.ok_type),
+            generate_to_scval(&r.error_type)
+        ),
+        ScSpecTypeDef::Vec(v) => format!("vecToScVal({})", generate_to_scval(&v.element_type)),
+        ScSpecTypeDef::Map(m) => format!(
+            "mapToScVal({}, {})",
+            generate_to_scval(&m.key_type),
+            generate_to_scval(&m.value_type)
+        ),
+        ScSpecTypeDef::Set(s) => format!("setToScVal({})", generate_to_scval(&s.element_type)),
+        ScSpecTypeDef::Tuple(t) => format!(
+            "tupleToScVal({})",
+            t.value_types.iter().map(generate_to_scval).join(", ")
+        ),
+        ScSpecTypeDef::BytesN(_) => "bytesToScVal".to_string(),
+        ScSpecTypeDef::Udt(u) => format!("{}ToScVal", u.name.to_string_lossy()),
+    }
+}
+
+/// Returns an expression for a function that converts `xdr.ScVal`s into values
+/// of the spec type.
+pub fn generate_from_scval(spec: &ScSpecTypeDef) -> String {
+    match spec {
+        ScSpecTypeDef::Val => "valFromScVal".to_string(),
+        ScSpecTypeDef::U64 => "u64FromScVal".to_string(),
+        ScSpecTypeDef::I64 => "i64FromScVal".to_string(),
+        ScSpecTypeDef::U32 => "u32FromScVal".to_string(),
+        ScSpecTypeDef::I32 => "i32FromScVal".to_string(),
+        ScSpecTypeDef::Bool => "boolFromScVal".to_string(),
+        ScSpecTypeDef::Symbol => "symbolFromScVal".to_string(),
+        ScSpecTypeDef::Bitset => "bitsetFromScVal".to_string(),
+        ScSpecTypeDef::Status => "statusFromScVal".to_string(),
+        ScSpecTypeDef::Bytes => "bytesFromScVal".to_string(),
+        ScSpecTypeDef::BigInt => "bigIntFromScVal".to_string(),
+        ScSpecTypeDef::Invoker => "valFromScVal".to_string(),
+        ScSpecTypeDef::AccountId => "valFromScVal".to_string(),
+        ScSpecTypeDef::Option(o) => {
+            format!("optionFromScVal({})", generate_from_scval(&o.value_type))
+        }
+        ScSpecTypeDef::Result(r) => format!(
+            "resultFromScVal({}, {})",
+            generate_from_scval(&r.ok_type),
+            generate_from_scval(&r.error_type)
+        ),
+        ScSpecTypeDef::Vec(v) => format!("vecFromScVal({})", generate_from_scval(&v.element_type)),
+        ScSpecTypeDef::Map(m) => format!(
+            "mapFromScVal({}, {})",
+            generate_from_scval(&m.key_type),
+            generate_from_scval(&m.value_type)
+        ),
+        ScSpecTypeDef::Set(s) => format!("setFromScVal({})", generate_from_scval(&s.element_type)),
+        // The tuple conversion is untyped, so the function is cast to the type
+        // of the tuple.
+        ScSpecTypeDef::Tuple(t) => format!(
+            "(tupleFromScVal({}) as FromScVal<{}>)",
+            t.value_types.iter().map(generate_from_scval).join(", "),
+            generate_type_ident(spec),
+        ),
+        ScSpecTypeDef::BytesN(_) => "bytesFromScVal".to_string(),
+        ScSpecTypeDef::Udt(u) => format!("{}FromScVal", u.name.to_string_lossy()),
+    }
+}
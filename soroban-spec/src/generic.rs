@@ -0,0 +1,614 @@
+//! Resolve the generic types of a contract spec.
+//!
+//! Generic types are named in the spec with their type parameters, e.g.
+//! `Page<T>`, and the types of their fields that are a type parameter are a
+//! user-defined type named the parameter, e.g. `T`. The structs describing the
+//! variants of generic enums with named fields are named with the parameters of
+//! the enum, e.g. `Shape<T>.Rect`.
+//!
+//! Uses of generic types are a user-defined type named with the names of the
+//! type arguments, as they are written in Rust contracts, e.g. `Page<u32>` or
+//! `Page<Vec<Symbol>>`.
+//!
+//! Types that values are converted to and from, or that code is generated for,
+//! are the instances of the generic types used by the contract, which are
+//! produced by [`instantiate`] and [`monomorphize`].
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use itertools::Itertools;
+use stellar_xdr::{
+    ScSpecEntry, ScSpecTypeBytesN, ScSpecTypeDef, ScSpecTypeMap, ScSpecTypeOption,
+    ScSpecTypeResult, ScSpecTypeSet, ScSpecTypeTuple, ScSpecTypeUdt, ScSpecTypeVec,
+};
+
+use crate::gen::type_name;
+
+/// The name of a user-defined type, split into the name of the type, the
+/// arguments, or parameters, of a generic type, and the case of the union the
+/// type describes the fields of.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UdtName {
+    /// The name of the type without its arguments, e.g. `Page` for `Page<u32>`.
+    pub name: String,
+    /// The type arguments, e.g. `u32` for `Page<u32>`, or the type parameters
+    /// of a generic type, e.g. `T` for `Page<T>`.
+    pub args: Vec<ScSpecTypeDef>,
+    /// The case of the union, e.g. `Rect` for `Shape<u32>.Rect`, if the type is
+    /// the struct describing the fields of the case of a generic union.
+    pub case: Option<String>,
+}
+
+impl UdtName {
+    /// Parses the name of the user-defined type. Names without arguments are
+    /// not split, and are the name of the type in whole, including any case.
+    pub fn parse(name: &str) -> Option<Self> {
+        let (base, rest) = match name.split_once('<') {
+            Some(split) => split,
+            None => {
+                return Some(Self {
+                    name: name.to_string(),
+                    args: Vec::new(),
+                    case: None,
+                })
+            }
+        };
+        let mut p = Parser { s: rest };
+        let args = p.list('>')?;
+        let case = match p.s {
+            "" => None,
+            s => Some(s.strip_prefix('.')?.to_string()),
+        };
+        Some(Self {
+            name: base.to_string(),
+            args,
+            case,
+        })
+    }
+
+    /// Returns the name the definition of the type is looked up by, that is
+    /// the name of the type followed by any case, without the arguments, e.g.
+    /// `Page` for `Page<u32>` and `Shape.Rect` for `Shape<u32>.Rect`.
+    pub fn key(&self) -> String {
+        match &self.case {
+            Some(case) => format!("{}.{case}", self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    /// Returns the name of the type as it is written in the spec.
+    pub fn to_name(&self) -> String {
+        let mut name = self.name.clone();
+        if !self.args.is_empty() {
+            name.push_str(&format!("<{}>", self.args.iter().map(type_name).join(", ")));
+        }
+        if let Some(case) = &self.case {
+            name.push_str(&format!(".{case}"));
+        }
+        name
+    }
+
+    /// Returns an identifier for the type, that is the name of the type
+    /// followed by the names of the arguments, e.g. `PageU32` for `Page<u32>`,
+    /// followed by any case, e.g. `ShapeU32.Rect` for `Shape<u32>.Rect`.
+    pub fn to_ident(&self) -> String {
+        let mut ident = self.name.clone();
+        for arg in &self.args {
+            for part in type_name(arg).split(|c: char| !c.is_ascii_alphanumeric()) {
+                let mut chars = part.chars();
+                if let Some(first) = chars.next() {
+                    ident.push(first.to_ascii_uppercase());
+                    ident.extend(chars);
+                }
+            }
+        }
+        if let Some(case) = &self.case {
+            ident.push_str(&format!(".{case}"));
+        }
+        ident
+    }
+}
+
+/// Parses the name of the type, as it is written in Rust contracts, and as it
+/// is returned by [`type_name`].
+pub fn parse_type_name(name: &str) -> Option<ScSpecTypeDef> {
+    let mut p = Parser { s: name };
+    let t = p.type_()?;
+    p.s.is_empty().then_some(t)
+}
+
+struct Parser<'a> {
+    s: &'a str,
+}
+
+impl Parser<'_> {
+    fn eat(&mut self, c: char) -> bool {
+        match self.s.strip_prefix(c) {
+            Some(rest) => {
+                self.s = rest.trim_start();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn ident(&mut self) -> Option<String> {
+        let end = self
+            .s
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(self.s.len());
+        if end == 0 {
+            return None;
+        }
+        let (ident, rest) = self.s.split_at(end);
+        self.s = rest;
+        Some(ident.to_string())
+    }
+
+    /// Parses the types separated by commas up to and including the closing
+    /// character.
+    fn list(&mut self, close: char) -> Option<Vec<ScSpecTypeDef>> {
+        let mut types = Vec::new();
+        while !self.eat(close) {
+            if !types.is_empty() && !self.eat(',') {
+                return None;
+            }
+            types.push(self.type_()?);
+        }
+        Some(types)
+    }
+
+    fn type_(&mut self) -> Option<ScSpecTypeDef> {
+        if self.eat('(') {
+            return Some(ScSpecTypeDef::Tuple(Box::new(ScSpecTypeTuple {
+                value_types: self.list(')')?.try_into().ok()?,
+            })));
+        }
+        let ident = self.ident()?;
+        if ident == "BytesN" {
+            let n = (self.eat('<'), self.ident()?.parse().ok()?, self.eat('>'));
+            return match n {
+                (true, n, true) => Some(ScSpecTypeDef::BytesN(ScSpecTypeBytesN { n })),
+                _ => None,
+            };
+        }
+        let args = if self.eat('<') {
+            self.list('>')?
+        } else {
+            Vec::new()
+        };
+        let t = match (ident.as_str(), args.as_slice()) {
+            ("RawVal", []) => ScSpecTypeDef::Val,
+            ("u64", []) => ScSpecTypeDef::U64,
+            ("i64", []) => ScSpecTypeDef::I64,
+            ("u32", []) => ScSpecTypeDef::U32,
+            ("i32", []) => ScSpecTypeDef::I32,
+            ("bool", []) => ScSpecTypeDef::Bool,
+            ("Symbol", []) => ScSpecTypeDef::Symbol,
+            ("Bitset", []) => ScSpecTypeDef::Bitset,
+            ("Status", []) => ScSpecTypeDef::Status,
+            ("Bytes", []) => ScSpecTypeDef::Bytes,
+            ("BigInt", []) => ScSpecTypeDef::BigInt,
+            ("Invoker", []) => ScSpecTypeDef::Invoker,
+            ("AccountId", []) => ScSpecTypeDef::AccountId,
+            ("Option", [t]) => ScSpecTypeDef::Option(Box::new(ScSpecTypeOption {
+                value_type: Box::new(t.clone()),
+            })),
+            ("Result", [ok, err]) => ScSpecTypeDef::Result(Box::new(ScSpecTypeResult {
+                ok_type: Box::new(ok.clone()),
+                error_type: Box::new(err.clone()),
+            })),
+            ("Vec", [t]) => ScSpecTypeDef::Vec(Box::new(ScSpecTypeVec {
+                element_type: Box::new(t.clone()),
+            })),
+            ("Map", [k, v]) => ScSpecTypeDef::Map(Box::new(ScSpecTypeMap {
+                key_type: Box::new(k.clone()),
+                value_type: Box::new(v.clone()),
+            })),
+            ("Set", [t]) => ScSpecTypeDef::Set(Box::new(ScSpecTypeSet {
+                element_type: Box::new(t.clone()),
+            })),
+            // Built-in types given the wrong number of arguments.
+            (
+                "RawVal" | "u64" | "i64" | "u32" | "i32" | "bool" | "Symbol" | "Bitset" | "Status"
+                | "Bytes" | "BigInt" | "Invoker" | "AccountId" | "Option" | "Result" | "Vec"
+                | "Map" | "Set",
+                _,
+            ) => return None,
+            _ => udt_named(
+                &UdtName {
+                    name: ident,
+                    args,
+                    case: None,
+                }
+                .to_name(),
+            )?,
+        };
+        Some(t)
+    }
+}
+
+/// Returns the names of the type parameters of the entry if it is the
+/// definition of a generic type, or [None] if it is not generic.
+pub fn type_params(entry: &ScSpecEntry) -> Option<Vec<String>> {
+    let name = match entry {
+        ScSpecEntry::UdtStructV0(s) => s.name.to_string_lossy(),
+        ScSpecEntry::UdtUnionV0(u) => u.name.to_string_lossy(),
+        _ => return None,
+    };
+    let name = UdtName::parse(&name)?;
+    if name.args.is_empty() {
+        return None;
+    }
+    name.args
+        .iter()
+        .map(|a| match a {
+            ScSpecTypeDef::Udt(u) => Some(u.name.to_string_lossy()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the entries with the definitions of generic types replaced by the
+/// instances of them that are used by the other entries, named with their type
+/// arguments, e.g. `Page<u32>`.
+///
+/// Instances that cannot be named in the spec, such as instances of types that
+/// contain instances of themselves with other arguments, are not included.
+pub fn instantiate(entries: &[ScSpecEntry]) -> Vec<ScSpecEntry> {
+    let mut generics = HashMap::new();
+    let mut instances = Vec::new();
+    for e in entries {
+        match type_params(e) {
+            Some(params) => {
+                let name = UdtName::parse(&entry_name(e)).unwrap();
+                generics.insert(name.key(), (params, e));
+            }
+            None => instances.push(e.clone()),
+        }
+    }
+
+    let mut queue = VecDeque::new();
+    for e in &instances {
+        queue.extend(udts_in_entry(e));
+    }
+    let mut seen = HashSet::new();
+    while let Some(name) = queue.pop_front() {
+        if !seen.insert(name.clone()) {
+            continue;
+        }
+        let udt = match UdtName::parse(&name) {
+            Some(udt) if !udt.args.is_empty() => udt,
+            _ => continue,
+        };
+        let (params, generic) = match generics.get(&udt.key()) {
+            Some((params, generic)) if params.len() == udt.args.len() => (params, generic),
+            _ => continue,
+        };
+        let args = params.iter().cloned().zip(udt.args).collect();
+        let instance = map_entry(generic, &name, &|t: &ScSpecTypeDef| substitute(t, &args));
+        if let Some(instance) = instance {
+            queue.extend(udts_in_entry(&instance));
+            instances.push(instance);
+        }
+    }
+    instances
+}
+
+/// Returns the entries with the definitions of generic types replaced by the
+/// instances of them that are used by the other entries, named with an
+/// identifier, e.g. `PageU32` for `Page<u32>`, so that code can be generated
+/// for each instance.
+pub fn monomorphize(entries: &[ScSpecEntry]) -> Vec<ScSpecEntry> {
+    instantiate(entries)
+        .iter()
+        .filter_map(|e| {
+            let name = ident_name(&entry_name(e));
+            let rename = |t: &ScSpecTypeDef| match t {
+                ScSpecTypeDef::Udt(u) => udt_named(&ident_name(&u.name.to_string_lossy())),
+                _ => Some(t.clone()),
+            };
+            map_entry(e, &name, &rename)
+        })
+        .collect()
+}
+
+/// Returns the name of the user-defined type as an identifier.
+fn ident_name(name: &str) -> String {
+    match UdtName::parse(name) {
+        Some(udt) => udt.to_ident(),
+        None => name.to_string(),
+    }
+}
+
+fn entry_name(entry: &ScSpecEntry) -> String {
+    match entry {
+        ScSpecEntry::FunctionV0(f) => f.name.to_string_lossy(),
+        ScSpecEntry::UdtStructV0(s) => s.name.to_string_lossy(),
+        ScSpecEntry::UdtUnionV0(u) => u.name.to_string_lossy(),
+        ScSpecEntry::UdtEnumV0(e) => e.name.to_string_lossy(),
+        ScSpecEntry::UdtErrorEnumV0(e) => e.name.to_string_lossy(),
+    }
+}
+
+fn udt_named(name: &str) -> Option<ScSpecTypeDef> {
+    Some(ScSpecTypeDef::Udt(ScSpecTypeUdt {
+        name: name.try_into().ok()?,
+    }))
+}
+
+/// Returns the type with the type parameters replaced by the type arguments,
+/// including within the arguments of uses of generic types.
+fn substitute(t: &ScSpecTypeDef, args: &HashMap<String, ScSpecTypeDef>) -> Option<ScSpecTypeDef> {
+    let name = match t {
+        ScSpecTypeDef::Udt(u) => u.name.to_string_lossy(),
+        _ => return Some(t.clone()),
+    };
+    if let Some(arg) = args.get(&name) {
+        return Some(arg.clone());
+    }
+    let mut udt = UdtName::parse(&name)?;
+    if udt.args.is_empty() {
+        return Some(t.clone());
+    }
+    udt.args = udt
+        .args
+        .iter()
+        .map(|a| map_type(a, &|t: &ScSpecTypeDef| substitute(t, args)))
+        .collect::<Option<_>>()?;
+    udt_named(&udt.to_name())
+}
+
+/// Returns the names of the user-defined types used by the entry.
+fn udts_in_entry(entry: &ScSpecEntry) -> Vec<String> {
+    let mut names = Vec::new();
+    match entry {
+        ScSpecEntry::FunctionV0(f) => {
+            for t in f.inputs.iter().map(|i| &i.type_).chain(f.outputs.iter()) {
+                udts_in_type(t, &mut names);
+            }
+        }
+        ScSpecEntry::UdtStructV0(s) => {
+            for f in s.fields.iter() {
+                udts_in_type(&f.type_, &mut names);
+            }
+        }
+        ScSpecEntry::UdtUnionV0(u) => {
+            for t in u.cases.iter().filter_map(|c| c.type_.as_ref()) {
+                udts_in_type(t, &mut names);
+            }
+        }
+        ScSpecEntry::UdtEnumV0(_) | ScSpecEntry::UdtErrorEnumV0(_) => {}
+    }
+    names
+}
+
+fn udts_in_type(t: &ScSpecTypeDef, names: &mut Vec<String>) {
+    match t {
+        ScSpecTypeDef::Udt(u) => names.push(u.name.to_string_lossy()),
+        ScSpecTypeDef::Option(o) => udts_in_type(&o.value_type, names),
+        ScSpecTypeDef::Result(r) => {
+            udts_in_type(&r.ok_type, names);
+            udts_in_type(&r.error_type, names);
+        }
+        ScSpecTypeDef::Vec(v) => udts_in_type(&v.element_type, names),
+        ScSpecTypeDef::Map(m) => {
+            udts_in_type(&m.key_type, names);
+            udts_in_type(&m.value_type, names);
+        }
+        ScSpecTypeDef::Set(s) => udts_in_type(&s.element_type, names),
+        ScSpecTypeDef::Tuple(t) => {
+            for t in t.value_types.iter() {
+                udts_in_type(t, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the entry renamed the name, with the user-defined types in the
+/// types of its inputs, outputs, fields, and cases mapped by the function, or
+/// [None] if the function returns [None] for any of them, or if the entry
+/// cannot be represented in the spec.
+fn map_entry(
+    entry: &ScSpecEntry,
+    name: &str,
+    f: &dyn Fn(&ScSpecTypeDef) -> Option<ScSpecTypeDef>,
+) -> Option<ScSpecEntry> {
+    let mut entry = entry.clone();
+    match &mut entry {
+        ScSpecEntry::FunctionV0(func) => {
+            let mut inputs = func.inputs.to_vec();
+            for i in &mut inputs {
+                i.type_ = map_type(&i.type_, f)?;
+            }
+            func.inputs = inputs.try_into().ok()?;
+            func.outputs = func
+                .outputs
+                .iter()
+                .map(|o| map_type(o, f))
+                .collect::<Option<Vec<_>>>()?
+                .try_into()
+                .ok()?;
+        }
+        ScSpecEntry::UdtStructV0(s) => {
+            s.name = name.try_into().ok()?;
+            let mut fields = s.fields.to_vec();
+            for field in &mut fields {
+                field.type_ = map_type(&field.type_, f)?;
+            }
+            s.fields = fields.try_into().ok()?;
+        }
+        ScSpecEntry::UdtUnionV0(u) => {
+            u.name = name.try_into().ok()?;
+            let mut cases = u.cases.to_vec();
+            for c in &mut cases {
+                if let Some(t) = &c.type_ {
+                    c.type_ = Some(map_type(t, f)?);
+                }
+            }
+            u.cases = cases.try_into().ok()?;
+        }
+        ScSpecEntry::UdtEnumV0(_) | ScSpecEntry::UdtErrorEnumV0(_) => {}
+    }
+    Some(entry)
+}
+
+/// Returns the type with the user-defined types within it mapped by the
+/// function.
+fn map_type(
+    t: &ScSpecTypeDef,
+    f: &dyn Fn(&ScSpecTypeDef) -> Option<ScSpecTypeDef>,
+) -> Option<ScSpecTypeDef> {
+    let t = match t {
+        ScSpecTypeDef::Udt(_) => f(t)?,
+        ScSpecTypeDef::Option(o) => ScSpecTypeDef::Option(Box::new(ScSpecTypeOption {
+            value_type: Box::new(map_type(&o.value_type, f)?),
+        })),
+        ScSpecTypeDef::Result(r) => ScSpecTypeDef::Result(Box::new(ScSpecTypeResult {
+            ok_type: Box::new(map_type(&r.ok_type, f)?),
+            error_type: Box::new(map_type(&r.error_type, f)?),
+        })),
+        ScSpecTypeDef::Vec(v) => ScSpecTypeDef::Vec(Box::new(ScSpecTypeVec {
+            element_type: Box::new(map_type(&v.element_type, f)?),
+        })),
+        ScSpecTypeDef::Map(m) => ScSpecTypeDef::Map(Box::new(ScSpecTypeMap {
+            key_type: Box::new(map_type(&m.key_type, f)?),
+            value_type: Box::new(map_type(&m.value_type, f)?),
+        })),
+        ScSpecTypeDef::Set(s) => ScSpecTypeDef::Set(Box::new(ScSpecTypeSet {
+            element_type: Box::new(map_type(&s.element_type, f)?),
+        })),
+        ScSpecTypeDef::Tuple(t) => ScSpecTypeDef::Tuple(Box::new(ScSpecTypeTuple {
+            value_types: t
+                .value_types
+                .iter()
+                .map(|t| map_type(t, f))
+                .collect::<Option<Vec<_>>>()?
+                .try_into()
+                .ok()?,
+        })),
+        t => t.clone(),
+    };
+    Some(t)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use stellar_xdr::{
+        ScSpecEntry, ScSpecFunctionInputV0, ScSpecFunctionV0, ScSpecTypeDef, ScSpecTypeUdt,
+        ScSpecUdtStructFieldV0, ScSpecUdtStructV0, ScSpecUdtUnionCaseV0, ScSpecUdtUnionV0,
+    };
+
+    use super::{monomorphize, parse_type_name, UdtName};
+    use crate::gen::type_name;
+
+    fn udt(name: &str) -> ScSpecTypeDef {
+        ScSpecTypeDef::Udt(ScSpecTypeUdt {
+            name: name.try_into().unwrap(),
+        })
+    }
+
+    #[test]
+    fn test_parse_type_name() {
+        for name in [
+            "u32",
+            "Vec<Option<u64>>",
+            "Map<Symbol, (u32, i64)>",
+            "Result<(), Error>",
+            "BytesN<32>",
+            "Page<Vec<Page<u32>>>",
+        ] {
+            assert_eq!(type_name(&parse_type_name(name).unwrap()), name);
+        }
+        assert_eq!(parse_type_name("Vec<u32"), None);
+        assert_eq!(parse_type_name("Option<u32, u32>"), None);
+    }
+
+    #[test]
+    fn test_udt_name() {
+        let name = UdtName::parse("Shape<Option<u32>>.Rect").unwrap();
+        assert_eq!(name.name, "Shape");
+        assert_eq!(name.args, vec![parse_type_name("Option<u32>").unwrap()]);
+        assert_eq!(name.case.as_deref(), Some("Rect"));
+        assert_eq!(name.key(), "Shape.Rect");
+        assert_eq!(name.to_name(), "Shape<Option<u32>>.Rect");
+        assert_eq!(name.to_ident(), "ShapeOptionU32.Rect");
+
+        let name = UdtName::parse("State.Auction").unwrap();
+        assert_eq!(name.key(), "State.Auction");
+        assert_eq!(name.to_ident(), "State.Auction");
+    }
+
+    #[test]
+    fn test_monomorphize() {
+        let entries = [
+            ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 {
+                lib: "".try_into().unwrap(),
+                name: "Shape<T>".try_into().unwrap(),
+                cases: vec![ScSpecUdtUnionCaseV0 {
+                    name: "Rect".try_into().unwrap(),
+                    type_: Some(udt("Shape<T>.Rect")),
+                }]
+                .try_into()
+                .unwrap(),
+            }),
+            ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+                lib: "".try_into().unwrap(),
+                name: "Shape<T>.Rect".try_into().unwrap(),
+                fields: vec![ScSpecUdtStructFieldV0 {
+                    name: "w".try_into().unwrap(),
+                    type_: udt("T"),
+                }]
+                .try_into()
+                .unwrap(),
+            }),
+            ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+                name: "area".try_into().unwrap(),
+                inputs: vec![ScSpecFunctionInputV0 {
+                    name: "shape".try_into().unwrap(),
+                    type_: udt("Shape<u32>"),
+                }]
+                .try_into()
+                .unwrap(),
+                outputs: vec![ScSpecTypeDef::U32].try_into().unwrap(),
+            }),
+        ];
+        assert_eq!(
+            monomorphize(&entries),
+            vec![
+                ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+                    name: "area".try_into().unwrap(),
+                    inputs: vec![ScSpecFunctionInputV0 {
+                        name: "shape".try_into().unwrap(),
+                        type_: udt("ShapeU32"),
+                    }]
+                    .try_into()
+                    .unwrap(),
+                    outputs: vec![ScSpecTypeDef::U32].try_into().unwrap(),
+                }),
+                ScSpecEntry::UdtUnionV0(ScSpecUdtUnionV0 {
+                    lib: "".try_into().unwrap(),
+                    name: "ShapeU32".try_into().unwrap(),
+                    cases: vec![ScSpecUdtUnionCaseV0 {
+                        name: "Rect".try_into().unwrap(),
+                        type_: Some(udt("ShapeU32.Rect")),
+                    }]
+                    .try_into()
+                    .unwrap(),
+                }),
+                ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+                    lib: "".try_into().unwrap(),
+                    name: "ShapeU32.Rect".try_into().unwrap(),
+                    fields: vec![ScSpecUdtStructFieldV0 {
+                        name: "w".try_into().unwrap(),
+                        type_: ScSpecTypeDef::U32,
+                    }]
+                    .try_into()
+                    .unwrap(),
+                }),
+            ]
+        );
+    }
+}
@@ -0,0 +1,828 @@
+//! Convert contract values between JSON and XDR, using the contract spec to
+//! determine the type of each value.
+//!
+//! Values are represented in JSON as:
+//! - `bool`, integer types, and `BitSet`: JSON booleans and numbers. `u64`
+//! values are always encoded as `ScObject::U64`, the same as contracts and the
+//! generated TypeScript and Python encode them.
+//! - `BigInt`: JSON numbers, or strings containing a decimal number.
+//! - `Symbol`: JSON strings.
+//! - `Bytes` and `BytesN`: JSON strings containing hex.
+//! - `Option`: `null`, or the value.
+//! - `Result`: `{"ok": value}` or `{"error": value}`.
+//! - `Vec`, `Set`, and tuples: JSON arrays. Repeated elements of `Set`s are
+//! ignored.
+//! - `Map`: JSON objects if the keys are `Symbol`s, otherwise JSON arrays of
+//! `[key, value]` pairs. Repeated keys are an error.
+//!
+//! The entries of `Set`s, `Map`s, and structs are ordered by key as the host
+//! requires, the same as the generated TypeScript and Python order them.
+//! - Structs with named fields: JSON objects with a property for each field.
+//! - Transparent structs: the value of their field.
+//! - Unit structs: `null`.
+//! - Structs with unnamed fields: JSON arrays of the field values.
+//! - Unions: JSON strings containing the name of the case for cases without a
+//! value, and JSON objects with a single property named after the case for
+//! cases with a value, e.g. `{"UdtB": {...}}`.
+//! - Enums and error enums: JSON strings containing the name of the case.
+
+use std::cmp::Ordering;
+
+use serde_json::{json, Map, Value};
+use stellar_xdr::{
+    ScBigInt, ScMap, ScMapEntry, ScObject, ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef,
+    ScSpecUdtStructV0, ScStatic, ScStatus, ScVal, ScVec, WriteXdr,
+};
+
+use crate::{
+    fmt::{decimal, hex},
+    gen::{struct_alias, type_name},
+    generic::instantiate,
+    read::{from_wasm, FromWasmError},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("function {name} not found in contract spec")]
+    FunctionNotFound { name: String },
+    #[error("type {name} not found in contract spec")]
+    TypeNotFound { name: String },
+    #[error("converting values of type {type_name} is not supported")]
+    UnsupportedType { type_name: String },
+    #[error("arguments must be a JSON object, found: {found}")]
+    InvalidArguments { found: Value },
+    #[error("missing argument {name}")]
+    MissingArgument { name: String },
+    #[error("unknown argument {name}")]
+    UnknownArgument { name: String },
+    #[error("argument {name}: {error}")]
+    Argument { name: String, error: Box<Error> },
+    #[error("expected JSON for {type_name}, found: {found}")]
+    InvalidJson { type_name: String, found: Value },
+    #[error("expected value of type {type_name}, found: {found:?}")]
+    InvalidVal { type_name: String, found: ScVal },
+    #[error("map key repeated: {key:?}")]
+    DuplicateKey { key: ScVal },
+    #[error("encoding xdr: {0}")]
+    Xdr(stellar_xdr::Error),
+}
+
+/// Converts values of a contract between JSON and XDR.
+#[derive(Clone, Debug, Default)]
+pub struct Converter {
+    entries: Vec<ScSpecEntry>,
+}
+
+impl Converter {
+    /// Create a converter with the spec entries of a contract. Values of generic
+    /// types are converted as the instances of the types the contract uses.
+    pub fn new(entries: Vec<ScSpecEntry>) -> Self {
+        Self {
+            entries: instantiate(&entries),
+        }
+    }
+
+    /// Create a converter with the spec entries read from the contract wasm.
+    pub fn from_wasm(wasm: &[u8]) -> Result<Self, FromWasmError> {
+        Ok(Self::new(from_wasm(wasm)?))
+    }
+
+    /// Returns the function with the name.
+    pub fn function(&self, name: &str) -> Result<&ScSpecFunctionV0, Error> {
+        self.entries
+            .iter()
+            .find_map(|e| match e {
+                ScSpecEntry::FunctionV0(f) if f.name.to_string_lossy() == name => Some(f),
+                _ => None,
+            })
+            .ok_or_else(|| Error::FunctionNotFound {
+                name: name.to_string(),
+            })
+    }
+
+    /// Convert a JSON object containing a property for each input of the
+    /// function into the arguments to invoke the function with.
+    pub fn args_from_json(&self, f: &ScSpecFunctionV0, args: &Value) -> Result<ScVec, Error> {
+        let args = match args {
+            Value::Object(args) => args,
+            _ => {
+                return Err(Error::InvalidArguments {
+                    found: args.clone(),
+                })
+            }
+        };
+        if let Some(name) = args
+            .keys()
+            .find(|k| !f.inputs.iter().any(|i| i.name.to_string_lossy() == **k))
+        {
+            return Err(Error::UnknownArgument { name: name.clone() });
+        }
+        let vals = f
+            .inputs
+            .iter()
+            .map(|i| {
+                let name = i.name.to_string_lossy();
+                let arg = args
+                    .get(&name)
+                    .ok_or_else(|| Error::MissingArgument { name: name.clone() })?;
+                self.from_json(&i.type_, arg).map_err(|e| Error::Argument {
+                    name,
+                    error: Box::new(e),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ScVec(vals.try_into().map_err(Error::Xdr)?))
+    }
+
+    /// Convert the value returned by the function into JSON.
+    pub fn output_to_json(&self, f: &ScSpecFunctionV0, val: &ScVal) -> Result<Value, Error> {
+        match f.outputs.first() {
+            Some(type_) => self.to_json(type_, val),
+            None => match val {
+                ScVal::Static(ScStatic::Void) => Ok(Value::Null),
+                _ => Err(Error::InvalidVal {
+                    type_name: "()".to_string(),
+                    found: val.clone(),
+                }),
+            },
+        }
+    }
+
+    /// Convert the JSON into a value of the type.
+    pub fn from_json(&self, type_: &ScSpecTypeDef, json: &Value) -> Result<ScVal, Error> {
+        let invalid = || Error::InvalidJson {
+            type_name: type_name(type_),
+            found: json.clone(),
+        };
+        let val = match (type_, json) {
+            (ScSpecTypeDef::Bool, Value::Bool(true)) => ScVal::Static(ScStatic::True),
+            (ScSpecTypeDef::Bool, Value::Bool(false)) => ScVal::Static(ScStatic::False),
+            (ScSpecTypeDef::U32, Value::Number(n)) => {
+                let n = n.as_u64().and_then(|n| n.try_into().ok());
+                ScVal::U32(n.ok_or_else(invalid)?)
+            }
+            (ScSpecTypeDef::I32, Value::Number(n)) => {
+                let n = n.as_i64().and_then(|n| n.try_into().ok());
+                ScVal::I32(n.ok_or_else(invalid)?)
+            }
+            // Contracts convert only objects into u64, even for values that fit
+            // in a U63.
+            (ScSpecTypeDef::U64, Value::Number(n)) => {
+                ScVal::Object(Some(ScObject::U64(n.as_u64().ok_or_else(invalid)?)))
+            }
+            (ScSpecTypeDef::I64, Value::Number(n)) => match n.as_i64().ok_or_else(invalid)? {
+                n if n >= 0 => ScVal::U63(n),
+                n => ScVal::Object(Some(ScObject::I64(n))),
+            },
+            (ScSpecTypeDef::Bitset, Value::Number(n)) => {
+                ScVal::Bitset(n.as_u64().ok_or_else(invalid)?)
+            }
+            (ScSpecTypeDef::BigInt, Value::Number(n)) => {
+                let big = match (n.as_u64(), n.as_i64()) {
+                    (Some(n), _) => big_int(false, &n.to_string()),
+                    (None, Some(n)) => big_int(true, &n.unsigned_abs().to_string()),
+                    (None, None) => None,
+                };
+                ScVal::Object(Some(ScObject::BigInt(big.ok_or_else(invalid)??)))
+            }
+            (ScSpecTypeDef::BigInt, Value::String(s)) => {
+                let big = match s.strip_prefix('-') {
+                    Some(s) => big_int(true, s),
+                    None => big_int(false, s),
+                };
+                ScVal::Object(Some(ScObject::BigInt(big.ok_or_else(invalid)??)))
+            }
+            (ScSpecTypeDef::Symbol, Value::String(s)) => {
+                ScVal::Symbol(s.as_str().try_into().map_err(Error::Xdr)?)
+            }
+            (ScSpecTypeDef::Bytes, Value::String(s)) => {
+                let bytes = from_hex(s).ok_or_else(invalid)?;
+                ScVal::Object(Some(ScObject::Bytes(bytes.try_into().map_err(Error::Xdr)?)))
+            }
+            (ScSpecTypeDef::BytesN(b), Value::String(s)) => {
+                let bytes = from_hex(s)
+                    .filter(|bytes| bytes.len() == b.n as usize)
+                    .ok_or_else(invalid)?;
+                ScVal::Object(Some(ScObject::Bytes(bytes.try_into().map_err(Error::Xdr)?)))
+            }
+            (ScSpecTypeDef::Option(_), Value::Null) => ScVal::Static(ScStatic::Void),
+            (ScSpecTypeDef::Option(o), json) => self.from_json(&o.value_type, json)?,
+            (ScSpecTypeDef::Result(r), Value::Object(o)) if o.len() == 1 => match o.iter().next() {
+                Some((k, v)) if k == "ok" => self.from_json(&r.ok_type, v)?,
+                Some((k, v)) if k == "error" => self.from_json(&r.error_type, v)?,
+                _ => return Err(invalid()),
+            },
+            (ScSpecTypeDef::Vec(v), Value::Array(items)) => vec(items
+                .iter()
+                .map(|i| self.from_json(&v.element_type, i))
+                .collect::<Result<Vec<_>, _>>()?)?,
+            (ScSpecTypeDef::Set(s), Value::Array(items)) => {
+                let mut entries = items
+                    .iter()
+                    .map(|i| {
+                        Ok(ScMapEntry {
+                            key: self.from_json(&s.element_type, i)?,
+                            val: ScVal::Static(ScStatic::Void),
+                        })
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+                entries.sort_by(|a, b| cmp_val(&a.key, &b.key));
+                entries.dedup_by(|a, b| a.key == b.key);
+                map(entries)?
+            }
+            (ScSpecTypeDef::Map(m), Value::Object(o)) if *m.key_type == ScSpecTypeDef::Symbol => {
+                map(o
+                    .iter()
+                    .map(|(k, v)| {
+                        Ok(ScMapEntry {
+                            key: self.from_json(&m.key_type, &json!(k))?,
+                            val: self.from_json(&m.value_type, v)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?)?
+            }
+            (ScSpecTypeDef::Map(m), Value::Array(pairs)) => map(pairs
+                .iter()
+                .map(|p| match p.as_array().map(Vec::as_slice) {
+                    Some([k, v]) => Ok(ScMapEntry {
+                        key: self.from_json(&m.key_type, k)?,
+                        val: self.from_json(&m.value_type, v)?,
+                    }),
+                    _ => Err(invalid()),
+                })
+                .collect::<Result<Vec<_>, _>>()?)?,
+            (ScSpecTypeDef::Tuple(t), Value::Array(items))
+                if t.value_types.len() == items.len() =>
+            {
+                vec(t
+                    .value_types
+                    .iter()
+                    .zip(items)
+                    .map(|(t, i)| self.from_json(t, i))
+                    .collect::<Result<Vec<_>, _>>()?)?
+            }
+            (ScSpecTypeDef::Udt(udt), json) => {
+                self.udt_from_json(&udt.name.to_string_lossy(), json)?
+            }
+            (
+                ScSpecTypeDef::Val
+                | ScSpecTypeDef::Status
+                | ScSpecTypeDef::Invoker
+                | ScSpecTypeDef::AccountId,
+                _,
+            ) => {
+                return Err(Error::UnsupportedType {
+                    type_name: type_name(type_),
+                })
+            }
+            _ => return Err(invalid()),
+        };
+        Ok(val)
+    }
+
+    fn udt_from_json(&self, name: &str, json: &Value) -> Result<ScVal, Error> {
+        let invalid = || Error::InvalidJson {
+            type_name: name.to_string(),
+            found: json.clone(),
+        };
+        let val = match (self.udt(name)?, json) {
+            // Transparent structs are the value of their single field.
+            (ScSpecEntry::UdtStructV0(s), v) if struct_alias(s).is_some() => {
+                self.from_json(struct_alias(s).ok_or_else(invalid)?, v)?
+            }
+            // Unit structs are void.
+            (ScSpecEntry::UdtStructV0(s), Value::Null) if s.fields.is_empty() => {
+                ScVal::Static(ScStatic::Void)
+            }
+            // Structs with named fields are maps from field name to value.
+            (ScSpecEntry::UdtStructV0(s), Value::Object(o)) if !is_tuple(s) => {
+                if o.keys()
+                    .any(|k| !s.fields.iter().any(|f| f.name.to_string_lossy() == *k))
+                {
+                    return Err(invalid());
+                }
+                let entries = s
+                    .fields
+                    .iter()
+                    .map(|f| {
+                        let field_name = f.name.to_string_lossy();
+                        let v = o.get(&field_name).ok_or_else(invalid)?;
+                        Ok(ScMapEntry {
+                            key: ScVal::Symbol(field_name.as_str().try_into().map_err(Error::Xdr)?),
+                            val: self.from_json(&f.type_, v)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+                map(entries)?
+            }
+            // Structs with unnamed fields are vecs of the field values.
+            (ScSpecEntry::UdtStructV0(s), Value::Array(items))
+                if is_tuple(s) && s.fields.len() == items.len() =>
+            {
+                vec(s
+                    .fields
+                    .iter()
+                    .zip(items)
+                    .map(|(f, i)| self.from_json(&f.type_, i))
+                    .collect::<Result<Vec<_>, _>>()?)?
+            }
+            // Unions are vecs of the case name followed by the case value.
+            (ScSpecEntry::UdtUnionV0(u), Value::String(case_name)) => {
+                u.cases
+                    .iter()
+                    .find(|c| c.name.to_string_lossy() == *case_name && c.type_.is_none())
+                    .ok_or_else(invalid)?;
+                vec(vec![ScVal::Symbol(
+                    case_name.as_str().try_into().map_err(Error::Xdr)?,
+                )])?
+            }
+            (ScSpecEntry::UdtUnionV0(u), Value::Object(o)) if o.len() == 1 => {
+                let (case_name, v) = o.iter().next().ok_or_else(invalid)?;
+                let type_ = u
+                    .cases
+                    .iter()
+                    .find(|c| c.name.to_string_lossy() == *case_name)
+                    .and_then(|c| c.type_.as_ref())
+                    .ok_or_else(invalid)?;
+                vec(vec![
+                    ScVal::Symbol(case_name.as_str().try_into().map_err(Error::Xdr)?),
+                    self.from_json(type_, v)?,
+                ])?
+            }
+            // Enums are u32 values.
+            (ScSpecEntry::UdtEnumV0(e), Value::String(case_name)) => {
+                let case = e
+                    .cases
+                    .iter()
+                    .find(|c| c.name.to_string_lossy() == *case_name)
+                    .ok_or_else(invalid)?;
+                ScVal::U32(case.value)
+            }
+            // Error enums are contract error statuses.
+            (ScSpecEntry::UdtErrorEnumV0(e), Value::String(case_name)) => {
+                let case = e
+                    .cases
+                    .iter()
+                    .find(|c| c.name.to_string_lossy() == *case_name)
+                    .ok_or_else(invalid)?;
+                ScVal::Status(ScStatus::ContractError(case.value))
+            }
+            _ => return Err(invalid()),
+        };
+        Ok(val)
+    }
+
+    /// Convert the value of the type into JSON.
+    pub fn to_json(&self, type_: &ScSpecTypeDef, val: &ScVal) -> Result<Value, Error> {
+        let invalid = || Error::InvalidVal {
+            type_name: type_name(type_),
+            found: val.clone(),
+        };
+        let json = match (type_, val) {
+            (ScSpecTypeDef::Bool, ScVal::Static(ScStatic::True)) => json!(true),
+            (ScSpecTypeDef::Bool, ScVal::Static(ScStatic::False)) => json!(false),
+            (ScSpecTypeDef::U32, ScVal::U32(v)) => json!(v),
+            (ScSpecTypeDef::I32, ScVal::I32(v)) => json!(v),
+            (ScSpecTypeDef::I64, ScVal::U63(v)) => json!(v),
+            (ScSpecTypeDef::U64, ScVal::Object(Some(ScObject::U64(v)))) => json!(v),
+            (ScSpecTypeDef::I64, ScVal::Object(Some(ScObject::I64(v)))) => json!(v),
+            (ScSpecTypeDef::Bitset, ScVal::Bitset(v)) => json!(v),
+            (ScSpecTypeDef::BigInt, ScVal::Object(Some(ScObject::BigInt(v)))) => match v {
+                ScBigInt::Zero => json!("0"),
+                ScBigInt::Positive(m) => json!(decimal(m.as_slice())),
+                ScBigInt::Negative(m) => json!(format!("-{}", decimal(m.as_slice()))),
+            },
+            (ScSpecTypeDef::Symbol, ScVal::Symbol(v)) => json!(v.to_string_lossy()),
+            (ScSpecTypeDef::Bytes, ScVal::Object(Some(ScObject::Bytes(v)))) => {
+                json!(hex(v.as_slice()))
+            }
+            (ScSpecTypeDef::BytesN(b), ScVal::Object(Some(ScObject::Bytes(v))))
+                if v.as_slice().len() == b.n as usize =>
+            {
+                json!(hex(v.as_slice()))
+            }
+            (ScSpecTypeDef::Option(_), ScVal::Static(ScStatic::Void)) => Value::Null,
+            (ScSpecTypeDef::Option(o), v) => self.to_json(&o.value_type, v)?,
+            (ScSpecTypeDef::Result(r), v @ ScVal::Status(_)) => {
+                json!({ "error": self.to_json(&r.error_type, v)? })
+            }
+            (ScSpecTypeDef::Result(r), v) => json!({ "ok": self.to_json(&r.ok_type, v)? }),
+            (ScSpecTypeDef::Vec(t), ScVal::Object(Some(ScObject::Vec(items)))) => Value::Array(
+                items
+                    .iter()
+                    .map(|v| self.to_json(&t.element_type, v))
+                    .collect::<Result<_, _>>()?,
+            ),
+            (ScSpecTypeDef::Set(t), ScVal::Object(Some(ScObject::Map(entries)))) => Value::Array(
+                entries
+                    .iter()
+                    .map(|e| self.to_json(&t.element_type, &e.key))
+                    .collect::<Result<_, _>>()?,
+            ),
+            (ScSpecTypeDef::Map(t), ScVal::Object(Some(ScObject::Map(entries))))
+                if *t.key_type == ScSpecTypeDef::Symbol =>
+            {
+                Value::Object(
+                    entries
+                        .iter()
+                        .map(|e| match &e.key {
+                            ScVal::Symbol(k) => {
+                                Ok((k.to_string_lossy(), self.to_json(&t.value_type, &e.val)?))
+                            }
+                            _ => Err(invalid()),
+                        })
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            (ScSpecTypeDef::Map(t), ScVal::Object(Some(ScObject::Map(entries)))) => Value::Array(
+                entries
+                    .iter()
+                    .map(|e| {
+                        Ok(json!([
+                            self.to_json(&t.key_type, &e.key)?,
+                            self.to_json(&t.value_type, &e.val)?,
+                        ]))
+                    })
+                    .collect::<Result<_, Error>>()?,
+            ),
+            (ScSpecTypeDef::Tuple(t), ScVal::Object(Some(ScObject::Vec(items))))
+                if t.value_types.len() == items.len() =>
+            {
+                Value::Array(
+                    t.value_types
+                        .iter()
+                        .zip(items.iter())
+                        .map(|(t, v)| self.to_json(t, v))
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            (ScSpecTypeDef::Udt(udt), v) => self.udt_to_json(&udt.name.to_string_lossy(), v)?,
+            (
+                ScSpecTypeDef::Val
+                | ScSpecTypeDef::Status
+                | ScSpecTypeDef::Invoker
+                | ScSpecTypeDef::AccountId,
+                _,
+            ) => {
+                return Err(Error::UnsupportedType {
+                    type_name: type_name(type_),
+                })
+            }
+            _ => return Err(invalid()),
+        };
+        Ok(json)
+    }
+
+    fn udt_to_json(&self, name: &str, val: &ScVal) -> Result<Value, Error> {
+        let invalid = || Error::InvalidVal {
+            type_name: name.to_string(),
+            found: val.clone(),
+        };
+        let json = match (self.udt(name)?, val) {
+            (ScSpecEntry::UdtStructV0(s), v) if struct_alias(s).is_some() => {
+                self.to_json(struct_alias(s).ok_or_else(invalid)?, v)?
+            }
+            (ScSpecEntry::UdtStructV0(s), ScVal::Static(ScStatic::Void)) if s.fields.is_empty() => {
+                Value::Null
+            }
+            (ScSpecEntry::UdtStructV0(s), ScVal::Object(Some(ScObject::Map(entries)))) => {
+                let mut o = Map::new();
+                for f in s.fields.iter() {
+                    let field_name = f.name.to_string_lossy();
+                    let e = entries
+                        .iter()
+                        .find(|e| {
+                            matches!(&e.key, ScVal::Symbol(k) if k.to_string_lossy() == field_name)
+                        })
+                        .ok_or_else(invalid)?;
+                    o.insert(field_name, self.to_json(&f.type_, &e.val)?);
+                }
+                Value::Object(o)
+            }
+            (ScSpecEntry::UdtStructV0(s), ScVal::Object(Some(ScObject::Vec(items))))
+                if s.fields.len() == items.len() =>
+            {
+                Value::Array(
+                    s.fields
+                        .iter()
+                        .zip(items.iter())
+                        .map(|(f, v)| self.to_json(&f.type_, v))
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            (ScSpecEntry::UdtUnionV0(u), ScVal::Object(Some(ScObject::Vec(items)))) => {
+                let (case_name, values) = match items.split_first() {
+                    Some((ScVal::Symbol(case_name), values)) => {
+                        (case_name.to_string_lossy(), values)
+                    }
+                    _ => return Err(invalid()),
+                };
+                let case = u
+                    .cases
+                    .iter()
+                    .find(|c| c.name.to_string_lossy() == case_name)
+                    .ok_or_else(invalid)?;
+                match (&case.type_, values) {
+                    (None, []) => json!(case_name),
+                    (Some(type_), [v]) => json!({ case_name: self.to_json(type_, v)? }),
+                    _ => return Err(invalid()),
+                }
+            }
+            (ScSpecEntry::UdtEnumV0(e), ScVal::U32(value)) => {
+                let case = e
+                    .cases
+                    .iter()
+                    .find(|c| c.value == *value)
+                    .ok_or_else(invalid)?;
+                json!(case.name.to_string_lossy())
+            }
+            (
+                ScSpecEntry::UdtErrorEnumV0(e),
+                ScVal::Status(ScStatus::ContractError(value)) | ScVal::U32(value),
+            ) => {
+                let case = e
+                    .cases
+                    .iter()
+                    .find(|c| c.value == *value)
+                    .ok_or_else(invalid)?;
+                json!(case.name.to_string_lossy())
+            }
+            _ => return Err(invalid()),
+        };
+        Ok(json)
+    }
+
+    /// Returns the user defined type with the name.
+    fn udt(&self, name: &str) -> Result<&ScSpecEntry, Error> {
+        self.entries
+            .iter()
+            .find(|e| match e {
+                ScSpecEntry::UdtStructV0(s) => s.name.to_string_lossy() == name,
+                ScSpecEntry::UdtUnionV0(u) => u.name.to_string_lossy() == name,
+                ScSpecEntry::UdtEnumV0(e) => e.name.to_string_lossy() == name,
+                ScSpecEntry::UdtErrorEnumV0(e) => e.name.to_string_lossy() == name,
+                ScSpecEntry::FunctionV0(_) => false,
+            })
+            .ok_or_else(|| Error::TypeNotFound {
+                name: name.to_string(),
+            })
+    }
+}
+
+/// Returns true if the struct has unnamed fields, which are named after their
+/// position in the spec.
+fn is_tuple(s: &ScSpecUdtStructV0) -> bool {
+    s.fields
+        .iter()
+        .all(|f| f.name.to_string_lossy().parse::<usize>().is_ok())
+}
+
+fn vec(items: Vec<ScVal>) -> Result<ScVal, Error> {
+    Ok(ScVal::Object(Some(ScObject::Vec(ScVec(
+        items.try_into().map_err(Error::Xdr)?,
+    )))))
+}
+
+/// Returns a map of the entries, ordered by key as the host requires, or an
+/// error if a key is repeated.
+fn map(mut entries: Vec<ScMapEntry>) -> Result<ScVal, Error> {
+    entries.sort_by(|a, b| cmp_val(&a.key, &b.key));
+    if let Some(w) = entries.windows(2).find(|w| w[0].key == w[1].key) {
+        return Err(Error::DuplicateKey {
+            key: w[0].key.clone(),
+        });
+    }
+    Ok(ScVal::Object(Some(ScObject::Map(ScMap(
+        entries.try_into().map_err(Error::Xdr)?,
+    )))))
+}
+
+/// Compares the values in the order the host orders the keys of maps, the same
+/// as the generated TypeScript and Python compare them: by the type of value,
+/// then by the value for numbers, symbols, and bytes, and otherwise by the XDR
+/// encoding of the values.
+fn cmp_val(a: &ScVal, b: &ScVal) -> Ordering {
+    let by_type = (a.discriminant() as i32).cmp(&(b.discriminant() as i32));
+    if by_type != Ordering::Equal {
+        return by_type;
+    }
+    match (a, b) {
+        (ScVal::U63(a), ScVal::U63(b)) => a.cmp(b),
+        (ScVal::U32(a), ScVal::U32(b)) => a.cmp(b),
+        (ScVal::I32(a), ScVal::I32(b)) => a.cmp(b),
+        (ScVal::Symbol(a), ScVal::Symbol(b)) => a.as_slice().cmp(b.as_slice()),
+        (ScVal::Object(Some(ao)), ScVal::Object(Some(bo))) => {
+            let by_obj_type = (ao.discriminant() as i32).cmp(&(bo.discriminant() as i32));
+            if by_obj_type != Ordering::Equal {
+                return by_obj_type;
+            }
+            match (ao, bo) {
+                (ScObject::Bytes(a), ScObject::Bytes(b)) => a.as_slice().cmp(b.as_slice()),
+                (ScObject::U64(a), ScObject::U64(b)) => a.cmp(b),
+                (ScObject::I64(a), ScObject::I64(b)) => a.cmp(b),
+                _ => a.to_xdr().ok().cmp(&b.to_xdr().ok()),
+            }
+        }
+        _ => a.to_xdr().ok().cmp(&b.to_xdr().ok()),
+    }
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Converts the decimal string into a big integer, or returns [None] if the
+/// string is not a decimal number.
+fn big_int(negative: bool, digits: &str) -> Option<Result<ScBigInt, Error>> {
+    if digits.is_empty() || !digits.bytes().all(|d| d.is_ascii_digit()) {
+        return None;
+    }
+    // Big-endian magnitude.
+    let mut magnitude = Vec::<u8>::new();
+    for d in digits.bytes() {
+        let mut carry = u32::from(d - b'0');
+        for b in magnitude.iter_mut().rev() {
+            let cur = u32::from(*b) * 10 + carry;
+            *b = cur as u8;
+            carry = cur >> 8;
+        }
+        if carry > 0 {
+            magnitude.insert(0, carry as u8);
+        }
+    }
+    if magnitude.is_empty() {
+        return Some(Ok(ScBigInt::Zero));
+    }
+    let magnitude = match magnitude.try_into() {
+        Ok(m) => m,
+        Err(e) => return Some(Err(Error::Xdr(e))),
+    };
+    Some(Ok(if negative {
+        ScBigInt::Negative(magnitude)
+    } else {
+        ScBigInt::Positive(magnitude)
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use stellar_xdr::{
+        ScMap, ScMapEntry, ScObject, ScSpecTypeDef, ScSpecTypeMap, ScSpecTypeSet, ScStatic, ScVal,
+        ScVec,
+    };
+
+    use super::{Converter, Error};
+
+    const EXAMPLE_WASM: &[u8] =
+        include_bytes!("../../target/wasm32-unknown-unknown/release/test_udt.wasm");
+
+    fn sym(s: &str) -> ScVal {
+        ScVal::Symbol(s.try_into().unwrap())
+    }
+
+    fn vec(items: Vec<ScVal>) -> ScVal {
+        ScVal::Object(Some(ScObject::Vec(ScVec(items.try_into().unwrap()))))
+    }
+
+    #[test]
+    fn test_args() {
+        let c = Converter::from_wasm(EXAMPLE_WASM).unwrap();
+        let f = c.function("add").unwrap();
+        let args = json!({
+            "a": "UdtA",
+            "b": { "UdtB": { "a": 10, "b": -12, "c": [1] } },
+        });
+        let vals = c.args_from_json(f, &args).unwrap();
+        assert_eq!(
+            vals,
+            ScVec(
+                vec![
+                    vec(vec![sym("UdtA")]),
+                    vec(vec![
+                        sym("UdtB"),
+                        ScVal::Object(Some(ScObject::Map(ScMap(
+                            vec![
+                                ScMapEntry {
+                                    key: sym("a"),
+                                    val: ScVal::U63(10),
+                                },
+                                ScMapEntry {
+                                    key: sym("b"),
+                                    val: ScVal::Object(Some(ScObject::I64(-12))),
+                                },
+                                ScMapEntry {
+                                    key: sym("c"),
+                                    val: vec(vec![ScVal::U63(1)]),
+                                },
+                            ]
+                            .try_into()
+                            .unwrap(),
+                        )))),
+                    ]),
+                ]
+                .try_into()
+                .unwrap()
+            )
+        );
+        // Arguments convert back into the same JSON.
+        let json = f
+            .inputs
+            .iter()
+            .zip(vals.iter())
+            .map(|(i, v)| c.to_json(&i.type_, v).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(json, vec![args["a"].clone(), args["b"].clone()]);
+        assert_eq!(c.output_to_json(f, &ScVal::U63(22)).unwrap(), json!(22));
+    }
+
+    #[test]
+    fn test_errors() {
+        let c = Converter::from_wasm(EXAMPLE_WASM).unwrap();
+        let f = c.function("add").unwrap();
+        assert!(matches!(
+            c.args_from_json(f, &json!({ "a": "UdtA" })),
+            Err(Error::MissingArgument { name }) if name == "b"
+        ));
+        assert!(matches!(
+            c.args_from_json(f, &json!({ "a": "UdtA", "b": "UdtE" })),
+            Err(Error::Argument { name, error }) if name == "b"
+                && matches!(*error, Error::InvalidJson { .. })
+        ));
+        assert!(matches!(
+            c.from_json(&ScSpecTypeDef::U32, &json!(-1)),
+            Err(Error::InvalidJson { .. })
+        ));
+        assert_eq!(
+            c.from_json(&ScSpecTypeDef::BigInt, &json!("-18446744073709551616"))
+                .unwrap(),
+            ScVal::Object(Some(ScObject::BigInt(stellar_xdr::ScBigInt::Negative(
+                vec![1, 0, 0, 0, 0, 0, 0, 0, 0].try_into().unwrap()
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_sets_and_maps_ordered() {
+        let c = Converter::default();
+        let set = ScSpecTypeDef::Set(Box::new(ScSpecTypeSet {
+            element_type: Box::new(ScSpecTypeDef::U32),
+        }));
+        let void = || ScVal::Static(ScStatic::Void);
+        assert_eq!(
+            c.from_json(&set, &json!([3, 1, 3, 2])).unwrap(),
+            ScVal::Object(Some(ScObject::Map(ScMap(
+                vec![
+                    ScMapEntry {
+                        key: ScVal::U32(1),
+                        val: void(),
+                    },
+                    ScMapEntry {
+                        key: ScVal::U32(2),
+                        val: void(),
+                    },
+                    ScMapEntry {
+                        key: ScVal::U32(3),
+                        val: void(),
+                    },
+                ]
+                .try_into()
+                .unwrap()
+            ))))
+        );
+
+        let map = ScSpecTypeDef::Map(Box::new(ScSpecTypeMap {
+            key_type: Box::new(ScSpecTypeDef::U64),
+            value_type: Box::new(ScSpecTypeDef::U32),
+        }));
+        let obj_u64 = |v| ScVal::Object(Some(ScObject::U64(v)));
+        assert_eq!(
+            c.from_json(&map, &json!([[u64::MAX, 1], [5, 2]])).unwrap(),
+            ScVal::Object(Some(ScObject::Map(ScMap(
+                vec![
+                    ScMapEntry {
+                        key: obj_u64(5),
+                        val: ScVal::U32(2),
+                    },
+                    ScMapEntry {
+                        key: obj_u64(u64::MAX),
+                        val: ScVal::U32(1),
+                    },
+                ]
+                .try_into()
+                .unwrap()
+            ))))
+        );
+        assert!(matches!(
+            c.from_json(&map, &json!([[5, 1], [5, 2]])),
+            Err(Error::DuplicateKey { key }) if key == obj_u64(5)
+        ));
+    }
+}
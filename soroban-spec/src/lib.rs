@@ -1,2 +1,9 @@
+pub mod compat;
+pub mod docs;
+pub mod fmt;
 pub mod gen;
+pub mod generic;
+pub mod json;
+pub mod meta;
 pub mod read;
+pub mod validate;
@@ -0,0 +1,57 @@
+use stellar_xdr::ScEnvMetaEntry;
+
+use crate::read::{meta_from_wasm, FromWasmError};
+
+#[derive(thiserror::Error, Debug)]
+pub enum InterfaceVersionError {
+    #[error("getting contract env meta: {0}")]
+    GetMeta(FromWasmError),
+    #[error(
+        "contract was built for env interface version {contract}, which is not compatible \
+        with env interface version {env}, rebuild the contract with a compatible soroban-sdk"
+    )]
+    Incompatible { contract: u64, env: u64 },
+}
+
+/// Returns the env interface version the contract was built for, or [None] if
+/// the contract does not declare one.
+pub fn interface_version_from_wasm(wasm: &[u8]) -> Result<Option<u64>, FromWasmError> {
+    let meta = meta_from_wasm(wasm)?;
+    Ok(meta.iter().find_map(|m| match m {
+        ScEnvMetaEntry::ScEnvMetaKindInterfaceVersion(v) => Some(*v),
+    }))
+}
+
+/// Checks that the contract was built for an env interface version that is
+/// compatible with the env interface version, which is typically
+/// `soroban_sdk::meta::INTERFACE_VERSION`.
+///
+/// Interface versions are only compatible if they are equal. Contracts that do
+/// not declare an interface version are assumed to be compatible.
+pub fn check_interface_version(wasm: &[u8], env: u64) -> Result<(), InterfaceVersionError> {
+    match interface_version_from_wasm(wasm).map_err(InterfaceVersionError::GetMeta)? {
+        Some(contract) if contract != env => {
+            Err(InterfaceVersionError::Incompatible { contract, env })
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_interface_version, interface_version_from_wasm, InterfaceVersionError};
+
+    const EXAMPLE_WASM: &[u8] =
+        include_bytes!("../../target/wasm32-unknown-unknown/release/test_udt.wasm");
+
+    #[test]
+    fn test_check_interface_version() {
+        let version = interface_version_from_wasm(EXAMPLE_WASM).unwrap().unwrap();
+        assert!(check_interface_version(EXAMPLE_WASM, version).is_ok());
+        assert!(matches!(
+            check_interface_version(EXAMPLE_WASM, version + 1),
+            Err(InterfaceVersionError::Incompatible { contract, env })
+                if contract == version && env == version + 1
+        ));
+    }
+}
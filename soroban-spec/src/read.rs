@@ -1,8 +1,10 @@
 use std::io::Cursor;
 
-use stellar_xdr::{self, ReadXdr, ScSpecEntry};
+use stellar_xdr::{self, ReadXdr, ScEnvMetaEntry, ScSpecEntry};
 use wasmparser::{BinaryReaderError, Parser, Payload};
 
+use crate::docs::Docs;
+
 // TODO: Move these functions into stellar_xdr.
 
 #[derive(thiserror::Error, Debug)]
@@ -34,16 +36,20 @@ pub enum FromWasmError {
     NotFound,
 }
 
-pub fn raw_from_wasm(wasm: &[u8]) -> Result<Vec<u8>, FromWasmError> {
+fn custom_section_from_wasm(wasm: &[u8], name: &str) -> Result<Option<Vec<u8>>, FromWasmError> {
     for payload in Parser::new(0).parse_all(wasm) {
         let payload = payload.map_err(FromWasmError::Read)?;
         if let Payload::CustomSection(section) = payload {
-            if section.name() == "contractspecv0" {
-                return Ok(section.data().to_vec());
+            if section.name() == name {
+                return Ok(Some(section.data().to_vec()));
             }
         };
     }
-    Err(FromWasmError::NotFound)
+    Ok(None)
+}
+
+pub fn raw_from_wasm(wasm: &[u8]) -> Result<Vec<u8>, FromWasmError> {
+    custom_section_from_wasm(wasm, "contractspecv0")?.ok_or(FromWasmError::NotFound)
 }
 
 pub fn base64_from_wasm(wasm: &[u8]) -> Result<String, FromWasmError> {
@@ -55,3 +61,31 @@ pub fn from_wasm(wasm: &[u8]) -> Result<Vec<ScSpecEntry>, FromWasmError> {
     let spec = raw_from_wasm(wasm)?;
     parse_raw(&spec).map_err(FromWasmError::Parse)
 }
+
+/// Returns the raw doc strings of the contract, or empty if the contract does
+/// not have any doc strings.
+pub fn docs_raw_from_wasm(wasm: &[u8]) -> Result<Vec<u8>, FromWasmError> {
+    Ok(custom_section_from_wasm(wasm, "contractdocsv0")?.unwrap_or_default())
+}
+
+/// Returns the doc strings of the functions and types of the contract.
+pub fn docs_from_wasm(wasm: &[u8]) -> Result<Docs, FromWasmError> {
+    let docs = docs_raw_from_wasm(wasm)?;
+    Docs::from_xdr(&docs).map_err(FromWasmError::Parse)
+}
+
+/// Returns the raw env meta of the contract, or empty if the contract does not
+/// have any env meta.
+pub fn meta_raw_from_wasm(wasm: &[u8]) -> Result<Vec<u8>, FromWasmError> {
+    Ok(custom_section_from_wasm(wasm, "contractenvmetav0")?.unwrap_or_default())
+}
+
+/// Returns the env meta of the contract, describing the environment the
+/// contract was built to run in.
+pub fn meta_from_wasm(wasm: &[u8]) -> Result<Vec<ScEnvMetaEntry>, FromWasmError> {
+    let meta = meta_raw_from_wasm(wasm)?;
+    let mut cursor = Cursor::new(meta);
+    ScEnvMetaEntry::read_xdr_iter(&mut cursor)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(FromWasmError::Parse)
+}
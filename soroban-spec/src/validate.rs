@@ -0,0 +1,435 @@
+//! Validate that the entries of a contract spec are consistent with each
+//! other, so that bindings can be generated for the contract.
+
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+use stellar_xdr::{ScSpecEntry, ScSpecTypeDef};
+
+use crate::{
+    gen::type_name,
+    generic::{instantiate, type_params, UdtName},
+};
+
+/// A problem found in a contract spec.
+///
+/// Paths are the name of the function or type, followed by the name of the
+/// input, field, or case separated by a `.`, e.g. `add.a` or `UdtStruct.c`.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    #[error("{name}: defined more than once")]
+    DuplicateName { name: String },
+    #[error("{path}: defined more than once")]
+    DuplicateMember { path: String },
+    #[error("{path}: value {value} is already used by another case")]
+    DuplicateValue { path: String, value: u32 },
+    #[error("{path}: type {name} is not defined")]
+    UndefinedType { path: String, name: String },
+    #[error("{path}: type {name} does not have {expected} type arguments")]
+    TypeArguments {
+        path: String,
+        name: String,
+        expected: usize,
+    },
+    #[error("{path}: Result error type {type_name} is not an error enum or Status")]
+    ResultErrorType { path: String, type_name: String },
+    #[error("{name}: type contains itself other than within a Vec, Map, or Set, and cannot be represented")]
+    RecursiveType { name: String },
+}
+
+/// Formats the diagnostics, one per line.
+pub fn fmt_diagnostics(diagnostics: &[Diagnostic]) -> String {
+    diagnostics.iter().join("\n")
+}
+
+/// Validates the contract spec, returning all the problems found.
+///
+/// Checks that:
+/// - Functions and types are uniquely named.
+/// - Inputs, fields, and cases are uniquely named within their function or
+/// type.
+/// - Enum and error enum cases have unique values.
+/// - Types referenced by name are defined in the spec, and generic types are
+/// given an argument for each of their type parameters.
+/// - Error types of `Result`s are error enums or `Status`.
+/// - Types do not contain themselves, other than within a `Vec`, `Map`, or
+/// `Set`, which would make them infinitely sized.
+pub fn validate(specs: &[ScSpecEntry]) -> Result<(), Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+
+    // Functions and types are in separate namespaces.
+    let mut fn_names = HashSet::new();
+    let mut types = HashMap::new();
+    for s in specs {
+        let (name, unique) = match s {
+            ScSpecEntry::FunctionV0(f) => {
+                let name = f.name.to_string_lossy();
+                (name.clone(), fn_names.insert(name))
+            }
+            ScSpecEntry::UdtStructV0(st) => udt_name(&mut types, st.name.to_string_lossy(), s),
+            ScSpecEntry::UdtUnionV0(u) => udt_name(&mut types, u.name.to_string_lossy(), s),
+            ScSpecEntry::UdtEnumV0(e) => udt_name(&mut types, e.name.to_string_lossy(), s),
+            ScSpecEntry::UdtErrorEnumV0(e) => udt_name(&mut types, e.name.to_string_lossy(), s),
+        };
+        if !unique {
+            diagnostics.push(Diagnostic::DuplicateName { name });
+        }
+    }
+
+    let v = Validator { types: &types };
+    for spec in specs {
+        match spec {
+            ScSpecEntry::FunctionV0(f) => {
+                let name = f.name.to_string_lossy();
+                let inputs = f.inputs.iter().map(|i| i.name.to_string_lossy());
+                v.unique_members(&mut diagnostics, &name, inputs);
+                for i in f.inputs.iter() {
+                    let path = format!("{name}.{}", i.name.to_string_lossy());
+                    v.check_type(&mut diagnostics, &path, &[], &i.type_);
+                }
+                for o in f.outputs.iter() {
+                    v.check_type(&mut diagnostics, &name, &[], o);
+                }
+            }
+            ScSpecEntry::UdtStructV0(s) => {
+                let name = s.name.to_string_lossy();
+                let fields = s.fields.iter().map(|f| f.name.to_string_lossy());
+                v.unique_members(&mut diagnostics, &name, fields);
+                let params = type_params(spec).unwrap_or_default();
+                for f in s.fields.iter() {
+                    let path = format!("{name}.{}", f.name.to_string_lossy());
+                    v.check_type(&mut diagnostics, &path, &params, &f.type_);
+                }
+            }
+            ScSpecEntry::UdtUnionV0(u) => {
+                let name = u.name.to_string_lossy();
+                let cases = u.cases.iter().map(|c| c.name.to_string_lossy());
+                v.unique_members(&mut diagnostics, &name, cases);
+                let params = type_params(spec).unwrap_or_default();
+                for c in u.cases.iter() {
+                    if let Some(t) = &c.type_ {
+                        let path = format!("{name}.{}", c.name.to_string_lossy());
+                        v.check_type(&mut diagnostics, &path, &params, t);
+                    }
+                }
+            }
+            ScSpecEntry::UdtEnumV0(e) => {
+                let name = e.name.to_string_lossy();
+                let cases = e.cases.iter().map(|c| c.name.to_string_lossy());
+                v.unique_members(&mut diagnostics, &name, cases);
+                let values = e.cases.iter().map(|c| (c.name.to_string_lossy(), c.value));
+                v.unique_values(&mut diagnostics, &name, values);
+            }
+            ScSpecEntry::UdtErrorEnumV0(e) => {
+                let name = e.name.to_string_lossy();
+                let cases = e.cases.iter().map(|c| c.name.to_string_lossy());
+                v.unique_members(&mut diagnostics, &name, cases);
+                let values = e.cases.iter().map(|c| (c.name.to_string_lossy(), c.value));
+                v.unique_values(&mut diagnostics, &name, values);
+            }
+        }
+    }
+
+    // Generic types are checked for containing themselves as the instances of
+    // them that are used.
+    let instances = instantiate(specs);
+    let mut instance_types = HashMap::new();
+    for i in &instances {
+        if let Some(name) = udt_entry_name(i) {
+            instance_types.entry(name).or_insert(i);
+        }
+    }
+    let instance_v = Validator {
+        types: &instance_types,
+    };
+    for name in instance_types.keys().sorted() {
+        if instance_v.is_recursive(name) {
+            diagnostics.push(Diagnostic::RecursiveType { name: name.clone() });
+        }
+    }
+
+    if diagnostics.is_empty() {
+        Ok(())
+    } else {
+        Err(diagnostics)
+    }
+}
+
+/// Records the type by name, returning the name and whether it is unique.
+/// Generic types are recorded by their name without their type parameters, so
+/// that they are in the same namespace as other types.
+fn udt_name<'a>(
+    types: &mut HashMap<String, &'a ScSpecEntry>,
+    name: String,
+    entry: &'a ScSpecEntry,
+) -> (String, bool) {
+    let key = UdtName::parse(&name).map_or_else(|| name.clone(), |n| n.key());
+    let unique = !types.contains_key(&key);
+    if unique {
+        types.insert(key, entry);
+    }
+    (name, unique)
+}
+
+fn udt_entry_name(entry: &ScSpecEntry) -> Option<String> {
+    match entry {
+        ScSpecEntry::FunctionV0(_) => None,
+        ScSpecEntry::UdtStructV0(s) => Some(s.name.to_string_lossy()),
+        ScSpecEntry::UdtUnionV0(u) => Some(u.name.to_string_lossy()),
+        ScSpecEntry::UdtEnumV0(e) => Some(e.name.to_string_lossy()),
+        ScSpecEntry::UdtErrorEnumV0(e) => Some(e.name.to_string_lossy()),
+    }
+}
+
+struct Validator<'a> {
+    types: &'a HashMap<String, &'a ScSpecEntry>,
+}
+
+impl Validator<'_> {
+    fn unique_members(
+        &self,
+        diagnostics: &mut Vec<Diagnostic>,
+        name: &str,
+        members: impl Iterator<Item = String>,
+    ) {
+        let mut seen = HashSet::new();
+        for m in members {
+            if !seen.insert(m.clone()) {
+                diagnostics.push(Diagnostic::DuplicateMember {
+                    path: format!("{name}.{m}"),
+                });
+            }
+        }
+    }
+
+    fn unique_values(
+        &self,
+        diagnostics: &mut Vec<Diagnostic>,
+        name: &str,
+        cases: impl Iterator<Item = (String, u32)>,
+    ) {
+        let mut seen = HashSet::new();
+        for (case, value) in cases {
+            if !seen.insert(value) {
+                diagnostics.push(Diagnostic::DuplicateValue {
+                    path: format!("{name}.{case}"),
+                    value,
+                });
+            }
+        }
+    }
+
+    /// Checks the type, where the type may refer to the type parameters of the
+    /// generic type it is part of.
+    fn check_type(
+        &self,
+        diagnostics: &mut Vec<Diagnostic>,
+        path: &str,
+        params: &[String],
+        t: &ScSpecTypeDef,
+    ) {
+        match t {
+            ScSpecTypeDef::Udt(u) => {
+                let name = u.name.to_string_lossy();
+                if params.contains(&name) {
+                    return;
+                }
+                let udt = UdtName::parse(&name);
+                let entry = udt.as_ref().and_then(|udt| self.types.get(&udt.key()));
+                match (udt, entry) {
+                    (Some(udt), Some(entry)) => {
+                        let expected = type_params(entry).map_or(0, |p| p.len());
+                        if udt.args.len() != expected {
+                            diagnostics.push(Diagnostic::TypeArguments {
+                                path: path.to_string(),
+                                name,
+                                expected,
+                            });
+                        }
+                        for arg in &udt.args {
+                            self.check_type(diagnostics, path, params, arg);
+                        }
+                    }
+                    _ => diagnostics.push(Diagnostic::UndefinedType {
+                        path: path.to_string(),
+                        name,
+                    }),
+                }
+            }
+            ScSpecTypeDef::Option(o) => self.check_type(diagnostics, path, params, &o.value_type),
+            ScSpecTypeDef::Result(r) => {
+                self.check_type(diagnostics, path, params, &r.ok_type);
+                self.check_type(diagnostics, path, params, &r.error_type);
+                let is_error = match r.error_type.as_ref() {
+                    ScSpecTypeDef::Status => true,
+                    ScSpecTypeDef::Udt(u) => match self.types.get(&u.name.to_string_lossy()) {
+                        Some(ScSpecEntry::UdtErrorEnumV0(_)) => true,
+                        Some(_) => false,
+                        // Reported as undefined above.
+                        None => true,
+                    },
+                    _ => false,
+                };
+                if !is_error {
+                    diagnostics.push(Diagnostic::ResultErrorType {
+                        path: path.to_string(),
+                        type_name: type_name(&r.error_type),
+                    });
+                }
+            }
+            ScSpecTypeDef::Vec(v) => self.check_type(diagnostics, path, params, &v.element_type),
+            ScSpecTypeDef::Set(s) => self.check_type(diagnostics, path, params, &s.element_type),
+            ScSpecTypeDef::Map(m) => {
+                self.check_type(diagnostics, path, params, &m.key_type);
+                self.check_type(diagnostics, path, params, &m.value_type);
+            }
+            ScSpecTypeDef::Tuple(t) => {
+                for t in t.value_types.iter() {
+                    self.check_type(diagnostics, path, params, t);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns true if the type contains itself other than within a `Vec`,
+    /// `Map`, or `Set`. Values in those types are stored in the host and
+    /// referenced by handle, so a type containing itself within them has a
+    /// finite size.
+    fn is_recursive(&self, name: &str) -> bool {
+        let mut seen = HashSet::new();
+        let mut stack = self.contained(name);
+        while let Some(n) = stack.pop() {
+            if n == name {
+                return true;
+            }
+            if seen.insert(n.clone()) {
+                stack.extend(self.contained(&n));
+            }
+        }
+        false
+    }
+
+    /// Returns the names of the types the type contains directly.
+    fn contained(&self, name: &str) -> Vec<String> {
+        let mut names = Vec::new();
+        match self.types.get(name) {
+            Some(ScSpecEntry::UdtStructV0(s)) => {
+                for f in s.fields.iter() {
+                    contained_in_type(&f.type_, &mut names);
+                }
+            }
+            Some(ScSpecEntry::UdtUnionV0(u)) => {
+                for t in u.cases.iter().filter_map(|c| c.type_.as_ref()) {
+                    contained_in_type(t, &mut names);
+                }
+            }
+            _ => {}
+        }
+        names
+    }
+}
+
+fn contained_in_type(t: &ScSpecTypeDef, names: &mut Vec<String>) {
+    match t {
+        ScSpecTypeDef::Udt(u) => names.push(u.name.to_string_lossy()),
+        ScSpecTypeDef::Option(o) => contained_in_type(&o.value_type, names),
+        ScSpecTypeDef::Result(r) => {
+            contained_in_type(&r.ok_type, names);
+            contained_in_type(&r.error_type, names);
+        }
+        ScSpecTypeDef::Tuple(t) => {
+            for t in t.value_types.iter() {
+                contained_in_type(t, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use stellar_xdr::{
+        ScSpecEntry, ScSpecTypeDef, ScSpecTypeOption, ScSpecTypeResult, ScSpecTypeUdt,
+    };
+
+    use super::{validate, Diagnostic};
+
+    const EXAMPLE_WASM: &[u8] =
+        include_bytes!("../../target/wasm32-unknown-unknown/release/test_udt.wasm");
+
+    fn udt(name: &str) -> ScSpecTypeDef {
+        ScSpecTypeDef::Udt(ScSpecTypeUdt {
+            name: name.try_into().unwrap(),
+        })
+    }
+
+    #[test]
+    fn test_valid() {
+        let entries = crate::read::from_wasm(EXAMPLE_WASM).unwrap();
+        assert_eq!(validate(&entries), Ok(()));
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut entries = crate::read::from_wasm(EXAMPLE_WASM).unwrap();
+        for e in entries.iter_mut() {
+            match e {
+                ScSpecEntry::FunctionV0(f) => {
+                    f.outputs = vec![ScSpecTypeDef::Result(Box::new(ScSpecTypeResult {
+                        ok_type: Box::new(ScSpecTypeDef::I64),
+                        error_type: Box::new(udt("UdtEnum2")),
+                    }))]
+                    .try_into()
+                    .unwrap();
+                }
+                ScSpecEntry::UdtEnumV0(e) => {
+                    let mut cases = e.cases.to_vec();
+                    cases[1].value = cases[0].value;
+                    e.cases = cases.try_into().unwrap();
+                }
+                ScSpecEntry::UdtStructV0(s) if s.name.to_string_lossy() == "UdtStruct" => {
+                    let mut fields = s.fields.to_vec();
+                    fields[0].type_ = udt("Missing");
+                    fields[1].type_ = ScSpecTypeDef::Option(Box::new(ScSpecTypeOption {
+                        value_type: Box::new(udt("UdtEnum")),
+                    }));
+                    s.fields = fields.try_into().unwrap();
+                }
+                _ => {}
+            }
+        }
+        entries.push(entries[0].clone());
+        assert_eq!(
+            validate(&entries),
+            Err(vec![
+                Diagnostic::DuplicateName {
+                    name: "UdtEnum2".to_string()
+                },
+                Diagnostic::DuplicateValue {
+                    path: "UdtEnum2.B".to_string(),
+                    value: 10
+                },
+                Diagnostic::UndefinedType {
+                    path: "UdtStruct.a".to_string(),
+                    name: "Missing".to_string()
+                },
+                Diagnostic::ResultErrorType {
+                    path: "add".to_string(),
+                    type_name: "UdtEnum2".to_string()
+                },
+                Diagnostic::DuplicateValue {
+                    path: "UdtEnum2.B".to_string(),
+                    value: 10
+                },
+                Diagnostic::RecursiveType {
+                    name: "UdtEnum".to_string()
+                },
+                Diagnostic::RecursiveType {
+                    name: "UdtStruct".to_string()
+                },
+            ])
+        );
+    }
+}
@@ -8,10 +8,12 @@ pub enum UdtEnum2 {
     B = 15,
 }
 
+/// A union of the other test types.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum UdtEnum {
     UdtA,
+    /// A struct value.
     UdtB(UdtStruct),
     UdtC(UdtEnum2),
     UdtD(UdtTuple),
@@ -21,11 +23,13 @@ pub enum UdtEnum {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct UdtTuple(pub i64, pub Vec<i64>);
 
+/// A struct with named fields.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct UdtStruct {
     a: i64,
     b: i64,
+    /// A vec of values.
     pub c: Vec<i64>,
 }
 
@@ -33,6 +37,10 @@ pub struct Contract;
 
 #[contractimpl]
 impl Contract {
+    /// Adds the values of the two UDTs.
+    ///
+    /// * `a` - The first value.
+    /// * `b` - The second value.
     pub fn add(a: UdtEnum, b: UdtEnum) -> i64 {
         let a = match a {
             UdtEnum::UdtA => 0,